@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Cache consulted by [`OgmiosHttpClient`](crate::OgmiosHttpClient) before sending a request,
+/// keyed by method and serialized params. Only successful responses are inserted.
+pub trait ResponseCache: Send + Sync {
+    /// Raw response body previously stored for this request, if still valid
+    fn get(&self, method: &str, params: &str) -> Option<String>;
+
+    fn insert(&self, method: &str, params: &str, response: String);
+}
+
+/// In-memory [`ResponseCache`] evicting the least recently used entry when full.
+/// Only methods given a TTL through [`InMemoryResponseCache::ttl`] are cached.
+#[derive(Debug)]
+pub struct InMemoryResponseCache {
+    capacity: usize,
+    ttls: HashMap<String, Duration>,
+    entries: Mutex<Entries>,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    map: HashMap<(String, String), Entry>,
+    /// Incremented on every access, to find the least recently used entry
+    clock: u64,
+}
+
+#[derive(Debug)]
+struct Entry {
+    response: String,
+    expires_at: Instant,
+    last_used: u64,
+}
+
+impl InMemoryResponseCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ttls: HashMap::new(),
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+
+    /// Caches responses to `method` for `ttl`
+    pub fn ttl(mut self, method: impl Into<String>, ttl: Duration) -> Self {
+        self.ttls.insert(method.into(), ttl);
+        self
+    }
+}
+
+impl ResponseCache for InMemoryResponseCache {
+    fn get(&self, method: &str, params: &str) -> Option<String> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.clock += 1;
+        let clock = entries.clock;
+        let key = (method.to_string(), params.to_string());
+        let entry = entries.map.get_mut(&key)?;
+        if entry.expires_at <= Instant::now() {
+            entries.map.remove(&key);
+            return None;
+        }
+        entry.last_used = clock;
+        Some(entry.response.clone())
+    }
+
+    fn insert(&self, method: &str, params: &str, response: String) {
+        let Some(ttl) = self.ttls.get(method) else {
+            return;
+        };
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        entries.clock += 1;
+        let key = (method.to_string(), params.to_string());
+        if !entries.map.contains_key(&key) && entries.map.len() >= self.capacity {
+            let oldest = entries
+                .map
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.map.remove(&oldest);
+            }
+        }
+        let entry = Entry {
+            response,
+            expires_at: Instant::now() + *ttl,
+            last_used: entries.clock,
+        };
+        entries.map.insert(key, entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_methods_with_ttl_are_cached() {
+        let cache = InMemoryResponseCache::new(10).ttl("a", Duration::from_secs(60));
+        cache.insert("a", "{}", "1".to_string());
+        cache.insert("b", "{}", "2".to_string());
+
+        assert_eq!(cache.get("a", "{}").as_deref(), Some("1"));
+        assert_eq!(cache.get("a", "[1]"), None);
+        assert_eq!(cache.get("b", "{}"), None);
+    }
+
+    #[test]
+    fn expired_entries_are_dropped() {
+        let cache = InMemoryResponseCache::new(10).ttl("a", Duration::ZERO);
+        cache.insert("a", "{}", "1".to_string());
+
+        assert_eq!(cache.get("a", "{}"), None);
+    }
+
+    #[test]
+    fn least_recently_used_entry_is_evicted() {
+        let cache = InMemoryResponseCache::new(2).ttl("a", Duration::from_secs(60));
+        cache.insert("a", "1", "1".to_string());
+        cache.insert("a", "2", "2".to_string());
+        cache.get("a", "1");
+        cache.insert("a", "3", "3".to_string());
+
+        assert_eq!(cache.get("a", "1").as_deref(), Some("1"));
+        assert_eq!(cache.get("a", "2"), None);
+        assert_eq!(cache.get("a", "3").as_deref(), Some("3"));
+    }
+}
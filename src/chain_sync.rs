@@ -0,0 +1,436 @@
+use std::cmp::Reverse;
+use std::time::Duration;
+
+use anyhow::Context;
+use futures_util::Stream;
+use reqwest::Url;
+
+use crate::method::chain_sync::{ChainSyncError, NextBlockResult, Point};
+use crate::method::tip::Tip;
+use crate::ws::{OgmiosWsClient, is_connection_lost};
+
+/// Slots a rollback can reach back: k = 2160 blocks span at most 3k/f = 129600 slots
+const POINT_WINDOW_SLOTS: u64 = 129_600;
+/// Most recent points kept regardless of spacing
+const DENSE_POINTS: usize = 8;
+/// Minimum slot distance between older points kept in the window
+const SPARSE_POINT_SPACING: u64 = 2_160;
+
+/// Chain-sync over an [`OgmiosWsClient`] that survives connection loss
+///
+/// A window of recent points, dense near the tip and sparse further back, is tracked as
+/// blocks come in. When the connection drops, the client reconnects, intersects with that
+/// window and keeps following the chain: if the intersection is the last applied point,
+/// the roll backward to it that Ogmios answers the first `nextBlock` with is not surfaced,
+/// since the consumer is already there. If the node rolled back past it in the meantime,
+/// the roll backward is surfaced.
+#[derive(Debug)]
+pub struct ResilientChainSync {
+    url: Url,
+    client: Option<OgmiosWsClient>,
+    /// Candidate intersection points, most recent first
+    points: Vec<Point>,
+    /// Set after reconnecting, the roll backward to this point is skipped
+    resumed_at: Option<Point>,
+    max_reconnect_attempts: usize,
+    reconnect_delay: Duration,
+}
+
+impl ResilientChainSync {
+    /// Connects and intersects at the most recent of `points` known to the node. `points` may
+    /// be given in any order.
+    pub async fn connect(url: Url, points: Vec<Point>) -> anyhow::Result<Self> {
+        let mut sync = Self {
+            url,
+            client: None,
+            points: most_recent_first(points),
+            resumed_at: None,
+            max_reconnect_attempts: 5,
+            reconnect_delay: Duration::from_secs(1),
+        };
+        sync.intersect().await?;
+        Ok(sync)
+    }
+
+    /// Consecutive reconnections attempted before giving up. Defaults to 5.
+    pub fn max_reconnect_attempts(mut self, attempts: usize) -> Self {
+        self.max_reconnect_attempts = attempts;
+        self
+    }
+
+    /// Delay before each reconnection attempt. Defaults to 1 second.
+    pub fn reconnect_delay(mut self, delay: Duration) -> Self {
+        self.reconnect_delay = delay;
+        self
+    }
+
+    /// The last point rolled forward or backward to, or the requested start points' best
+    /// candidate before any block was received
+    pub fn last_point(&self) -> Option<&Point> {
+        self.points.first()
+    }
+
+    /// Connects and finds the intersection with the tracked points
+    async fn intersect(&mut self) -> anyhow::Result<()> {
+        let mut client = OgmiosWsClient::connect(self.url.clone()).await?;
+        let intersection = Result::from(client.find_intersection(self.points.clone()).await?)
+            .context("Failed to find an intersection")?;
+        self.roll_to(intersection.intersection);
+        self.client = Some(client);
+        Ok(())
+    }
+
+    async fn reconnect(&mut self) -> anyhow::Result<()> {
+        self.client = None;
+        let last_point = self.points.first().cloned();
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            tokio::time::sleep(self.reconnect_delay).await;
+            match self.intersect().await {
+                Ok(()) => {
+                    self.resumed_at = last_point.filter(|p| self.points.first() == Some(p));
+                    return Ok(());
+                }
+                // The tracked points are gone from the node, retrying won't bring them back
+                Err(e)
+                    if matches!(
+                        e.downcast_ref::<ChainSyncError>(),
+                        Some(ChainSyncError::IntersectionNotFound { .. })
+                    ) =>
+                {
+                    return Err(e.context("Failed to reconnect chain-sync"));
+                }
+                Err(e) if attempts < self.max_reconnect_attempts => {
+                    tracing::warn!(
+                        "Failed to reconnect chain-sync (attempt {}): {:#}",
+                        attempts,
+                        e
+                    );
+                }
+                Err(e) => return Err(e.context("Failed to reconnect chain-sync")),
+            }
+        }
+    }
+
+    /// The next roll forward or backward, reconnecting if the connection was lost
+    pub async fn next(&mut self) -> anyhow::Result<NextBlockResult> {
+        loop {
+            let Some(client) = &mut self.client else {
+                self.reconnect().await?;
+                continue;
+            };
+            let response = match client.next_block().await {
+                Ok(response) => response,
+                Err(e) if is_connection_lost(&e) => {
+                    tracing::warn!("Chain-sync connection lost, reconnecting: {:#}", e);
+                    self.reconnect().await?;
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            let result = Result::from(response)?;
+
+            match &result {
+                NextBlockResult::Forward { block, .. } => {
+                    // Epoch boundary blocks have no slot, the previous point is kept
+                    if let Some(slot) = block.slot {
+                        self.roll_to(Tip::Point {
+                            slot,
+                            id: block.id.clone(),
+                        });
+                    }
+                }
+                NextBlockResult::Backward { point, .. } => {
+                    if self.resumed_at.take().as_ref() == Some(point) {
+                        continue;
+                    }
+                    self.roll_to(point.clone());
+                }
+            }
+            self.resumed_at = None;
+            return Ok(result);
+        }
+    }
+
+    /// Makes `point` the most recent one, dropping newer points and thinning the window
+    fn roll_to(&mut self, point: Point) {
+        let tip = slot_of(&point);
+        self.points.retain(|p| slot_of(p) < tip);
+        self.points.insert(0, point);
+
+        let mut kept = 0;
+        let mut last_kept = tip;
+        self.points.retain(|p| {
+            let slot = slot_of(p);
+            let keep = kept < DENSE_POINTS
+                || (tip - slot <= POINT_WINDOW_SLOTS && last_kept - slot >= SPARSE_POINT_SPACING);
+            if keep {
+                kept += 1;
+                last_kept = slot;
+            }
+            keep
+        });
+    }
+
+    /// Streams roll forwards and backwards, ending at the first unrecoverable error
+    pub fn into_stream(self) -> impl Stream<Item = anyhow::Result<NextBlockResult>> {
+        futures_util::stream::unfold(Some(self), |sync| async move {
+            let mut sync = sync?;
+            match sync.next().await {
+                Ok(result) => Some((Ok(result), Some(sync))),
+                Err(e) => Some((Err(e), None)),
+            }
+        })
+    }
+}
+
+/// Sorts `points` by descending slot and drops duplicates, as the point window expects
+fn most_recent_first(mut points: Vec<Point>) -> Vec<Point> {
+    points.sort_by_key(|point| Reverse(slot_of(point)));
+    points.dedup();
+    points
+}
+
+/// The origin sorts before every block
+fn slot_of(point: &Point) -> u64 {
+    match point {
+        Tip::Point { slot, .. } => *slot,
+        Tip::Origin => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use futures_util::{SinkExt, StreamExt};
+    use serde_json::{Value, json};
+    use tokio_tungstenite::tungstenite::Message;
+
+    use super::*;
+    use crate::test_utils::MockWsServer;
+
+    fn point(slot: u64) -> Value {
+        json!({ "slot": slot, "id": format!("{:064x}", slot) })
+    }
+
+    fn forward(slot: u64) -> Value {
+        json!({
+            "direction": "forward",
+            "block": {
+                "type": "praos",
+                "era": "conway",
+                "id": format!("{:064x}", slot),
+                "ancestor": format!("{:064x}", slot - 1),
+                "height": slot,
+                "slot": slot
+            },
+            "tip": point(10)
+        })
+    }
+
+    fn backward(to: Value) -> Value {
+        json!({ "direction": "backward", "point": to, "tip": point(10) })
+    }
+
+    #[tokio::test]
+    async fn resumes_from_last_point_after_connection_loss() {
+        let connections = Arc::new(AtomicUsize::new(0));
+        let intersections = Arc::new(Mutex::new(vec![]));
+        let server = {
+            let connections = connections.clone();
+            let intersections = intersections.clone();
+            MockWsServer::start(move |mut ws| {
+                let connection = connections.fetch_add(1, Ordering::SeqCst);
+                let intersections = intersections.clone();
+                async move {
+                    // The first connection serves two blocks then drops, the second resumes
+                    let mut blocks = match connection {
+                        0 => vec![backward(json!("origin")), forward(1), forward(2)],
+                        _ => vec![backward(point(2)), forward(3)],
+                    }
+                    .into_iter();
+                    while let Some(Ok(Message::Text(text))) = ws.next().await {
+                        let request: Value = serde_json::from_str(&text).unwrap();
+                        let result = match request["method"].as_str().unwrap() {
+                            "findIntersection" => {
+                                let points = request["params"]["points"].clone();
+                                intersections.lock().unwrap().push(points.clone());
+                                json!({ "intersection": points[0], "tip": point(10) })
+                            }
+                            _ => match blocks.next() {
+                                Some(block) => block,
+                                None => return,
+                            },
+                        };
+                        let response = json!({
+                            "jsonrpc": "2.0",
+                            "method": request["method"],
+                            "result": result,
+                            "id": request["id"],
+                        });
+                        ws.send(Message::Text(response.to_string().into()))
+                            .await
+                            .unwrap();
+                    }
+                }
+            })
+            .await
+        };
+
+        let sync = ResilientChainSync::connect(server.url(), vec![Tip::Origin])
+            .await
+            .unwrap()
+            .reconnect_delay(Duration::ZERO);
+        let results: Vec<_> = sync.into_stream().take(4).collect().await;
+        let points: Vec<_> = results
+            .into_iter()
+            .map(|result| match result.unwrap() {
+                NextBlockResult::Forward { block, .. } => format!("forward {}", block.height),
+                NextBlockResult::Backward { point, .. } => format!("backward {}", point),
+            })
+            .collect();
+
+        assert_eq!(
+            points,
+            ["backward origin", "forward 1", "forward 2", "forward 3"]
+        );
+        assert_eq!(
+            *intersections.lock().unwrap(),
+            [json!(["origin"]), json!([point(2), point(1), "origin"])]
+        );
+    }
+
+    #[tokio::test]
+    async fn gives_up_when_the_points_are_no_longer_on_chain() {
+        let connections = Arc::new(AtomicUsize::new(0));
+        let server = {
+            let connections = connections.clone();
+            MockWsServer::start(move |mut ws| {
+                let connection = connections.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    let mut blocks = vec![backward(json!("origin")), forward(1)].into_iter();
+                    while let Some(Ok(Message::Text(text))) = ws.next().await {
+                        let request: Value = serde_json::from_str(&text).unwrap();
+                        let response = match request["method"].as_str().unwrap() {
+                            "findIntersection" if connection > 0 => json!({
+                                "jsonrpc": "2.0",
+                                "method": "findIntersection",
+                                "error": {
+                                    "code": 1000,
+                                    "message": "No intersection found.",
+                                    "data": { "tip": "origin" }
+                                },
+                                "id": request["id"],
+                            }),
+                            "findIntersection" => json!({
+                                "jsonrpc": "2.0",
+                                "method": "findIntersection",
+                                "result": { "intersection": "origin", "tip": point(10) },
+                                "id": request["id"],
+                            }),
+                            _ => match blocks.next() {
+                                Some(block) => json!({
+                                    "jsonrpc": "2.0",
+                                    "method": "nextBlock",
+                                    "result": block,
+                                    "id": request["id"],
+                                }),
+                                None => return,
+                            },
+                        };
+                        ws.send(Message::Text(response.to_string().into()))
+                            .await
+                            .unwrap();
+                    }
+                }
+            })
+            .await
+        };
+
+        let mut sync = ResilientChainSync::connect(server.url(), vec![Tip::Origin])
+            .await
+            .unwrap()
+            .reconnect_delay(Duration::ZERO);
+        sync.next().await.unwrap();
+        sync.next().await.unwrap();
+        let error = sync.next().await.unwrap_err();
+
+        assert!(matches!(
+            error.downcast_ref::<ChainSyncError>(),
+            Some(ChainSyncError::IntersectionNotFound { .. })
+        ));
+        assert_eq!(connections.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn point_window_is_sparse_and_bounded() {
+        let mut sync = ResilientChainSync {
+            url: "ws://localhost".parse().unwrap(),
+            client: None,
+            points: vec![Tip::Origin],
+            resumed_at: None,
+            max_reconnect_attempts: 5,
+            reconnect_delay: Duration::ZERO,
+        };
+        let at = |slot: u64| Tip::Point {
+            slot,
+            id: format!("{:064x}", slot),
+        };
+        for slot in (1..=300_000).step_by(20) {
+            sync.roll_to(at(slot));
+        }
+
+        let slots: Vec<_> = sync.points.iter().map(slot_of).collect();
+        assert_eq!(
+            slots[..DENSE_POINTS],
+            [
+                299_981, 299_961, 299_941, 299_921, 299_901, 299_881, 299_861, 299_841
+            ]
+        );
+        assert!(
+            slots
+                .windows(2)
+                .skip(DENSE_POINTS)
+                .all(|w| w[0] - w[1] >= SPARSE_POINT_SPACING)
+        );
+        assert!(299_981 - slots.last().unwrap() <= POINT_WINDOW_SLOTS);
+        assert!(
+            slots.len() < DENSE_POINTS + (POINT_WINDOW_SLOTS / SPARSE_POINT_SPACING) as usize + 2
+        );
+
+        sync.roll_to(at(299_900));
+        assert_eq!(slot_of(&sync.points[0]), 299_900);
+        assert_eq!(slot_of(&sync.points[1]), 299_881);
+    }
+
+    #[test]
+    fn start_points_are_sorted_and_deduplicated() {
+        let at = |slot: u64| Tip::Point {
+            slot,
+            id: format!("{:064x}", slot),
+        };
+        let points: Vec<_> = [3, 40_000, 7, 12, 3, 1, 25_000, 9, 5, 30, 2, 11]
+            .into_iter()
+            .map(at)
+            .chain([Tip::Origin])
+            .collect();
+        let mut sync = ResilientChainSync {
+            url: "ws://localhost".parse().unwrap(),
+            client: None,
+            points: most_recent_first(points),
+            resumed_at: None,
+            max_reconnect_attempts: 5,
+            reconnect_delay: Duration::ZERO,
+        };
+
+        let slots: Vec<_> = sync.points.iter().map(slot_of).collect();
+        assert_eq!(slots, [40_000, 25_000, 30, 12, 11, 9, 7, 5, 3, 2, 1, 0]);
+
+        sync.roll_to(at(50_000));
+        let slots: Vec<_> = sync.points.iter().map(slot_of).collect();
+        assert_eq!(slots, [50_000, 40_000, 25_000, 30, 12, 11, 9, 7]);
+    }
+}
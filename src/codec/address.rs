@@ -0,0 +1,310 @@
+use std::borrow::Borrow;
+use std::fmt;
+use std::ops::Deref;
+
+use serde::{Deserialize, Serialize};
+
+use super::BECH32_CHARSET;
+use super::cbor::{Decoder, crc32};
+
+/// A Cardano address, as sent by Ogmios: either bech32 (`addr1...`, `addr_test1...`) for
+/// Shelley-era addresses, or base58 (`DdzFF...`, `Ae2...`) for legacy Byron addresses.
+/// Addresses are not validated on decode.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Address(pub String);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+}
+
+impl Address {
+    /// Whether this is a legacy Byron (bootstrap) address
+    pub fn is_byron(&self) -> bool {
+        self.byron_attributes().is_ok()
+    }
+
+    /// Protocol magic of a Byron address. Only testnet addresses carry one, so `None` is
+    /// returned for Byron mainnet addresses and any non-Byron address.
+    pub fn protocol_magic(&self) -> Option<u32> {
+        self.byron_attributes().ok().flatten()
+    }
+
+    /// Network the address belongs to, inferred from the bech32 prefix for Shelley addresses
+    /// and from the protocol magic attribute for Byron addresses
+    pub fn network(&self) -> Option<Network> {
+        if self.0.starts_with("addr_test1") {
+            Some(Network::Testnet)
+        } else if self.0.starts_with("addr1") {
+            Some(Network::Mainnet)
+        } else {
+            match self.byron_attributes() {
+                Ok(Some(_)) => Some(Network::Testnet),
+                Ok(None) => Some(Network::Mainnet),
+                Err(_) => None,
+            }
+        }
+    }
+
+    /// Raw bytes of the address, as found in transaction outputs
+    pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        if self.0.starts_with("addr") {
+            let (_, data) = bech32::decode(&self.0).map_err(|e| e.to_string())?;
+            Ok(data)
+        } else {
+            bs58::decode(&self.0).into_vec().map_err(|e| e.to_string())
+        }
+    }
+
+    /// Decodes a Byron address, returning its protocol magic attribute if any:
+    /// `[#6.24(bytes .cbor [root, attributes, type]), crc32]`
+    fn byron_attributes(&self) -> Result<Option<u32>, String> {
+        let bytes = bs58::decode(&self.0)
+            .into_vec()
+            .map_err(|e| e.to_string())?;
+        let mut decoder = Decoder::new(&bytes);
+        if decoder.array()? != 2 || decoder.tag()? != 24 {
+            return Err("not a Byron address".to_string());
+        }
+        let payload = decoder.bytes()?;
+        let checksum = decoder.unsigned()?;
+        if !decoder.is_empty() {
+            return Err("trailing bytes".to_string());
+        }
+        if checksum != crc32(payload) as u64 {
+            return Err("checksum mismatch".to_string());
+        }
+
+        let mut decoder = Decoder::new(payload);
+        if decoder.array()? != 3 {
+            return Err("expected [root, attributes, type]".to_string());
+        }
+        decoder.bytes()?;
+        let mut protocol_magic = None;
+        for _ in 0..decoder.map()? {
+            match decoder.unsigned()? {
+                2 => {
+                    let magic = Decoder::new(decoder.bytes()?).unsigned()?;
+                    protocol_magic =
+                        Some(u32::try_from(magic).map_err(|_| "protocol magic out of range")?);
+                }
+                _ => decoder.skip()?,
+            }
+        }
+        decoder.unsigned()?;
+        Ok(protocol_magic)
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<String> for Address {
+    fn from(address: String) -> Self {
+        Address(address)
+    }
+}
+
+impl From<&str> for Address {
+    fn from(address: &str) -> Self {
+        Address(address.to_string())
+    }
+}
+
+impl From<Address> for String {
+    fn from(address: Address) -> Self {
+        address.0
+    }
+}
+
+impl Deref for Address {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Address {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<&str> for Address {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+/// A reward (stake) account, bech32-encoded (`stake1...` or `stake_test1...`), or as a hex-encoded
+/// 28-byte credential hash. Only the shape is validated on decode, not the bech32 checksum.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct RewardAccount(pub String);
+
+impl RewardAccount {
+    /// Network of a bech32-encoded account, `None` for a bare credential hash
+    pub fn network(&self) -> Option<Network> {
+        if self.0.starts_with("stake_test1") {
+            Some(Network::Testnet)
+        } else if self.0.starts_with("stake1") {
+            Some(Network::Mainnet)
+        } else {
+            None
+        }
+    }
+}
+
+impl TryFrom<String> for RewardAccount {
+    type Error = String;
+
+    fn try_from(account: String) -> Result<Self, Self::Error> {
+        let is_bech32 = account
+            .strip_prefix("stake_test1")
+            .or_else(|| account.strip_prefix("stake1"))
+            .is_some_and(|data| {
+                data.len() >= 6 && data.chars().all(|c| BECH32_CHARSET.contains(c))
+            });
+        let is_hex = account.len() == 56 && account.chars().all(|c| c.is_ascii_hexdigit());
+        if is_bech32 || is_hex {
+            Ok(RewardAccount(account))
+        } else {
+            Err(format!("invalid reward account '{}'", account))
+        }
+    }
+}
+
+impl From<RewardAccount> for String {
+    fn from(account: RewardAccount) -> Self {
+        account.0
+    }
+}
+
+impl fmt::Display for RewardAccount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Deref for RewardAccount {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl PartialEq<&str> for RewardAccount {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byron_mainnet_address() {
+        let address = Address::from(
+            "DdzFFzCqrht8mbSTZHqpM2u4HeND2mdspsaBhdQ1BowPJBMzbDeBMeKgqdoKqo1D4sdPusEdZJVrFJRBBxX1jUEofNDYCJSZLg8MkyCE",
+        );
+        assert!(address.is_byron());
+        assert_eq!(address.protocol_magic(), None);
+        assert_eq!(address.network(), Some(Network::Mainnet));
+    }
+
+    #[test]
+    fn byron_icarus_mainnet_address() {
+        let address = Address::from("Ae2tdPwUPEZFRbyhz3cpfC2CumGzNkFBN2L42rcUc2yjQpEkxDbkPodpMAi");
+        assert!(address.is_byron());
+        assert_eq!(address.network(), Some(Network::Mainnet));
+    }
+
+    #[test]
+    fn byron_testnet_address() {
+        let address = Address::from(
+            "37btjrVyb4KDXBNC4haBVPCrro8AQPHwvCMp3RFhhSVWwfFmZ6wwzSK6JK1hY6wHNmtrpTf1kdbva8TCneM2YsiXT7mrzT21EacHnPpz5YyUdj64na",
+        );
+        assert!(address.is_byron());
+        assert_eq!(address.protocol_magic(), Some(1097911063));
+        assert_eq!(address.network(), Some(Network::Testnet));
+    }
+
+    #[test]
+    fn shelley_addresses() {
+        let testnet =
+            Address::from("addr_test1vz09v9yfxguvlp0zsnrpa3tdtm7el8xufp3m5lsm7qxzclgmzkket");
+        assert!(!testnet.is_byron());
+        assert_eq!(testnet.protocol_magic(), None);
+        assert_eq!(testnet.network(), Some(Network::Testnet));
+
+        let mainnet = Address::from(
+            "addr1q9d34spgg2kdy47n82e7x9pdd6vql6d2engxmpj20jmhuc2047yqd4xnh7u6u5jp4t0q3fkxzckph4tgnzvamlu7k5psuahzcp",
+        );
+        assert!(!mainnet.is_byron());
+        assert_eq!(mainnet.network(), Some(Network::Mainnet));
+    }
+
+    #[test]
+    fn address_bytes() {
+        let shelley =
+            Address::from("addr_test1vz09v9yfxguvlp0zsnrpa3tdtm7el8xufp3m5lsm7qxzclgmzkket");
+        let bytes = shelley.to_bytes().unwrap();
+        // Enterprise address header on testnet, followed by a 28-byte key hash
+        assert_eq!(bytes.len(), 29);
+        assert_eq!(bytes[0], 0x60);
+
+        let byron = Address::from("Ae2tdPwUPEZFRbyhz3cpfC2CumGzNkFBN2L42rcUc2yjQpEkxDbkPodpMAi");
+        let bytes = byron.to_bytes().unwrap();
+        assert_eq!(&bytes[..2], &[0x82, 0xd8]);
+
+        assert!(Address::from("addr_test1invalid").to_bytes().is_err());
+    }
+
+    #[test]
+    fn corrupted_byron_address() {
+        let address = Address::from(
+            "DdzFFzCqrht8mbSTZHqpM2u4HeND2mdspsaBhdQ1BowPJBMzbDeBMeKgqdoKqo1D4sdPusEdZJVrFJRBBxX1jUEofNDYCJSZLg8MkyCF",
+        );
+        assert!(!address.is_byron());
+        assert_eq!(address.network(), None);
+    }
+
+    #[test]
+    fn deserialize_as_string() {
+        let address: Address = serde_json::from_str(
+            "\"addr_test1vz09v9yfxguvlp0zsnrpa3tdtm7el8xufp3m5lsm7qxzclgmzkket\"",
+        )
+        .unwrap();
+        assert_eq!(
+            address,
+            "addr_test1vz09v9yfxguvlp0zsnrpa3tdtm7el8xufp3m5lsm7qxzclgmzkket"
+        );
+    }
+
+    #[test]
+    fn reward_account_formats() {
+        let mainnet: RewardAccount =
+            serde_json::from_str("\"stake1uyehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gh6ffgw\"")
+                .unwrap();
+        assert_eq!(mainnet.network(), Some(Network::Mainnet));
+        let testnet: RewardAccount = serde_json::from_str(
+            "\"stake_test1uqfu74w3wh4gfzu8m6e7j987h4lq9r3t7ef5gaw497uu85qsqfy27\"",
+        )
+        .unwrap();
+        assert_eq!(testnet.network(), Some(Network::Testnet));
+        let hex: RewardAccount =
+            serde_json::from_value(serde_json::json!("ab".repeat(28))).unwrap();
+        assert_eq!(hex.network(), None);
+
+        assert!(serde_json::from_str::<RewardAccount>("\"pool1abc\"").is_err());
+        assert!(serde_json::from_str::<RewardAccount>("\"stake1INVALID\"").is_err());
+    }
+}
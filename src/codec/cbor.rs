@@ -0,0 +1,409 @@
+//! A minimal CBOR reader and writer, covering what the crate needs to inspect locally
+//! (addresses, transaction envelopes) and to encode UTxOs, without pulling in a full CBOR
+//! implementation. Indefinite-length arrays and maps can be read through
+//! [`Decoder::array_len`] and [`Decoder::map_len`], and any indefinite-length item can be
+//! skipped, but byte and text strings are only read when definite.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Major {
+    Unsigned,
+    Negative,
+    Bytes,
+    Text,
+    Array,
+    Map,
+    Tag,
+    Simple,
+}
+
+impl Major {
+    fn bits(self) -> u8 {
+        match self {
+            Major::Unsigned => 0,
+            Major::Negative => 1,
+            Major::Bytes => 2,
+            Major::Text => 3,
+            Major::Array => 4,
+            Major::Map => 5,
+            Major::Tag => 6,
+            Major::Simple => 7,
+        }
+    }
+
+    fn from_bits(bits: u8) -> Self {
+        match bits {
+            0 => Major::Unsigned,
+            1 => Major::Negative,
+            2 => Major::Bytes,
+            3 => Major::Text,
+            4 => Major::Array,
+            5 => Major::Map,
+            6 => Major::Tag,
+            _ => Major::Simple,
+        }
+    }
+}
+
+pub(crate) struct Decoder<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Decoder<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| format!("unexpected end of input at byte {}", self.pos))?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    /// Reads the initial byte and its argument, `None` for the length of an indefinite-length
+    /// item
+    pub(crate) fn initial(&mut self) -> Result<(Major, Option<u64>), String> {
+        let initial = self.take(1)?[0];
+        let major = Major::from_bits(initial >> 5);
+        let argument = match initial & 0x1f {
+            n @ 0..=23 => n as u64,
+            24 => self.take(1)?[0] as u64,
+            25 => u16::from_be_bytes(self.take(2)?.try_into().unwrap()) as u64,
+            26 => u32::from_be_bytes(self.take(4)?.try_into().unwrap()) as u64,
+            27 => u64::from_be_bytes(self.take(8)?.try_into().unwrap()),
+            31 if matches!(
+                major,
+                Major::Bytes | Major::Text | Major::Array | Major::Map
+            ) =>
+            {
+                return Ok((major, None));
+            }
+            31 if major == Major::Simple => {
+                return Err(format!("unexpected break at byte {}", self.pos - 1));
+            }
+            n => return Err(format!("unsupported additional information {}", n)),
+        };
+        Ok((major, Some(argument)))
+    }
+
+    /// Reads the initial byte and its argument
+    pub(crate) fn header(&mut self) -> Result<(Major, u64), String> {
+        match self.initial()? {
+            (major, Some(argument)) => Ok((major, argument)),
+            (major, None) => Err(format!("unexpected indefinite-length {:?}", major)),
+        }
+    }
+
+    /// Consumes a break, returning whether the next byte was one
+    pub(crate) fn at_break(&mut self) -> bool {
+        let is_break = self.bytes.get(self.pos) == Some(&0xff);
+        if is_break {
+            self.pos += 1;
+        }
+        is_break
+    }
+
+    fn expect(&mut self, expected: Major) -> Result<u64, String> {
+        let (major, argument) = self.header()?;
+        if major != expected {
+            return Err(format!("expected {:?}, got {:?}", expected, major));
+        }
+        Ok(argument)
+    }
+
+    pub(crate) fn unsigned(&mut self) -> Result<u64, String> {
+        self.expect(Major::Unsigned)
+    }
+
+    pub(crate) fn bytes(&mut self) -> Result<&'a [u8], String> {
+        let len = self.expect(Major::Bytes)?;
+        self.take(len as usize)
+    }
+
+    pub(crate) fn array(&mut self) -> Result<u64, String> {
+        self.expect(Major::Array)
+    }
+
+    /// Length of an array, `None` when it's indefinite and ends with a break
+    pub(crate) fn array_len(&mut self) -> Result<Option<u64>, String> {
+        self.expect_len(Major::Array)
+    }
+
+    pub(crate) fn map(&mut self) -> Result<u64, String> {
+        self.expect(Major::Map)
+    }
+
+    /// Number of entries in a map, `None` when it's indefinite and ends with a break
+    pub(crate) fn map_len(&mut self) -> Result<Option<u64>, String> {
+        self.expect_len(Major::Map)
+    }
+
+    fn expect_len(&mut self, expected: Major) -> Result<Option<u64>, String> {
+        let (major, len) = self.initial()?;
+        if major != expected {
+            return Err(format!("expected {:?}, got {:?}", expected, major));
+        }
+        Ok(len)
+    }
+
+    /// Whether another item (or map entry) of a container of length `len` follows, counting
+    /// `len` down for a definite container and consuming the break of an indefinite one
+    pub(crate) fn next_item(&mut self, len: &mut Option<u64>) -> bool {
+        match len {
+            Some(0) => false,
+            Some(n) => {
+                *n -= 1;
+                true
+            }
+            None => !self.at_break(),
+        }
+    }
+
+    pub(crate) fn tag(&mut self) -> Result<u64, String> {
+        self.expect(Major::Tag)
+    }
+
+    /// Skips over the next item, including any nested items. Counts the items left to skip
+    /// rather than recursing, so deeply nested input can't overflow the stack: one count per
+    /// enclosing indefinite-length item, which also has to end with a break.
+    pub(crate) fn skip(&mut self) -> Result<(), String> {
+        // (items left, whether a break ends the item)
+        let mut frames = vec![(1u64, false)];
+        while let Some((remaining, indefinite)) = frames.last_mut() {
+            if *remaining == 0 {
+                if !*indefinite || self.at_break() {
+                    frames.pop();
+                    continue;
+                }
+                // Another item of an indefinite-length item
+                *remaining = 1;
+            }
+            *remaining -= 1;
+            let nested = match self.initial()? {
+                (_, None) => {
+                    frames.push((0, true));
+                    continue;
+                }
+                (Major::Unsigned | Major::Negative | Major::Simple, _) => 0,
+                (Major::Bytes | Major::Text, Some(len)) => {
+                    self.take(len as usize)?;
+                    0
+                }
+                (Major::Array, Some(len)) => len,
+                (Major::Map, Some(len)) => len
+                    .checked_mul(2)
+                    .ok_or_else(|| format!("map length {} out of range", len))?,
+                (Major::Tag, _) => 1,
+            };
+            let (remaining, _) = frames.last_mut().unwrap();
+            *remaining = remaining
+                .checked_add(nested)
+                .ok_or_else(|| format!("too many items at byte {}", self.pos))?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes items with the shortest encoding of their arguments
+#[derive(Default)]
+pub(crate) struct Encoder {
+    bytes: Vec<u8>,
+}
+
+impl Encoder {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Writes the initial byte and its argument
+    pub(crate) fn header(&mut self, major: Major, argument: u64) -> &mut Self {
+        let major = major.bits() << 5;
+        match argument {
+            0..=23 => self.bytes.push(major | argument as u8),
+            24..=0xff => self.bytes.extend([major | 24, argument as u8]),
+            0x100..=0xffff => {
+                self.bytes.push(major | 25);
+                self.bytes.extend((argument as u16).to_be_bytes());
+            }
+            0x1_0000..=0xffff_ffff => {
+                self.bytes.push(major | 26);
+                self.bytes.extend((argument as u32).to_be_bytes());
+            }
+            _ => {
+                self.bytes.push(major | 27);
+                self.bytes.extend(argument.to_be_bytes());
+            }
+        }
+        self
+    }
+
+    pub(crate) fn unsigned(&mut self, value: u64) -> &mut Self {
+        self.header(Major::Unsigned, value)
+    }
+
+    pub(crate) fn bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        self.header(Major::Bytes, bytes.len() as u64);
+        self.bytes.extend_from_slice(bytes);
+        self
+    }
+
+    pub(crate) fn array(&mut self, len: u64) -> &mut Self {
+        self.header(Major::Array, len)
+    }
+
+    pub(crate) fn map(&mut self, len: u64) -> &mut Self {
+        self.header(Major::Map, len)
+    }
+
+    pub(crate) fn tag(&mut self, tag: u64) -> &mut Self {
+        self.header(Major::Tag, tag)
+    }
+
+    /// Appends an already encoded item
+    pub(crate) fn raw(&mut self, item: &[u8]) -> &mut Self {
+        self.bytes.extend_from_slice(item);
+        self
+    }
+}
+
+/// CRC-32 (IEEE), as used by Byron address checksums
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_nested_items() {
+        // [24(h'01'), {2: 5}, 1000]
+        let bytes = hex::decode("83d81841 01a10205 1903e8".replace(' ', "")).unwrap();
+        let mut decoder = Decoder::new(&bytes);
+        assert_eq!(decoder.array().unwrap(), 3);
+        assert_eq!(decoder.tag().unwrap(), 24);
+        assert_eq!(decoder.bytes().unwrap(), &[0x01]);
+        assert_eq!(decoder.map().unwrap(), 1);
+        assert_eq!(decoder.unsigned().unwrap(), 2);
+        decoder.skip().unwrap();
+        assert_eq!(decoder.unsigned().unwrap(), 1000);
+        assert!(decoder.is_empty());
+    }
+
+    #[test]
+    fn write_read_round_trip() {
+        let mut encoder = Encoder::new();
+        encoder
+            .array(3)
+            .tag(24)
+            .bytes(&[0x01])
+            .map(1)
+            .unsigned(2)
+            .unsigned(5)
+            .unsigned(1000);
+        assert_eq!(
+            hex::encode(encoder.into_bytes()),
+            "83d81841 01a10205 1903e8".replace(' ', "")
+        );
+
+        let mut encoder = Encoder::new();
+        encoder
+            .unsigned(23)
+            .unsigned(24)
+            .unsigned(u32::MAX as u64 + 1);
+        assert_eq!(
+            hex::encode(encoder.into_bytes()),
+            "17 1818 1b0000000100000000".replace(' ', "")
+        );
+    }
+
+    #[test]
+    fn reject_truncated_input() {
+        let mut decoder = Decoder::new(&[0x42, 0x01]);
+        assert!(decoder.bytes().is_err());
+    }
+
+    #[test]
+    fn skip_deeply_nested_input() {
+        // [[[ ... 0 ... ]]]
+        let mut bytes = vec![0x81; 1_000_000];
+        bytes.push(0x00);
+        let mut decoder = Decoder::new(&bytes);
+        decoder.skip().unwrap();
+        assert!(decoder.is_empty());
+
+        let mut decoder = Decoder::new(&bytes[..1_000_000]);
+        assert!(decoder.skip().is_err());
+    }
+
+    #[test]
+    fn read_indefinite_items() {
+        // [_ {_ 1: [_ ]}, 2]
+        let bytes = hex::decode("9f bf01 9fff ff 02 ff".replace(' ', "")).unwrap();
+        let mut decoder = Decoder::new(&bytes);
+        let mut len = decoder.array_len().unwrap();
+        assert_eq!(len, None);
+        assert!(decoder.next_item(&mut len));
+        let mut entries = decoder.map_len().unwrap();
+        assert!(decoder.next_item(&mut entries));
+        assert_eq!(decoder.unsigned().unwrap(), 1);
+        decoder.skip().unwrap();
+        assert!(!decoder.next_item(&mut entries));
+        assert!(decoder.next_item(&mut len));
+        assert_eq!(decoder.unsigned().unwrap(), 2);
+        assert!(!decoder.next_item(&mut len));
+        assert!(decoder.is_empty());
+
+        let mut len = Some(1);
+        assert!(decoder.next_item(&mut len));
+        assert!(!decoder.next_item(&mut len));
+    }
+
+    #[test]
+    fn skip_indefinite_items() {
+        // [_ h'01', (_ h'02', h'03'), [4, [_ 5]], {_ 6: 7}], 8
+        let bytes =
+            hex::decode("9f 4101 5f41024103ff 82 04 9f05ff bf0607ff ff 08".replace(' ', ""))
+                .unwrap();
+        let mut decoder = Decoder::new(&bytes);
+        decoder.skip().unwrap();
+        assert_eq!(decoder.unsigned().unwrap(), 8);
+        assert!(decoder.is_empty());
+
+        // Missing break
+        let mut decoder = Decoder::new(&bytes[..bytes.len() - 2]);
+        assert!(decoder.skip().is_err());
+        // Stray break
+        assert!(Decoder::new(&[0xff]).skip().is_err());
+        assert!(Decoder::new(&[0x9f, 0xff]).array().is_err());
+    }
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+}
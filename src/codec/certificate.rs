@@ -0,0 +1,211 @@
+use serde::Deserialize;
+
+use super::{AdaBalance, Anchor, CredentialOrigin, EpochNo, Ratio, RewardAccount, StakePoolId};
+
+/// A delegation certificate, as Ogmios reports them in transactions and script purposes.
+/// Certificate types not modeled here decode as [`Certificate::Other`].
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum Certificate {
+    /// Delegation of a stake credential to a stake pool, a delegate representative, or both
+    StakeDelegation {
+        /// Hex-encoded 28-byte blake2b hash digest of the stake credential
+        credential: String,
+        from: CredentialOrigin,
+        #[serde(default)]
+        stake_pool: Option<StakePoolId>,
+        #[serde(default)]
+        delegate_representative: Option<DelegateRepresentative>,
+    },
+    StakeCredentialRegistration {
+        /// Hex-encoded 28-byte blake2b hash digest of the stake credential
+        credential: String,
+        from: CredentialOrigin,
+        /// Only explicit in certificates introduced in Conway
+        #[serde(default)]
+        deposit: Option<AdaBalance>,
+    },
+    StakeCredentialDeregistration {
+        /// Hex-encoded 28-byte blake2b hash digest of the stake credential
+        credential: String,
+        from: CredentialOrigin,
+        /// Only explicit in certificates introduced in Conway
+        #[serde(default)]
+        deposit: Option<AdaBalance>,
+    },
+    StakePoolRegistration {
+        stake_pool: StakePoolParameters,
+    },
+    StakePoolRetirement {
+        stake_pool: StakePoolRetirement,
+    },
+    DelegateRepresentativeRegistration {
+        delegate_representative: DelegateRepresentative,
+        deposit: AdaBalance,
+        #[serde(default)]
+        anchor: Option<Anchor>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum DelegateRepresentative {
+    Registered {
+        /// Hex-encoded 28-byte blake2b hash digest of the credential
+        id: String,
+        from: CredentialOrigin,
+    },
+    Abstain,
+    NoConfidence,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakePoolRetirement {
+    /// Bech32-encoded pool id (pool1...)
+    pub id: String,
+    pub retirement_epoch: EpochNo,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakePoolParameters {
+    /// Bech32-encoded pool id (pool1...)
+    pub id: String,
+    /// Hex-encoded 32-byte blake2b hash digest of the pool's VRF verification key
+    pub vrf_verification_key_hash: String,
+    /// Hex-encoded 28-byte blake2b hash digests of the owners' stake keys
+    pub owners: Vec<String>,
+    pub cost: AdaBalance,
+    pub margin: Ratio,
+    pub pledge: AdaBalance,
+    pub reward_account: RewardAccount,
+    #[serde(default)]
+    pub metadata: Option<Anchor>,
+    #[serde(default)]
+    pub relays: Vec<Relay>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Relay {
+    IpAddress {
+        #[serde(default)]
+        ipv4: Option<String>,
+        #[serde(default)]
+        ipv6: Option<String>,
+        #[serde(default)]
+        port: Option<u16>,
+    },
+    /// Without a port, the hostname is resolved through DNS SRV records
+    Hostname {
+        hostname: String,
+        #[serde(default)]
+        port: Option<u16>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn deserialize_stake_delegation() {
+        let certificate: Certificate = serde_json::from_value(json!({
+            "type": "stakeDelegation",
+            "credential": "aa".repeat(28),
+            "from": "script",
+            "stakePool": { "id": "pool1pu5jlj4q9w9jlxeu370a3c9myx47md5j5m2str0naunn2q3lkdy" },
+            "delegateRepresentative": { "type": "abstain" }
+        }))
+        .unwrap();
+        let Certificate::StakeDelegation {
+            credential,
+            from,
+            stake_pool,
+            delegate_representative,
+        } = certificate
+        else {
+            panic!("Expected a stake delegation, got {:?}", certificate);
+        };
+        assert_eq!(credential, "aa".repeat(28));
+        assert_eq!(from, CredentialOrigin::Script);
+        assert_eq!(
+            stake_pool.unwrap().id,
+            "pool1pu5jlj4q9w9jlxeu370a3c9myx47md5j5m2str0naunn2q3lkdy"
+        );
+        assert_eq!(
+            delegate_representative,
+            Some(DelegateRepresentative::Abstain)
+        );
+    }
+
+    #[test]
+    fn deserialize_stake_pool_retirement() {
+        let certificate: Certificate = serde_json::from_value(json!({
+            "type": "stakePoolRetirement",
+            "stakePool": {
+                "retirementEpoch": 530,
+                "id": "pool1pu5jlj4q9w9jlxeu370a3c9myx47md5j5m2str0naunn2q3lkdy"
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            certificate,
+            Certificate::StakePoolRetirement {
+                stake_pool: StakePoolRetirement {
+                    id: "pool1pu5jlj4q9w9jlxeu370a3c9myx47md5j5m2str0naunn2q3lkdy".to_string(),
+                    retirement_epoch: EpochNo(530),
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_delegate_representative_registration() {
+        let certificate: Certificate = serde_json::from_value(json!({
+            "type": "delegateRepresentativeRegistration",
+            "delegateRepresentative": { "type": "registered", "id": "bb".repeat(28), "from": "verificationKey" },
+            "deposit": { "ada": { "lovelace": 500000000 } },
+            "anchor": null
+        }))
+        .unwrap();
+        let Certificate::DelegateRepresentativeRegistration {
+            delegate_representative,
+            deposit,
+            anchor,
+        } = certificate
+        else {
+            panic!("Expected a DRep registration, got {:?}", certificate);
+        };
+        assert_eq!(
+            delegate_representative,
+            DelegateRepresentative::Registered {
+                id: "bb".repeat(28),
+                from: CredentialOrigin::VerificationKey,
+            }
+        );
+        assert_eq!(deposit.lovelace, 500000000);
+        assert_eq!(anchor, None);
+    }
+
+    #[test]
+    fn other_certificates() {
+        let certificate: Certificate = serde_json::from_value(json!({
+            "type": "genesisDelegation",
+            "delegate": { "id": "cc".repeat(28) },
+            "issuer": { "id": "dd".repeat(28) },
+            "vrfVerificationKeyHash": "ee".repeat(32)
+        }))
+        .unwrap();
+        assert_eq!(certificate, Certificate::Other);
+    }
+}
@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+
+use reqwest::Url;
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{
+    AdaBalance, CredentialOrigin, Hash32, ProtocolVersion, Ratio, RewardAccount, TxOutputPointer,
+};
+
+/// Error parsing an [`Anchor`] url, re-exported from the `url` crate
+pub type UrlParseError = <Url as std::str::FromStr>::Err;
+
+/// Off-chain metadata location along with the hash of its content, as found in constitutions,
+/// governance proposals, delegate representative registrations and stake pool registrations
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Anchor {
+    /// Free text on chain, only its length is enforced by the ledger, see [`Self::parsed_url`]
+    pub url: String,
+    /// Blake2b-256 hash digest of the content
+    pub hash: Hash32,
+}
+
+impl Anchor {
+    /// The url parsed, failing for the malformed or scheme-less urls the ledger lets through
+    pub fn parsed_url(&self) -> Result<Url, UrlParseError> {
+        Url::parse(&self.url)
+    }
+
+    /// Whether `content`, as fetched from the anchor's url, matches the anchored hash
+    pub fn verify(&self, content: &[u8]) -> bool {
+        let digest = blake2b_simd::Params::new().hash_length(32).hash(content);
+        digest.to_hex().eq_ignore_ascii_case(&self.hash)
+    }
+}
+
+/// Identifies a governance proposal by the transaction submitting it and its index therein
+pub type GovernanceProposalReference = TxOutputPointer;
+
+/// Script constraining protocol parameter updates and treasury withdrawals
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Guardrails {
+    /// Hex-encoded 28-byte blake2b hash digest of the script
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ProposedConstitution {
+    pub metadata: Anchor,
+    #[serde(default)]
+    pub guardrails: Option<Guardrails>,
+}
+
+/// A Conway governance action. `ancestor` is the last enacted action of the same purpose,
+/// `None` when there is none yet.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum GovernanceAction {
+    #[serde(rename = "protocolParametersUpdate")]
+    ParameterChange {
+        #[serde(default)]
+        ancestor: Option<GovernanceProposalReference>,
+        /// Only the updated parameters are present, keyed by their Ogmios name
+        parameters: HashMap<String, Value>,
+        #[serde(default)]
+        guardrails: Option<Guardrails>,
+    },
+    HardForkInitiation {
+        #[serde(default)]
+        ancestor: Option<GovernanceProposalReference>,
+        version: ProtocolVersion,
+    },
+    TreasuryWithdrawals {
+        withdrawals: HashMap<RewardAccount, AdaBalance>,
+        #[serde(default)]
+        guardrails: Option<Guardrails>,
+    },
+    NoConfidence {
+        #[serde(default)]
+        ancestor: Option<GovernanceProposalReference>,
+    },
+    #[serde(rename = "constitutionalCommittee")]
+    UpdateCommittee {
+        #[serde(default)]
+        ancestor: Option<GovernanceProposalReference>,
+        /// Members added and removed, in Ogmios' shape
+        members: Value,
+        quorum: Ratio,
+    },
+    #[serde(rename = "constitution")]
+    NewConstitution {
+        #[serde(default)]
+        ancestor: Option<GovernanceProposalReference>,
+        constitution: ProposedConstitution,
+    },
+    #[serde(rename = "information")]
+    Info,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VoterRole {
+    ConstitutionalCommittee,
+    DelegateRepresentative,
+    StakePoolOperator,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Voter {
+    pub role: VoterRole,
+    /// Hex-encoded 28-byte blake2b hash digest of the voter's credential or pool id
+    pub id: String,
+    /// Absent for stake pool operators, which always vote with their key
+    #[serde(default)]
+    pub from: Option<CredentialOrigin>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Vote {
+    Yes,
+    No,
+    Abstain,
+}
+
+/// A vote cast on a governance proposal
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct GovernanceVote {
+    pub issuer: Voter,
+    pub vote: Vote,
+}
+
+/// A vote that was rejected, along with the proposal it was cast on
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RejectedVote {
+    pub voter: Voter,
+    pub proposal: GovernanceProposalReference,
+}
+
+/// A governance action as proposed, with its deposit and the account the deposit returns to
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GovernanceProposal {
+    pub deposit: AdaBalance,
+    pub return_account: RewardAccount,
+    pub metadata: Anchor,
+    pub action: GovernanceAction,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn deserialize_treasury_withdrawals() {
+        let json = json!({
+            "type": "treasuryWithdrawals",
+            "withdrawals": {
+                "stake1u9x9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m": {
+                    "ada": { "lovelace": 42000000 }
+                }
+            },
+            "guardrails": { "hash": "fa24fb305126805cf2164c161d852a0e7330cf988f1fe558cf7d4a64" }
+        });
+        let action: GovernanceAction = serde_json::from_value(json).unwrap();
+        let GovernanceAction::TreasuryWithdrawals {
+            withdrawals,
+            guardrails,
+        } = action
+        else {
+            panic!("Expected treasury withdrawals, got {:?}", action);
+        };
+        let account = RewardAccount::try_from(
+            "stake1u9x9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m".to_string(),
+        )
+        .unwrap();
+        assert_eq!(withdrawals[&account].lovelace, 42000000);
+        assert!(guardrails.is_some());
+    }
+
+    #[test]
+    fn deserialize_parameter_change() {
+        let json = json!({
+            "type": "protocolParametersUpdate",
+            "ancestor": {
+                "transaction": { "id": "ee".repeat(32) },
+                "index": 0
+            },
+            "parameters": { "maxBlockBodySize": { "bytes": 90112 } },
+            "guardrails": null
+        });
+        let action: GovernanceAction = serde_json::from_value(json).unwrap();
+        let GovernanceAction::ParameterChange {
+            ancestor,
+            parameters,
+            guardrails,
+        } = action
+        else {
+            panic!("Expected a parameter change, got {:?}", action);
+        };
+        assert_eq!(ancestor.unwrap().transaction.id, "ee".repeat(32));
+        assert_eq!(parameters["maxBlockBodySize"], json!({ "bytes": 90112 }));
+        assert_eq!(guardrails, None);
+    }
+
+    #[test]
+    fn deserialize_info() {
+        let action: GovernanceAction =
+            serde_json::from_value(json!({ "type": "information" })).unwrap();
+        assert_eq!(action, GovernanceAction::Info);
+    }
+
+    #[test]
+    fn deserialize_voter_roles() {
+        let voters: Vec<Voter> = serde_json::from_value(json!([
+            { "role": "constitutionalCommittee", "id": "aa".repeat(28), "from": "script" },
+            { "role": "delegateRepresentative", "id": "bb".repeat(28), "from": "verificationKey" },
+            { "role": "stakePoolOperator", "id": "cc".repeat(28) }
+        ]))
+        .unwrap();
+        assert_eq!(voters[0].role, VoterRole::ConstitutionalCommittee);
+        assert_eq!(voters[0].from, Some(CredentialOrigin::Script));
+        assert_eq!(voters[1].role, VoterRole::DelegateRepresentative);
+        assert_eq!(voters[1].from, Some(CredentialOrigin::VerificationKey));
+        assert_eq!(voters[2].role, VoterRole::StakePoolOperator);
+        assert_eq!(voters[2].from, None);
+    }
+
+    #[test]
+    fn deserialize_vote() {
+        let vote: GovernanceVote = serde_json::from_value(json!({
+            "issuer": { "role": "delegateRepresentative", "id": "bb".repeat(28), "from": "script" },
+            "vote": "abstain"
+        }))
+        .unwrap();
+        assert_eq!(vote.vote, Vote::Abstain);
+        assert!(serde_json::from_value::<Vote>(json!("maybe")).is_err());
+    }
+
+    #[test]
+    fn anchor_verify() {
+        let anchor: Anchor = serde_json::from_value(json!({
+            "url": "https://example.com/metadata.json",
+            "hash": "256c83b5465d8e6a2f1ef0b9ff6b8e6f8a5b0b1d1e4a1a82b8a8a1e2bd4b0f86"
+        }))
+        .unwrap();
+        assert_eq!(anchor.parsed_url().unwrap().host_str(), Some("example.com"));
+
+        let content = br#"{"body":{"title":"Treasury withdrawal"}}"#;
+        let hash = blake2b_simd::Params::new()
+            .hash_length(32)
+            .hash(content)
+            .to_hex();
+        let anchor = Anchor {
+            hash: Hash32::try_from(hash.to_string()).unwrap(),
+            ..anchor
+        };
+        assert!(anchor.verify(content));
+        assert!(!anchor.verify(br#"{"body":{"title":"Treasury withdrawal!"}}"#));
+    }
+
+    #[test]
+    fn anchor_known_digest() {
+        // blake2b-256 of the empty string
+        let anchor: Anchor = serde_json::from_value(json!({
+            "url": "ipfs://bafkreiazhhawe7sjwuthcfgl3mmv2swec7sukvclu3oli7qdyz4uhhuvmy",
+            "hash": "0E5751C026E543B2E8AB2EB06099DAA1D1E5DF47778F7787FAAB45CDF12FE3A8"
+        }))
+        .unwrap();
+        assert!(anchor.verify(b""));
+        assert!(!anchor.verify(b"\0"));
+    }
+
+    #[test]
+    fn anchor_rejects_invalid() {
+        // Malformed urls are accepted on chain, only parsing them fails
+        let invalid_url = json!({ "url": "not a url", "hash": "00".repeat(32) });
+        let anchor = serde_json::from_value::<Anchor>(invalid_url).unwrap();
+        assert_eq!(anchor.url, "not a url");
+        assert!(anchor.parsed_url().is_err());
+        let invalid_hash = json!({ "url": "https://example.com", "hash": "00" });
+        assert!(serde_json::from_value::<Anchor>(invalid_hash).is_err());
+    }
+}
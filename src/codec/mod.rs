@@ -1,12 +1,43 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
 use std::str::FromStr as _;
+use std::sync::atomic::{AtomicU64, Ordering};
 
-use num::BigRational;
+use num::{BigInt, BigRational, ToPrimitive as _};
 use serde::{Deserialize, Deserializer, Serialize};
 
+use crate::error::InputError;
+
+mod address;
+pub(crate) mod cbor;
+mod certificate;
+mod governance;
 mod script;
+mod slot;
+pub use address::*;
+pub use certificate::*;
+pub use governance::*;
 pub use script::*;
+pub use slot::*;
+
+/// Serializes `value` with object keys sorted at every level, so that maps serialize to the
+/// same bytes regardless of their iteration order
+pub fn to_canonical_string<T: Serialize>(value: &T) -> serde_json::Result<String> {
+    fn sort_keys(value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(map) => {
+                map.sort_keys();
+                map.values_mut().for_each(sort_keys);
+            }
+            serde_json::Value::Array(values) => values.iter_mut().for_each(sort_keys),
+            _ => {}
+        }
+    }
+
+    let mut value = serde_json::to_value(value)?;
+    sort_keys(&mut value);
+    serde_json::to_string(&value)
+}
 
 #[derive(Debug, Clone, Serialize)]
 pub struct RpcRequest<T: Serialize> {
@@ -19,19 +50,100 @@ pub struct RpcRequest<T: Serialize> {
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 pub struct RpcResponseIdentifier {
     pub method: String,
+    /// Missing or `null` when the request was sent without an id
+    #[serde(default)]
     pub id: Option<Id>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(untagged)]
+impl std::fmt::Display for RpcResponseIdentifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.id {
+            Some(id) => write!(f, "{} (id {})", self.method, id.as_str()),
+            None => write!(f, "{} (no id)", self.method),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum RpcResponse<T, E> {
     Success(RpcSuccess<T>),
     Error(RpcError<E>),
 }
 
-impl<T, E> Into<Result<T, E>> for RpcResponse<T, E> {
-    fn into(self) -> Result<T, E> {
-        match self {
+/// Picks the variant by the `result` or `error` key and decodes it in place. Unlike an
+/// untagged enum, the response isn't buffered first, which would read integers beyond `u64`
+/// as lossy floats before they reach fields able to hold them.
+impl<'de, T: Deserialize<'de>, E: Deserialize<'de>> Deserialize<'de> for RpcResponse<T, E> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ResponseVisitor<T, E>(std::marker::PhantomData<(T, E)>);
+
+        impl<'de, T: Deserialize<'de>, E: Deserialize<'de>> serde::de::Visitor<'de>
+            for ResponseVisitor<T, E>
+        {
+            type Value = RpcResponse<T, E>;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a JSON-RPC response with either a result or an error")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                use serde::de::Error as _;
+
+                let mut jsonrpc = None;
+                let mut method = None;
+                let mut outcome = None;
+                let mut id = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "jsonrpc" => jsonrpc = Some(map.next_value()?),
+                        "method" => method = map.next_value()?,
+                        "id" => id = map.next_value()?,
+                        "result" | "error" if outcome.is_some() => {
+                            return Err(A::Error::custom(
+                                "a response can't have both a result and an error",
+                            ));
+                        }
+                        "result" => outcome = Some(Ok(map.next_value()?)),
+                        "error" => outcome = Some(Err(map.next_value()?)),
+                        _ => {
+                            map.next_value::<serde::de::IgnoredAny>()?;
+                        }
+                    }
+                }
+
+                let jsonrpc = jsonrpc.ok_or_else(|| A::Error::missing_field("jsonrpc"))?;
+                Ok(
+                    match outcome.ok_or_else(|| A::Error::missing_field("result"))? {
+                        Ok(result) => RpcResponse::Success(RpcSuccess {
+                            jsonrpc,
+                            method,
+                            result,
+                            id,
+                        }),
+                        Err(error) => RpcResponse::Error(RpcError {
+                            jsonrpc,
+                            method,
+                            error,
+                            id,
+                        }),
+                    },
+                )
+            }
+        }
+
+        deserializer.deserialize_map(ResponseVisitor(std::marker::PhantomData))
+    }
+}
+
+impl<T, E> From<RpcResponse<T, E>> for Result<T, E> {
+    fn from(response: RpcResponse<T, E>) -> Self {
+        match response {
             RpcResponse::Success(success) => Ok(success.result),
             RpcResponse::Error(error) => Err(error.error),
         }
@@ -56,7 +168,7 @@ pub struct RpcError<T> {
     pub id: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 #[serde(transparent)]
 pub struct Id(String);
 
@@ -66,52 +178,584 @@ impl Default for Id {
     }
 }
 
+impl Id {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Source of request ids
+#[derive(Debug, Default)]
+pub enum IdGenerator {
+    /// Random UUIDs
+    #[default]
+    Random,
+    /// Zero-padded counter, so ids sort in the order requests were sent
+    Sequential(AtomicU64),
+}
+
+impl IdGenerator {
+    pub fn sequential() -> Self {
+        IdGenerator::Sequential(AtomicU64::new(0))
+    }
+
+    pub fn next_id(&self) -> Id {
+        match self {
+            IdGenerator::Random => Id::default(),
+            IdGenerator::Sequential(counter) => {
+                Id(format!("{:020}", counter.fetch_add(1, Ordering::Relaxed)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod rpc_id_tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn missing_and_null_ids_deserialize_to_none() {
+        let missing = json!({"jsonrpc":"2.0","method":"queryLedgerState/epoch","result":1});
+        let null = json!({"jsonrpc":"2.0","method":"queryLedgerState/epoch","result":1,"id":null});
+
+        let success: RpcSuccess<u64> = serde_json::from_value(missing.clone()).unwrap();
+        assert_eq!(success.id, None);
+        let success: RpcSuccess<u64> = serde_json::from_value(null.clone()).unwrap();
+        assert_eq!(success.id, None);
+
+        let error_missing = json!({"jsonrpc":"2.0","method":"queryLedgerState/epoch","error":{}});
+        let error_null =
+            json!({"jsonrpc":"2.0","method":"queryLedgerState/epoch","error":{},"id":null});
+        let error: RpcError<serde_json::Value> = serde_json::from_value(error_missing).unwrap();
+        assert_eq!(error.id, None);
+        let error: RpcError<serde_json::Value> = serde_json::from_value(error_null).unwrap();
+        assert_eq!(error.id, None);
+
+        let identifier: RpcResponseIdentifier = serde_json::from_value(missing).unwrap();
+        assert_eq!(identifier.id, None);
+        let identifier: RpcResponseIdentifier = serde_json::from_value(null).unwrap();
+        assert_eq!(identifier.id, None);
+    }
+
+    #[test]
+    fn response_variant_follows_result_or_error_key() {
+        let success: RpcResponse<u64, serde_json::Value> =
+            serde_json::from_str(r#"{"result":1,"jsonrpc":"2.0","extra":[]}"#).unwrap();
+        assert!(matches!(
+            success,
+            RpcResponse::Success(RpcSuccess { result: 1, .. })
+        ));
+
+        let error: RpcResponse<u64, serde_json::Value> =
+            serde_json::from_str(r#"{"jsonrpc":"2.0","method":"m","error":{"code":1},"id":"a"}"#)
+                .unwrap();
+        let RpcResponse::Error(error) = error else {
+            panic!("expected an error response");
+        };
+        assert_eq!(error.error, json!({ "code": 1 }));
+        assert_eq!(error.method.as_deref(), Some("m"));
+        assert_eq!(error.id, Some(json!("a")));
+
+        for invalid in [
+            r#"{"jsonrpc":"2.0"}"#,
+            r#"{"result":1}"#,
+            r#"{"jsonrpc":"2.0","result":1,"error":{}}"#,
+            r#"{"jsonrpc":"2.0","result":"one"}"#,
+        ] {
+            assert!(serde_json::from_str::<RpcResponse<u64, serde_json::Value>>(invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn null_id_matches_request_without_id() {
+        let expected = RpcResponseIdentifier {
+            method: "queryLedgerState/epoch".to_string(),
+            id: None,
+        };
+        let received: RpcResponseIdentifier = serde_json::from_value(
+            json!({"jsonrpc":"2.0","method":"queryLedgerState/epoch","result":1,"id":null}),
+        )
+        .unwrap();
+        assert_eq!(received, expected);
+    }
+
+    #[test]
+    fn sequential_ids_are_unique_and_monotonic() {
+        let ids = IdGenerator::sequential();
+        let generated: Vec<Id> = (0..1000).map(|_| ids.next_id()).collect();
+        assert!(generated.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(generated[0].as_str(), "00000000000000000000");
+        assert_eq!(generated[999].as_str(), "00000000000000000999");
+    }
+
+    #[test]
+    fn random_ids_are_unique() {
+        let ids = IdGenerator::Random;
+        let generated: HashSet<Id> = (0..1000).map(|_| ids.next_id()).collect();
+        assert_eq!(generated.len(), 1000);
+    }
+
+    #[test]
+    fn string_id_matches_request_with_same_id() {
+        let id = Id::default();
+        let expected = RpcResponseIdentifier {
+            method: "queryLedgerState/epoch".to_string(),
+            id: Some(id.clone()),
+        };
+        let received: RpcResponseIdentifier = serde_json::from_value(
+            json!({"jsonrpc":"2.0","method":"queryLedgerState/epoch","result":1,"id":id}),
+        )
+        .unwrap();
+        assert_eq!(received, expected);
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TxCbor {
     /// A hex-encoded CBOR value
+    #[serde(deserialize_with = "deserialize_hex")]
     pub cbor: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+/// Deserializes a string, rejecting anything that isn't valid hex
+fn deserialize_hex<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    if s.len() % 2 != 0 {
+        return Err(serde::de::Error::custom(format!(
+            "invalid hex: odd length {}",
+            s.len()
+        )));
+    }
+    if let Some(c) = s.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(serde::de::Error::custom(format!(
+            "invalid hex: unexpected character '{}'",
+            c
+        )));
+    }
+    Ok(s)
+}
+
+impl TxCbor {
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            cbor: hex::encode(bytes),
+        }
+    }
+
+    /// Validates a hex-encoded transaction before it is sent to Ogmios
+    pub fn from_hex(cbor: &str) -> Result<Self, InputError> {
+        let bytes = hex::decode(cbor.trim()).map_err(|e| InputError::InvalidHex(e.to_string()))?;
+        // Transactions are always encoded as a CBOR array (major type 4)
+        match bytes.first() {
+            None => return Err(InputError::InvalidCbor("empty input".to_string())),
+            Some(byte) if byte >> 5 != 4 => {
+                return Err(InputError::InvalidCbor(format!(
+                    "expected a CBOR array, got initial byte 0x{:02x}",
+                    byte
+                )));
+            }
+            Some(_) => {}
+        }
+        Ok(Self::from_bytes(&bytes))
+    }
+
+    /// Reads a transaction from a cardano-cli text envelope, as written by
+    /// `cardano-cli transaction sign --out-file`
+    pub fn from_text_envelope(envelope: &str) -> Result<Self, InputError> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct TextEnvelope {
+            #[serde(rename = "type")]
+            type_: String,
+            cbor_hex: String,
+        }
+
+        let envelope: TextEnvelope = serde_json::from_str(envelope)
+            .map_err(|e| InputError::InvalidEnvelope(e.to_string()))?;
+        if !envelope.type_.contains("Tx") {
+            return Err(InputError::InvalidEnvelope(format!(
+                "expected a transaction envelope, got type '{}'",
+                envelope.type_
+            )));
+        }
+        Self::from_hex(&envelope.cbor_hex)
+    }
+}
+
+/// Output references a serialized transaction spends or references, i.e. the inputs needed to
+/// evaluate it: regular inputs followed by reference inputs. Collateral isn't included.
+pub(crate) fn transaction_inputs(tx: &[u8]) -> Result<Vec<TxOutputPointer>, String> {
+    let mut decoder = cbor::Decoder::new(tx);
+    decoder.array_len()?;
+    let mut inputs = vec![];
+    let mut reference_inputs = vec![];
+    let mut entries = decoder.map_len()?;
+    while decoder.next_item(&mut entries) {
+        match decoder.unsigned()? {
+            0 => inputs = read_inputs(&mut decoder)?,
+            18 => reference_inputs = read_inputs(&mut decoder)?,
+            _ => decoder.skip()?,
+        }
+    }
+    inputs.extend(reference_inputs);
+    Ok(inputs)
+}
+
+/// Reads a set of inputs, tagged as a set (258) since Conway. Either may be indefinite-length.
+fn read_inputs(decoder: &mut cbor::Decoder) -> Result<Vec<TxOutputPointer>, String> {
+    let mut len = match decoder.initial()? {
+        (cbor::Major::Tag, Some(258)) => decoder.array_len()?,
+        (cbor::Major::Array, len) => len,
+        (major, _) => return Err(format!("expected a set of inputs, got {:?}", major)),
+    };
+    let mut inputs = vec![];
+    while decoder.next_item(&mut len) {
+        let fields = decoder.array_len()?;
+        if fields.is_some_and(|fields| fields != 2) {
+            return Err("expected an input as [transaction id, index]".to_string());
+        }
+        let id = hex::encode(decoder.bytes()?);
+        let index = decoder.unsigned()?;
+        if fields.is_none() && !decoder.at_break() {
+            return Err("expected an input as [transaction id, index]".to_string());
+        }
+        inputs.push(TxOutputPointer {
+            transaction: TxPointer { id },
+            index: u32::try_from(index).map_err(|_| format!("invalid input index {}", index))?,
+        });
+    }
+    Ok(inputs)
+}
+
+#[cfg(test)]
+mod tx_cbor_tests {
+    use super::*;
+
+    #[test]
+    fn from_hex_accepts_array() {
+        let tx = TxCbor::from_hex("84a400").unwrap();
+        assert_eq!(tx.cbor, "84a400");
+    }
+
+    #[test]
+    fn inputs_and_reference_inputs() {
+        // [{0: 258([[h'aa..', 1]]), 13: [[h'cc..', 0]], 18: [[h'bb..', 0]]}, {}, true, null]
+        let tx = format!(
+            "84a300d90102818258 20{}01 0d818258 20{}00 12818258 20{}00 a0f5f6",
+            "aa".repeat(32),
+            "cc".repeat(32),
+            "bb".repeat(32)
+        )
+        .replace(' ', "");
+        let inputs = transaction_inputs(&hex::decode(tx).unwrap()).unwrap();
+        let ids: Vec<_> = inputs
+            .iter()
+            .map(|input| (input.transaction.id.clone(), input.index))
+            .collect();
+        assert_eq!(ids, [("aa".repeat(32), 1), ("bb".repeat(32), 0)]);
+
+        assert!(transaction_inputs(&hex::decode("84a10001a0f5f6").unwrap()).is_err());
+    }
+
+    #[test]
+    fn indefinite_length_inputs() {
+        // [_ {_ 0: 258([_ [_ h'aa..', 1], [h'bb..', 0]]), 2: 0}, {}, true, null]
+        let tx = format!(
+            "9f bf00d901029f 9f5820{}01ff 825820{}00 ff 0200 ff a0f5f6ff",
+            "aa".repeat(32),
+            "bb".repeat(32)
+        )
+        .replace(' ', "");
+        let inputs = transaction_inputs(&hex::decode(tx).unwrap()).unwrap();
+        let ids: Vec<_> = inputs
+            .iter()
+            .map(|input| (input.transaction.id.clone(), input.index))
+            .collect();
+        assert_eq!(ids, [("aa".repeat(32), 1), ("bb".repeat(32), 0)]);
+
+        // An input missing its break
+        let tx = format!("84a1009f9f5820{}0100ffa0f5f6", "aa".repeat(32));
+        assert!(transaction_inputs(&hex::decode(tx).unwrap()).is_err());
+    }
+
+    #[test]
+    fn from_hex_rejects_odd_length() {
+        assert!(matches!(
+            TxCbor::from_hex("84a"),
+            Err(InputError::InvalidHex(_))
+        ));
+    }
+
+    #[test]
+    fn from_hex_rejects_non_hex() {
+        assert!(matches!(
+            TxCbor::from_hex("84zz"),
+            Err(InputError::InvalidHex(_))
+        ));
+    }
+
+    #[test]
+    fn from_hex_rejects_non_array_cbor() {
+        assert!(matches!(
+            TxCbor::from_hex("a400"),
+            Err(InputError::InvalidCbor(_))
+        ));
+        assert!(matches!(
+            TxCbor::from_hex(""),
+            Err(InputError::InvalidCbor(_))
+        ));
+    }
+
+    #[test]
+    fn deserialize_valid_hex() {
+        let tx: TxCbor = serde_json::from_str(r#"{"cbor":"84a400FF"}"#).unwrap();
+        assert_eq!(tx.cbor, "84a400FF");
+    }
+
+    #[test]
+    fn deserialize_rejects_odd_length() {
+        let err = serde_json::from_str::<TxCbor>(r#"{"cbor":"84a"}"#).unwrap_err();
+        assert!(err.to_string().contains("odd length"));
+    }
+
+    #[test]
+    fn deserialize_rejects_non_hex() {
+        let err = serde_json::from_str::<TxCbor>(r#"{"cbor":"84zz"}"#).unwrap_err();
+        assert!(err.to_string().contains("unexpected character 'z'"));
+    }
+
+    #[test]
+    fn from_text_envelope() {
+        let envelope = r#"{"type":"Witnessed Tx ConwayEra","description":"Ledger Cddl Format","cborHex":"84a400"}"#;
+        let tx = TxCbor::from_text_envelope(envelope).unwrap();
+        assert_eq!(tx.cbor, "84a400");
+    }
+
+    #[test]
+    fn from_text_envelope_rejects_malformed_json() {
+        assert!(matches!(
+            TxCbor::from_text_envelope("{\"cborHex\":"),
+            Err(InputError::InvalidEnvelope(_))
+        ));
+    }
+
+    #[test]
+    fn from_text_envelope_rejects_non_transaction() {
+        let envelope =
+            r#"{"type":"PaymentSigningKeyShelley_ed25519","description":"","cborHex":"5820aa"}"#;
+        assert!(matches!(
+            TxCbor::from_text_envelope(envelope),
+            Err(InputError::InvalidEnvelope(_))
+        ));
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Tx {
     pub id: String,
     pub inputs: Vec<TxOutputPointer>,
     pub outputs: Vec<TxOutput>,
-    #[serde(default)]
+    /// Inputs consumed if a script fails, `collaterals` in Ogmios v6
+    #[serde(default, alias = "collaterals")]
     pub collateral: Vec<TxOutputPointer>,
-    pub collateral_return: Vec<TxOutput>,
+    /// Output created if a script fails, a transaction has at most one
+    #[serde(default)]
+    pub collateral_return: Option<TxOutput>,
     pub fee: Balance,
-    pub network: String,
+    /// Only present when the transaction declares a network id
+    #[serde(default)]
+    pub network: Option<String>,
     /// The raw serialized (CBOR) transaction in hex, as found on-chain
     /// Use --include-transaction-cbor on Ogmios to always include this field
     pub cbor: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl Tx {
+    /// All addresses this transaction pays to. Input addresses aren't included, since inputs
+    /// are only references and would need to be resolved first.
+    pub fn addresses(&self) -> HashSet<Address> {
+        self.outputs
+            .iter()
+            .map(|output| output.address.clone())
+            .collect()
+    }
+
+    /// Value produced by the transaction: the sum of its outputs plus the fee, the right-hand
+    /// side of the ledger's value conservation check (see `ValueNotConserved`, 3123).
+    /// Deposits and burned tokens are not included, as they aren't decoded yet. Fails if the
+    /// lovelace total overflows `u64`.
+    pub fn produced_value(&self) -> Result<Balance, AmountOverflow> {
+        Balance::checked_sum(
+            self.outputs
+                .iter()
+                .map(|output| &output.value)
+                .chain([&self.fee]),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tx_tests {
+    use super::*;
+
+    fn output(address: &str, lovelace: u64) -> TxOutput {
+        TxOutput {
+            address: address.into(),
+            value: Balance {
+                lovelace,
+                assets: Assets::default(),
+            },
+            datum_hash: None,
+            datum: None,
+            script: None,
+        }
+    }
+
+    #[test]
+    fn addresses_from_outputs() {
+        let tx = Tx {
+            id: "e8a3d2e4b7f1c6a5d9b0e3f2a1c4b7d6e5f8a9b0c1d2e3f4a5b6c7d8e9f0a1b2".to_string(),
+            inputs: vec![],
+            outputs: vec![
+                output(
+                    "addr_test1vz09v9yfxguvlp0zsnrpa3tdtm7el8xufp3m5lsm7qxzclgmzkket",
+                    1_000_000,
+                ),
+                output(
+                    "addr_test1wpnlxv2xv9a9ucvnvzqakwepzl9ltx7jzgm53av2e9ncv4sysemm8",
+                    2_000_000,
+                ),
+                output(
+                    "addr_test1vz09v9yfxguvlp0zsnrpa3tdtm7el8xufp3m5lsm7qxzclgmzkket",
+                    3_000_000,
+                ),
+            ],
+            collateral: vec![],
+            collateral_return: None,
+            fee: Balance {
+                lovelace: 170_000,
+                assets: Assets::default(),
+            },
+            network: None,
+            cbor: None,
+        };
+
+        let addresses = tx.addresses();
+        assert_eq!(addresses.len(), 2);
+        assert!(
+            addresses.contains("addr_test1vz09v9yfxguvlp0zsnrpa3tdtm7el8xufp3m5lsm7qxzclgmzkket")
+        );
+        assert!(
+            addresses.contains("addr_test1wpnlxv2xv9a9ucvnvzqakwepzl9ltx7jzgm53av2e9ncv4sysemm8")
+        );
+    }
+
+    #[test]
+    fn output_with_reference_script() {
+        let output: TxOutput = serde_json::from_value(serde_json::json!({
+            "address": "addr_test1wpnlxv2xv9a9ucvnvzqakwepzl9ltx7jzgm53av2e9ncv4sysemm8",
+            "value": { "ada": { "lovelace": 12_000_000 } },
+            "script": {
+                "language": "plutus:v3",
+                "cbor": "46450101002499"
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            output.script,
+            Some(Script::PlutusV3 {
+                cbor: "46450101002499".to_string()
+            })
+        );
+
+        let output: TxOutput = serde_json::from_value(serde_json::json!({
+            "address": "addr_test1wpnlxv2xv9a9ucvnvzqakwepzl9ltx7jzgm53av2e9ncv4sysemm8",
+            "value": { "ada": { "lovelace": 2_000_000 } }
+        }))
+        .unwrap();
+        assert_eq!(output.script, None);
+    }
+
+    #[test]
+    fn produced_value_sums_outputs_and_fee() {
+        let tx: Tx = serde_json::from_value(serde_json::json!({
+            "id": "e8a3d2e4b7f1c6a5d9b0e3f2a1c4b7d6e5f8a9b0c1d2e3f4a5b6c7d8e9f0a1b2",
+            "inputs": [],
+            "outputs": [
+                {
+                    "address": "addr_test1vz09v9yfxguvlp0zsnrpa3tdtm7el8xufp3m5lsm7qxzclgmzkket",
+                    "value": { "ada": { "lovelace": 1_000_000 }, "aa": { "01": 5 } }
+                },
+                {
+                    "address": "addr_test1wpnlxv2xv9a9ucvnvzqakwepzl9ltx7jzgm53av2e9ncv4sysemm8",
+                    "value": { "ada": { "lovelace": 2_000_000 }, "aa": { "01": 7, "02": 1 } }
+                }
+            ],
+            "fee": { "ada": { "lovelace": 170_000 } }
+        }))
+        .unwrap();
+
+        let produced = tx.produced_value().unwrap();
+        assert_eq!(produced.lovelace, 3_170_000);
+        assert_eq!(produced.assets["aa"]["01"], BigInt::from(12));
+        assert_eq!(produced.assets["aa"]["02"], BigInt::from(1));
+    }
+
+    #[test]
+    fn produced_value_overflow() {
+        let tx = Tx {
+            outputs: vec![output(
+                "addr_test1vz09v9yfxguvlp0zsnrpa3tdtm7el8xufp3m5lsm7qxzclgmzkket",
+                u64::MAX,
+            )],
+            ..serde_json::from_value(serde_json::json!({
+                "id": "e8a3d2e4b7f1c6a5d9b0e3f2a1c4b7d6e5f8a9b0c1d2e3f4a5b6c7d8e9f0a1b2",
+                "inputs": [],
+                "outputs": [],
+                "fee": { "ada": { "lovelace": 170_000 } }
+            }))
+            .unwrap()
+        };
+        assert_eq!(
+            tx.produced_value().unwrap_err().asset_name,
+            "lovelace".to_string()
+        );
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct TxPointer {
     /// 32-byte hex-encoded blake2b digest of the transaction body
     pub id: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct TxOutputPointer {
     pub transaction: TxPointer,
     pub index: u32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct TxOutput {
     /// A Cardano address (either legacy format or new format)
     /// New: `addr1q9d34spgg2kdy47n82e7x9pdd6vql6d2engxmpj20jmhuc2047yqd4xnh7u6u5jp4t0q3fkxzckph4tgnzvamlu7k5psuahzcp`
     /// Legacy: `DdzFFzCqrht8mbSTZHqpM2u4HeND2mdspsaBhdQ1BowPJBMzbDeBMeKgqdoKqo1D4sdPusEdZJVrFJRBBxX1jUEofNDYCJSZLg8MkyCE`
-    pub address: String,
+    pub address: Address,
     pub value: Balance,
     /// A Blake2b 32-byte hash digest, hex-encoded
     pub datum_hash: Option<String>,
     /// Hex-encoded CBOR value
     pub datum: Option<String>,
-    // TODO: script
+    /// Reference script attached to the output, since Babbage
+    #[serde(default)]
+    pub script: Option<Script>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -121,19 +765,28 @@ pub struct ExecutionUnits {
     pub cpu: Ratio,
 }
 
+impl ExecutionUnits {
+    /// How far `self` exceeds `max` as `(memory, cpu)`, rounded up. Negative values mean the
+    /// budget is under the maximum by that much. `None` when either doesn't fit `i128`.
+    pub fn overage(&self, max: &ExecutionUnits) -> Option<(i128, i128)> {
+        let over = |used: &Ratio, max: &Ratio| (&used.0 - &max.0).ceil().to_integer().to_i128();
+        Some((over(&self.memory, &max.memory)?, over(&self.cpu, &max.cpu)?))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Ratio(pub num_rational::BigRational);
 
-impl Into<BigRational> for Ratio {
-    fn into(self) -> BigRational {
-        self.0
+impl From<Ratio> for BigRational {
+    fn from(ratio: Ratio) -> Self {
+        ratio.0
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 enum RatioVariant {
-    Integer(u32),
+    Integer(u64),
     String(String),
 }
 
@@ -142,12 +795,7 @@ impl<'de> serde::Deserialize<'de> for Ratio {
     where
         D: Deserializer<'de>,
     {
-        let variant = match RatioVariant::deserialize(deserializer) {
-            Ok(variant) => variant,
-            Err(e) => return Err(e),
-        };
-
-        match variant {
+        match RatioVariant::deserialize(deserializer)? {
             RatioVariant::Integer(i) => {
                 Ok(Ratio(num_rational::BigRational::from_integer(i.into())))
             }
@@ -188,6 +836,32 @@ mod ratio_tests {
         );
     }
 
+    #[test]
+    fn deserialize_large_integer_ratio() {
+        let json = json!({ "memory": 14_000_000, "cpu": 10_000_000_000u64 });
+        let units: ExecutionUnits = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            units.cpu.0,
+            num_rational::BigRational::from_integer(10_000_000_000u64.into())
+        );
+    }
+
+    #[test]
+    fn execution_units_overage() {
+        let max: ExecutionUnits =
+            serde_json::from_value(json!({ "memory": 14_000_000, "cpu": 10_000_000_000u64 }))
+                .unwrap();
+        let used: ExecutionUnits =
+            serde_json::from_value(json!({ "memory": 14_500_000, "cpu": 9_000_000_000u64 }))
+                .unwrap();
+        assert_eq!(used.overage(&max), Some((500_000, -1_000_000_000)));
+
+        let used: ExecutionUnits =
+            serde_json::from_value(json!({ "memory": format!("1{}/1", "0".repeat(40)), "cpu": 0 }))
+                .unwrap();
+        assert_eq!(used.overage(&max), None);
+    }
+
     #[test]
     fn deserialize_string_ratio() {
         let json = json!({ "memory": "100/1000", "cpu": "100/1000" });
@@ -203,13 +877,13 @@ mod ratio_tests {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct RedeemerPointer {
     pub purpose: RedeemerPurpose,
     pub index: u64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub enum RedeemerPurpose {
     #[serde(rename = "spend")]
@@ -226,7 +900,7 @@ pub enum RedeemerPurpose {
     Propose,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum Era {
     #[serde(rename = "byron")]
     Byron,
@@ -244,7 +918,77 @@ pub enum Era {
     Conway,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// The failure reported by one era's decoder in a `Deserialization` (-32602) error
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeserializationFailure<'a> {
+    pub era: Era,
+    pub message: &'a str,
+}
+
+impl std::fmt::Display for DeserializationFailure<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} decoder: {}", self.era, self.message)
+    }
+}
+
+/// Picks the failure of `era` among every era's decoder failures. Without a hint, picks the
+/// decoder that got the furthest into the transaction, going by the `DeserialiseFailure`
+/// offset of its message, and the latest era when that doesn't tell them apart.
+pub(crate) fn pick_deserialization_failure<'a>(
+    failures: [(Era, &'a str); 7],
+    era: Option<&Era>,
+) -> DeserializationFailure<'a> {
+    fn offset(message: &str) -> Option<u64> {
+        let (_, rest) = message.split_once("DeserialiseFailure ")?;
+        let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+        digits.parse().ok()
+    }
+
+    let (era, message) = match era {
+        Some(era) => failures.into_iter().find(|(e, _)| e == era),
+        None => failures
+            .into_iter()
+            .enumerate()
+            .max_by_key(|(i, (_, message))| (offset(message), *i))
+            .map(|(_, failure)| failure),
+    }
+    .expect("every era has a failure");
+    DeserializationFailure { era, message }
+}
+
+/// Body of `deserialization_failure` for the error types sharing the `Deserialization`
+/// (-32602) variant, picking among its per-era failures
+macro_rules! deserialization_failure {
+    ($error:expr, $era:expr) => {
+        match $error {
+            Self::Deserialization {
+                byron,
+                shelley,
+                allegra,
+                mary,
+                alonzo,
+                babbage,
+                conway,
+                ..
+            } => Some($crate::codec::pick_deserialization_failure(
+                [
+                    ($crate::codec::Era::Byron, byron),
+                    ($crate::codec::Era::Shelley, shelley),
+                    ($crate::codec::Era::Allegra, allegra),
+                    ($crate::codec::Era::Mary, mary),
+                    ($crate::codec::Era::Alonzo, alonzo),
+                    ($crate::codec::Era::Babbage, babbage),
+                    ($crate::codec::Era::Conway, conway),
+                ],
+                $era,
+            )),
+            _ => None,
+        }
+    };
+}
+pub(crate) use deserialization_failure;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum Language {
     #[serde(rename = "plutus:v1")]
     PlutusV1,
@@ -254,34 +998,162 @@ pub enum Language {
     PlutusV3,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub enum InputSource {
     Inputs,
     Collaterals,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub enum CredentialOrigin {
     VerificationKey,
     Script,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg(test)]
+mod string_enum_tests {
+    use serde::de::DeserializeOwned;
+    use serde_json::json;
+
+    use super::*;
+
+    fn round_trip<T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug>(
+        name: &str,
+        value: T,
+    ) {
+        assert_eq!(serde_json::from_value::<T>(json!(name)).unwrap(), value);
+        assert_eq!(serde_json::to_value(&value).unwrap(), json!(name));
+    }
+
+    #[test]
+    fn eras() {
+        round_trip("byron", Era::Byron);
+        round_trip("shelley", Era::Shelley);
+        round_trip("allegra", Era::Allegra);
+        round_trip("mary", Era::Mary);
+        round_trip("alonzo", Era::Alonzo);
+        round_trip("babbage", Era::Babbage);
+        round_trip("conway", Era::Conway);
+        assert!(serde_json::from_value::<Era>(json!("Conway")).is_err());
+    }
+
+    #[test]
+    fn languages() {
+        round_trip("plutus:v1", Language::PlutusV1);
+        round_trip("plutus:v2", Language::PlutusV2);
+        round_trip("plutus:v3", Language::PlutusV3);
+    }
+
+    #[test]
+    fn input_sources_and_credential_origins() {
+        round_trip("inputs", InputSource::Inputs);
+        round_trip("collaterals", InputSource::Collaterals);
+        round_trip("verificationKey", CredentialOrigin::VerificationKey);
+        round_trip("script", CredentialOrigin::Script);
+    }
+
+    #[test]
+    fn era_mismatch_fields() {
+        let json = json!({ "queryEra": "babbage", "ledgerEra": "conway" });
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct EraMismatch {
+            query_era: Era,
+            ledger_era: Era,
+        }
+        let mismatch: EraMismatch = serde_json::from_value(json).unwrap();
+        assert_eq!(mismatch.query_era, Era::Babbage);
+        assert_eq!(mismatch.ledger_era, Era::Conway);
+    }
+}
+
+/// Slots in which a transaction is valid, unbounded on a side whose bound is absent
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct ValidityInterval {
+    /// First slot the transaction is valid in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub invalid_before: Option<u64>,
+    /// First slot the transaction is no longer valid in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub invalid_hereafter: Option<u64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl ValidityInterval {
+    pub fn new(invalid_before: Option<u64>, invalid_hereafter: Option<u64>) -> Self {
+        Self {
+            invalid_before,
+            invalid_hereafter,
+        }
+    }
+
+    /// Whether a transaction with this interval is valid in `slot`: from `invalid_before`
+    /// included to `invalid_hereafter` excluded
+    pub fn contains(&self, slot: u64) -> bool {
+        self.invalid_before.is_none_or(|before| before <= slot)
+            && self
+                .invalid_hereafter
+                .is_none_or(|hereafter| slot < hereafter)
+    }
+}
+
+#[cfg(test)]
+mod validity_interval_tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let interval = ValidityInterval::new(Some(100), Some(200));
+        let json = serde_json::to_value(interval).unwrap();
+        assert_eq!(
+            json,
+            json!({ "invalidBefore": 100, "invalidHereafter": 200 })
+        );
+        assert_eq!(
+            serde_json::from_value::<ValidityInterval>(json).unwrap(),
+            interval
+        );
+
+        let unbounded = ValidityInterval::new(None, Some(200));
+        let json = serde_json::to_value(unbounded).unwrap();
+        assert_eq!(json, json!({ "invalidHereafter": 200 }));
+        assert_eq!(
+            serde_json::from_value::<ValidityInterval>(json).unwrap(),
+            unbounded
+        );
+        let nulls = json!({ "invalidBefore": null, "invalidHereafter": null });
+        assert_eq!(
+            serde_json::from_value::<ValidityInterval>(nulls).unwrap(),
+            ValidityInterval::default()
+        );
+    }
+
+    #[test]
+    fn contains_bounds() {
+        let interval = ValidityInterval::new(Some(100), Some(200));
+        assert!(!interval.contains(99));
+        assert!(interval.contains(100));
+        assert!(interval.contains(199));
+        assert!(!interval.contains(200));
+
+        assert!(ValidityInterval::new(None, Some(200)).contains(0));
+        assert!(ValidityInterval::new(Some(100), None).contains(u64::MAX));
+        assert!(ValidityInterval::default().contains(0));
+        assert!(!ValidityInterval::new(Some(200), Some(200)).contains(200));
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct NumberOfBytes {
     pub bytes: u64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct ProtocolVersion {
     pub major: u32,
@@ -295,44 +1167,467 @@ pub struct TxId {
     pub id: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct StakePoolId {
     /// Hex-encoded 28-byte blake2b hash digest (pool1...)
     pub id: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+const BECH32_CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// A stake pool identifier, either bech32-encoded (`pool1...`) or as a hex-encoded 28-byte
+/// blake2b hash digest. Only the shape is validated on decode, not the bech32 checksum.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct PoolId(pub String);
+
+impl TryFrom<String> for PoolId {
+    type Error = String;
+
+    fn try_from(id: String) -> Result<Self, Self::Error> {
+        let is_bech32 = id.len() == 56
+            && id
+                .strip_prefix("pool1")
+                .is_some_and(|data| data.chars().all(|c| BECH32_CHARSET.contains(c)));
+        let is_hex = id.len() == 56 && id.chars().all(|c| c.is_ascii_hexdigit());
+        if is_bech32 || is_hex {
+            Ok(PoolId(id))
+        } else {
+            Err(format!("invalid stake pool id '{}'", id))
+        }
+    }
+}
+
+impl From<PoolId> for String {
+    fn from(id: PoolId) -> Self {
+        id.0
+    }
+}
+
+impl Deref for PoolId {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl PartialEq<&str> for PoolId {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+/// A hex-encoded 32-byte hash digest
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Hash32(pub String);
+
+impl TryFrom<String> for Hash32 {
+    type Error = String;
+
+    fn try_from(hash: String) -> Result<Self, Self::Error> {
+        if hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            Ok(Hash32(hash))
+        } else {
+            Err(format!("invalid 32-byte hex hash '{}'", hash))
+        }
+    }
+}
+
+impl From<Hash32> for String {
+    fn from(hash: Hash32) -> Self {
+        hash.0
+    }
+}
+
+impl Deref for Hash32 {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// A stake credential, either from a verification key or a script
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Credential {
+    /// Hex-encoded 28-byte blake2b hash digest of a verification key
+    Key(String),
+    /// Hex-encoded 28-byte blake2b hash digest of a script
+    Script(String),
+}
+
+impl Credential {
+    /// Parses a credential given as a bech32 stake address (`stake1...`, `stake_test1...`),
+    /// a bech32 hash (`stake_vkh1...` or `script1...`) or a hex-encoded hash, the latter being
+    /// a script hash if `script` is set. The hash is kept hex-encoded, in lowercase.
+    pub fn parse(credential: &str, script: bool) -> Result<Credential, InputError> {
+        let invalid =
+            |reason: &str| InputError::InvalidCredential(format!("'{}': {}", credential, reason));
+        if credential.len() == 56 && credential.chars().all(|c| c.is_ascii_hexdigit()) {
+            let hash = credential.to_ascii_lowercase();
+            return Ok(if script {
+                Credential::Script(hash)
+            } else {
+                Credential::Key(hash)
+            });
+        }
+
+        let (hrp, data) = bech32::decode(credential).map_err(|e| invalid(&e.to_string()))?;
+        let (is_script, hash) = match (hrp.as_str(), data.as_slice()) {
+            ("stake" | "stake_test", [header, hash @ ..]) if hash.len() == 28 => {
+                // Stake address headers are 0b1110 for keys and 0b1111 for scripts
+                match header >> 4 {
+                    0b1110 => (false, hash),
+                    0b1111 => (true, hash),
+                    _ => return Err(invalid("not a stake address")),
+                }
+            }
+            ("stake_vkh", hash) if hash.len() == 28 => (false, hash),
+            ("script", hash) if hash.len() == 28 => (true, hash),
+            _ => return Err(invalid("not a stake credential")),
+        };
+        let hash = hex::encode(hash);
+        Ok(if is_script {
+            Credential::Script(hash)
+        } else {
+            Credential::Key(hash)
+        })
+    }
+}
+
+#[cfg(test)]
+mod credential_tests {
+    use super::*;
+
+    const KEY_HASH: &str = "337b62cfff6403a06a3acbc34f8c46003c69fe79a3628cefa9c47251";
+    const SCRIPT_HASH: &str = "c37b1b5dc0669f1d3c61a6fddb2e8fde96be87b881c60bce8e8d542f";
+
+    #[test]
+    fn parse_bech32_and_hex() {
+        let key = Credential::Key(KEY_HASH.to_string());
+        let script = Credential::Script(SCRIPT_HASH.to_string());
+
+        assert_eq!(Credential::parse(KEY_HASH, false).unwrap(), key);
+        assert_eq!(
+            Credential::parse(&KEY_HASH.to_ascii_uppercase(), false).unwrap(),
+            key
+        );
+        assert_eq!(Credential::parse(SCRIPT_HASH, true).unwrap(), script);
+        // CIP-19 test vectors
+        assert_eq!(
+            Credential::parse(
+                "stake1uyehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gh6ffgw",
+                false
+            )
+            .unwrap(),
+            key
+        );
+        assert_eq!(
+            Credential::parse(
+                "stake178phkx6acpnf78fuvxn0mkew3l0fd058hzquvz7w36x4gtcccycj5",
+                false
+            )
+            .unwrap(),
+            script
+        );
+        let stake_vkh = bech32::encode::<bech32::Bech32>(
+            bech32::Hrp::parse("stake_vkh").unwrap(),
+            &hex::decode(KEY_HASH).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(Credential::parse(&stake_vkh, true).unwrap(), key);
+    }
+
+    #[test]
+    fn parse_rejects_invalid() {
+        // Checksum broken by the last character
+        assert!(matches!(
+            Credential::parse(
+                "stake1uyehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gh6ffgx",
+                false
+            ),
+            Err(InputError::InvalidCredential(_))
+        ));
+        assert!(Credential::parse(&KEY_HASH[2..], false).is_err());
+        assert!(
+            Credential::parse(
+                "addr1vpu5vlrf4xkxv2qpwngf6cjhtw542ayty80v8dyr49rf5eg0yu80w",
+                false
+            )
+            .is_err()
+        );
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Balance {
     pub lovelace: u64,
     pub assets: Assets,
 }
 
-#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
-pub struct Assets(HashMap<String, HashMap<String, u64>>);
+/// Hex-encoded 28-byte blake2b hash digest of a minting policy
+pub type PolicyId = String;
+/// Hex-encoded asset name (up to 32 bytes)
+pub type AssetName = String;
+
+/// Native-asset quantities by policy id and asset name. Quantities are arbitrary-precision
+/// and signed, since burned amounts are negative and sums may exceed `u64`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Assets(HashMap<String, HashMap<String, BigInt>>);
 
 impl Deref for Assets {
-    type Target = HashMap<String, HashMap<String, u64>>;
+    type Target = HashMap<String, HashMap<String, BigInt>>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
+impl Assets {
+    /// Quantity of a single asset, `None` if it isn't present. Borrowed as a [`BigInt`] rather
+    /// than copied out as a `u64`: quantities in values and mint fields may be negative or
+    /// beyond `u64`, and a lossy accessor would hide that.
+    pub fn quantity_of(&self, policy_id: &str, asset_name: &str) -> Option<&BigInt> {
+        self.0.get(policy_id)?.get(asset_name)
+    }
+
+    /// Policy ids with at least one asset present
+    pub fn policies(&self) -> impl Iterator<Item = &PolicyId> {
+        self.0
+            .iter()
+            .filter(|(_, tokens)| !tokens.is_empty())
+            .map(|(policy_id, _)| policy_id)
+    }
+
+    /// Whether no asset is present at all, even if some policies are listed without assets.
+    /// Not named `is_empty`: an inherent method of that name would silently shadow the map's
+    /// `is_empty` reached through `Deref`, which counts policies instead.
+    pub fn has_no_quantities(&self) -> bool {
+        self.0.values().all(HashMap::is_empty)
+    }
+
+    /// Every asset as `(policy id, asset name, quantity)`, in no particular order. Not named
+    /// `iter` for the same reason as [`Self::has_no_quantities`]: the map's `iter`, reached
+    /// through `Deref`, yields policies with their assets.
+    pub fn flat_iter(&self) -> impl Iterator<Item = (&PolicyId, &AssetName, &BigInt)> {
+        self.0.iter().flat_map(|(policy_id, tokens)| {
+            tokens
+                .iter()
+                .map(move |(asset_name, quantity)| (policy_id, asset_name, quantity))
+        })
+    }
+}
+
+/// A native-asset quantity, as a JSON integer or a decimal string. Quantities beyond `i64` and
+/// `u64` are serialized as strings so they survive a round trip without losing digits.
+struct Quantity(BigInt);
+
+/// Name serde_json recognizes to hand a visitor the raw text of the next value, as it does for
+/// `RawValue` with the `raw_value` feature. Integers of any size are parsed from that text;
+/// deserializers without it, such as serde's own buffering for untagged enums, go through
+/// `deserialize_any` and are limited to 128 bits.
+const RAW_VALUE_TOKEN: &str = "$serde_json::private::RawValue";
+
+impl Quantity {
+    fn parse<E: serde::de::Error>(value: &str) -> Result<Self, E> {
+        BigInt::from_str(value)
+            .map(Quantity)
+            .map_err(|_| E::custom(format!("invalid quantity '{}'", value)))
+    }
+}
+
+impl<'de> Deserialize<'de> for Quantity {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct QuantityVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for QuantityVisitor {
+            type Value = Quantity;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("an integer quantity, as a number or a decimal string")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Quantity, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                use serde::de::Error as _;
+
+                if map.next_key::<String>()?.as_deref() != Some(RAW_VALUE_TOKEN) {
+                    return Err(A::Error::invalid_type(serde::de::Unexpected::Map, &self));
+                }
+                let raw: String = map.next_value()?;
+                let raw = raw.trim();
+                if raw.starts_with('"') {
+                    let value: String = serde_json::from_str(raw).map_err(A::Error::custom)?;
+                    Quantity::parse(&value)
+                } else {
+                    Quantity::parse(raw)
+                }
+            }
+
+            fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Quantity, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                deserializer.deserialize_any(self)
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Quantity, E> {
+                Ok(Quantity(value.into()))
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Quantity, E> {
+                Ok(Quantity(value.into()))
+            }
+
+            fn visit_u128<E: serde::de::Error>(self, value: u128) -> Result<Quantity, E> {
+                Ok(Quantity(value.into()))
+            }
+
+            fn visit_i128<E: serde::de::Error>(self, value: i128) -> Result<Quantity, E> {
+                Ok(Quantity(value.into()))
+            }
+
+            fn visit_f64<E: serde::de::Error>(self, value: f64) -> Result<Quantity, E> {
+                Err(E::custom(format!(
+                    "quantity {} is not an integer within the i64 or u64 range",
+                    value
+                )))
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Quantity, E> {
+                Quantity::parse(value)
+            }
+        }
+
+        deserializer.deserialize_newtype_struct(RAW_VALUE_TOKEN, QuantityVisitor)
+    }
+}
+
+impl Serialize for Quantity {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if let Some(quantity) = self.0.to_u64() {
+            serializer.serialize_u64(quantity)
+        } else if let Some(quantity) = self.0.to_i64() {
+            serializer.serialize_i64(quantity)
+        } else {
+            serializer.serialize_str(&self.0.to_string())
+        }
+    }
+}
+
+impl Serialize for Assets {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeMap as _;
+
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (policy_id, tokens) in self.0.iter() {
+            let tokens: HashMap<&String, Quantity> = tokens
+                .iter()
+                .map(|(asset_name, quantity)| (asset_name, Quantity(quantity.clone())))
+                .collect();
+            map.serialize_entry(policy_id, &tokens)?;
+        }
+        map.end()
+    }
+}
+
+/// A sum of quantities exceeded `u64::MAX`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmountOverflow {
+    pub policy_id: PolicyId,
+    pub asset_name: AssetName,
+}
+
+impl std::fmt::Display for AmountOverflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "quantity of {}.{} overflows u64",
+            self.policy_id, self.asset_name
+        )
+    }
+}
+
+impl std::error::Error for AmountOverflow {}
+
+impl Balance {
+    /// Adds two balances, failing instead of wrapping if lovelace overflows
+    pub fn checked_add(&self, other: &Balance) -> Result<Balance, AmountOverflow> {
+        let lovelace = self
+            .lovelace
+            .checked_add(other.lovelace)
+            .ok_or_else(|| AmountOverflow {
+                policy_id: "ada".to_string(),
+                asset_name: "lovelace".to_string(),
+            })?;
+        let mut assets = self.assets.0.clone();
+        for (policy_id, tokens) in other.assets.0.iter() {
+            let entry = assets.entry(policy_id.clone()).or_default();
+            for (asset_name, quantity) in tokens {
+                *entry.entry(asset_name.clone()).or_default() += quantity;
+            }
+        }
+        Ok(Balance {
+            lovelace,
+            assets: Assets(assets),
+        })
+    }
+
+    /// Sums balances, failing instead of wrapping if lovelace overflows
+    pub fn checked_sum<'a>(
+        balances: impl IntoIterator<Item = &'a Balance>,
+    ) -> Result<Balance, AmountOverflow> {
+        balances.into_iter().try_fold(
+            Balance {
+                lovelace: 0,
+                assets: Assets::default(),
+            },
+            |total, balance| total.checked_add(balance),
+        )
+    }
+}
+
 impl<'de> Deserialize<'de> for Balance {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let mut assets: HashMap<String, HashMap<String, u64>> = HashMap::deserialize(deserializer)?;
+        let mut assets: HashMap<String, HashMap<String, Quantity>> =
+            HashMap::deserialize(deserializer)?;
 
         // Require "ada.lovelace" entry to exist
-        let lovelace = *assets
-            .get("ada")
+        let lovelace = assets
+            .remove("ada")
             .ok_or_else(|| serde::de::Error::missing_field("ada"))?
-            .get("lovelace")
+            .remove("lovelace")
             .ok_or_else(|| serde::de::Error::missing_field("ada.lovelace"))?;
-        assets.remove("ada");
-
+        let lovelace = lovelace.0.to_u64().ok_or_else(|| {
+            serde::de::Error::custom(format!("invalid lovelace quantity {}", lovelace.0))
+        })?;
+
+        let assets = assets
+            .into_iter()
+            .map(|(policy_id, tokens)| {
+                let tokens = tokens
+                    .into_iter()
+                    .map(|(asset_name, quantity)| (asset_name, quantity.0))
+                    .collect();
+                (policy_id, tokens)
+            })
+            .collect();
         Ok(Balance {
             lovelace,
             assets: Assets(assets),
@@ -340,7 +1635,204 @@ impl<'de> Deserialize<'de> for Balance {
     }
 }
 
-#[derive(Debug, Clone)]
+/// Serializes to the Ogmios shape `{"ada":{"lovelace":N},"<policy>":{"<asset>":N}}`, the
+/// same one deserialized above
+impl Serialize for Balance {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeMap as _;
+
+        let mut map = serializer.serialize_map(Some(self.assets.len() + 1))?;
+        map.serialize_entry("ada", &HashMap::from([("lovelace", self.lovelace)]))?;
+        for (policy_id, tokens) in self.assets.0.iter() {
+            let tokens: HashMap<&String, Quantity> = tokens
+                .iter()
+                .map(|(asset_name, quantity)| (asset_name, Quantity(quantity.clone())))
+                .collect();
+            map.serialize_entry(policy_id, &tokens)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod balance_tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn checked_sum_merges_assets() {
+        let a: Balance =
+            serde_json::from_value(json!({ "ada": { "lovelace": 1 }, "aa": { "01": 2 } })).unwrap();
+        let b: Balance =
+            serde_json::from_value(json!({ "ada": { "lovelace": 3 }, "aa": { "01": 4, "02": 5 } }))
+                .unwrap();
+
+        let total = Balance::checked_sum([&a, &b]).unwrap();
+        assert_eq!(total.lovelace, 4);
+        assert_eq!(total.assets["aa"]["01"], BigInt::from(6));
+        assert_eq!(total.assets["aa"]["02"], BigInt::from(5));
+    }
+
+    #[test]
+    fn checked_sum_overflow() {
+        let a: Balance =
+            serde_json::from_value(json!({ "ada": { "lovelace": 1 }, "aa": { "01": u64::MAX } }))
+                .unwrap();
+        let b: Balance =
+            serde_json::from_value(json!({ "ada": { "lovelace": 1 }, "aa": { "01": 1 } })).unwrap();
+
+        // Asset quantities don't overflow, only lovelace does
+        let total = Balance::checked_sum([&a, &b]).unwrap();
+        assert_eq!(total.assets["aa"]["01"], BigInt::from(u64::MAX) + 1);
+
+        let max = Balance {
+            lovelace: u64::MAX,
+            assets: Assets::default(),
+        };
+        let err = max.checked_add(&a).unwrap_err();
+        assert_eq!(err.to_string(), "quantity of ada.lovelace overflows u64");
+    }
+
+    #[test]
+    fn serialization_round_trip() {
+        let json = json!({ "ada": { "lovelace": 2_000_000 }, "aa": { "01": 2, "": 1 } });
+        let balance: Balance = serde_json::from_value(json.clone()).unwrap();
+        let serialized = serde_json::to_value(&balance).unwrap();
+        assert_eq!(serialized, json);
+        assert_eq!(
+            serde_json::from_value::<Balance>(serialized).unwrap(),
+            balance
+        );
+
+        let empty = Balance {
+            lovelace: 0,
+            assets: Assets::default(),
+        };
+        let serialized = serde_json::to_value(&empty).unwrap();
+        assert_eq!(serialized, json!({ "ada": { "lovelace": 0 } }));
+        assert_eq!(
+            serde_json::from_value::<Balance>(serialized).unwrap(),
+            empty
+        );
+    }
+
+    #[test]
+    fn assets_accessors() {
+        let balance: Balance = serde_json::from_value(json!({
+            "ada": { "lovelace": 1 },
+            "aa": { "01": 2, "02": 3 },
+            "bb": { "": 4 }
+        }))
+        .unwrap();
+        let assets = &balance.assets;
+
+        assert_eq!(assets.quantity_of("aa", "02"), Some(&BigInt::from(3)));
+        assert_eq!(assets.quantity_of("bb", ""), Some(&BigInt::from(4)));
+        assert_eq!(assets.quantity_of("aa", "03"), None);
+        assert_eq!(assets.quantity_of("cc", "01"), None);
+
+        let mut policies: Vec<_> = assets.policies().collect();
+        policies.sort();
+        assert_eq!(policies, ["aa", "bb"]);
+
+        let mut triples: Vec<_> = assets
+            .flat_iter()
+            .map(|(policy_id, asset_name, quantity)| {
+                (
+                    policy_id.as_str(),
+                    asset_name.as_str(),
+                    quantity.to_string(),
+                )
+            })
+            .collect();
+        triples.sort();
+        assert_eq!(
+            triples,
+            [
+                ("aa", "01", "2".to_string()),
+                ("aa", "02", "3".to_string()),
+                ("bb", "", "4".to_string())
+            ]
+        );
+
+        assert!(!assets.has_no_quantities());
+        assert!(Assets::default().has_no_quantities());
+        let empty_policy = Assets(HashMap::from([("aa".to_string(), HashMap::new())]));
+        assert!(empty_policy.has_no_quantities());
+        // The map methods are left untouched
+        assert!(!empty_policy.is_empty());
+        assert_eq!(empty_policy.len(), 1);
+        assert_eq!(assets.iter().count(), 2);
+        assert_eq!(Assets::default().flat_iter().count(), 0);
+    }
+
+    #[test]
+    fn quantities_across_the_u64_and_i64_ranges() {
+        let json = r#"{
+            "ada": { "lovelace": 1500000 },
+            "aa": { "01": 18446744073709551615, "02": -9223372036854775808 }
+        }"#;
+        let balance: Balance = serde_json::from_str(json).unwrap();
+        assert_eq!(balance.assets["aa"]["01"], BigInt::from(u64::MAX));
+        assert_eq!(balance.assets["aa"]["02"], BigInt::from(i64::MIN));
+
+        // Also when buffered, as responses are while matching `RpcResponse`'s variants
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            serde_json::from_value::<Balance>(value.clone()).unwrap(),
+            balance
+        );
+        assert_eq!(serde_json::to_value(&balance).unwrap(), value);
+
+        let negative_lovelace = json!({ "ada": { "lovelace": -1 } });
+        assert!(serde_json::from_value::<Balance>(negative_lovelace).is_err());
+        let fractional = json!({ "ada": { "lovelace": 1 }, "aa": { "01": 1.5 } });
+        assert!(serde_json::from_value::<Balance>(fractional).is_err());
+    }
+
+    #[test]
+    fn quantities_beyond_u64() {
+        let json = r#"{
+            "ada": { "lovelace": 1500000 },
+            "aa": { "01": 340282366920938463463374607431768211456, "02": -18446744073709551617 }
+        }"#;
+        let response = format!(
+            r#"{{"jsonrpc":"2.0","method":"queryLedgerState/utxo","result":{},"id":null}}"#,
+            json
+        );
+        let response: RpcResponse<Balance, serde_json::Value> =
+            serde_json::from_str(&response).unwrap();
+        let balance = Result::from(response).unwrap();
+        assert_eq!(
+            balance.assets["aa"]["01"],
+            BigInt::from_str("340282366920938463463374607431768211456").unwrap()
+        );
+        assert_eq!(balance.assets["aa"]["02"], -BigInt::from(u64::MAX) - 2);
+        assert_eq!(serde_json::from_str::<Balance>(json).unwrap(), balance);
+    }
+
+    #[test]
+    fn sums_beyond_u64_round_trip_as_strings() {
+        let balance: Balance = serde_json::from_value(json!({
+            "ada": { "lovelace": 1 },
+            "aa": { "01": u64::MAX }
+        }))
+        .unwrap();
+        let total = balance.checked_add(&balance).unwrap();
+        let expected = BigInt::from(u64::MAX) * 2;
+        assert_eq!(total.assets["aa"]["01"], expected);
+
+        let value = serde_json::to_value(&total).unwrap();
+        assert_eq!(value["aa"]["01"], json!(expected.to_string()));
+        assert_eq!(serde_json::from_value::<Balance>(value).unwrap(), total);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct AdaBalance {
     pub lovelace: u64,
 }
@@ -363,7 +1855,7 @@ impl<'de> Deserialize<'de> for AdaBalance {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AdaBalanceDelta {
     pub lovelace: i64,
 }
@@ -387,7 +1879,9 @@ impl<'de> Deserialize<'de> for AdaBalanceDelta {
     }
 }
 
-/// Helper macro for generating deserializable error types
+/// Helper macro for generating deserializable error types. Variants take their fields from
+/// the error data as `{ field: Type }`, the whole data as `(Type)` in a `data` field, or the
+/// whole data in a named field as `[field: Type]`.
 #[macro_export]
 macro_rules! define_ogmios_error {
     (
@@ -402,6 +1896,7 @@ macro_rules! define_ogmios_error {
                     ),* $(,)?
                 })?
                 $(( $single_ty:ty ))?
+                $([ $data_field:ident: $data_ty:ty ])?
             ),+
             $(,)?
             $(#[$fallback_meta:meta])*
@@ -419,13 +1914,17 @@ macro_rules! define_ogmios_error {
                         $field: $ty,
                     )*)?
                     $(data: $single_ty,)?
+                    $($data_field: $data_ty,)?
                 },
             )+
             $(#[$fallback_meta])*
             $fallback_variant {
                 message: String,
                 code: i32,
+                /// The `data` field of the error
                 error: serde_json::Value,
+                /// The whole error object as sent by Ogmios
+                raw: serde_json::Value,
             },
         }
 
@@ -447,6 +1946,14 @@ macro_rules! define_ogmios_error {
                     $enum_name::$fallback_variant { message, .. } => message,
                 }
             }
+
+            /// The whole error object as sent by Ogmios, for codes without a typed variant
+            pub fn raw_data(&self) -> Option<&serde_json::Value> {
+                match self {
+                    $enum_name::$fallback_variant { raw, .. } => Some(raw),
+                    _ => None,
+                }
+            }
         }
 
         impl<'de> serde::Deserialize<'de> for $enum_name {
@@ -491,10 +1998,11 @@ macro_rules! define_ogmios_error {
                         .and_then(|v| serde_json::from_value(v.clone()).map_err(E::custom))
                 }
 
-                let raw = RawError::deserialize(deserializer)?;
-                let data = raw.data;
-                let message = raw.message;
-                let code = raw.code;
+                let raw = serde_json::Value::deserialize(deserializer)?;
+                let error = RawError::deserialize(&raw).map_err(serde::de::Error::custom)?;
+                let data = error.data;
+                let message = error.message;
+                let code = error.code;
 
                 match code {
                     $(
@@ -503,19 +2011,34 @@ macro_rules! define_ogmios_error {
                                 $enum_name, $variant, message, data
                                 $({ $($field: $ty),* })?
                                 $(( $single_ty ))?
+                                $([ $data_field: $data_ty ])?
                             )
                         }
                     )+
                     _ => {
                         let error = data.unwrap_or(serde_json::Value::Null);
-                        Ok($enum_name::$fallback_variant { message, code, error })
+                        Ok($enum_name::$fallback_variant { message, code, error, raw })
                     }
                 }
             }
         }
 
+        impl $crate::OgmiosErrorCode for $enum_name {
+            fn code(&self) -> i32 {
+                $enum_name::code(self)
+            }
+        }
+
         impl std::fmt::Display for $enum_name {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                if self.code() == $crate::METHOD_NOT_FOUND {
+                    return write!(
+                        f,
+                        "[{}] method not supported by this Ogmios version: {}",
+                        self.code(),
+                        self.message()
+                    );
+                }
                 write!(f, "[{}] {}", self.code(), self.message())
             }
         }
@@ -551,6 +2074,16 @@ macro_rules! define_ogmios_error {
         let data: $single_ty = serde_json::from_value(data).map_err(serde::de::Error::custom)?;
         Ok($enum_name::$variant { message: $message, data })
     }};
+
+    // Internal rule: single value in a named field
+    (@deserialize_variant
+        $enum_name:ident, $variant:ident, $message:ident, $data:ident
+        [ $field:ident: $ty:ty ]
+    ) => {{
+        let data = $data.ok_or_else(|| serde::de::Error::missing_field("data"))?;
+        let $field: $ty = serde_json::from_value(data).map_err(serde::de::Error::custom)?;
+        Ok($enum_name::$variant { message: $message, $field })
+    }};
 }
 
 #[cfg(test)]
@@ -577,6 +2110,7 @@ mod tests {
             },
             3 => NoData,
             4 => SingleValue(CustomErrorData),
+            5 => NamedValue[values: Vec<u32>],
             _ => Unknown { error: Value }
         }
     }
@@ -680,6 +2214,30 @@ mod tests {
         }
     }
 
+    mod named_value_variant {
+        use super::*;
+
+        #[test]
+        fn deserialize_named_value() {
+            let json = json!({
+                "code": 5,
+                "message": "Named error",
+                "data": [1, 2, 3]
+            });
+
+            let error: EvaluationError = serde_json::from_value(json).unwrap();
+
+            assert_eq!(error.code(), 5);
+            assert_eq!(
+                error,
+                EvaluationError::NamedValue {
+                    message: "Named error".to_string(),
+                    values: vec![1, 2, 3],
+                }
+            );
+        }
+    }
+
     mod unknown_variant {
         use super::*;
 
@@ -703,10 +2261,12 @@ mod tests {
                     message,
                     code,
                     error,
+                    raw,
                 } => {
                     assert_eq!(message, "Unknown error");
                     assert_eq!(code, 9999);
                     assert_eq!(error, json!({"foo": "bar", "baz": 123}));
+                    assert_eq!(raw["data"], error);
                 }
                 _ => panic!("Expected Unknown variant"),
             }
@@ -728,6 +2288,37 @@ mod tests {
                 _ => panic!("Expected Unknown variant"),
             }
         }
+
+        #[test]
+        fn raw_data_keeps_whole_error_object() {
+            let json = json!({
+                "code": 3999,
+                "message": "Some error from a newer Ogmios",
+                "data": {
+                    "nested": { "list": [1, 2, 3] },
+                    "hint": "upgrade"
+                },
+                "extra": "outside of data"
+            });
+
+            let error: EvaluationError = serde_json::from_value(json.clone()).unwrap();
+
+            assert_eq!(error.code(), 3999);
+            assert_eq!(error.raw_data(), Some(&json));
+            assert_eq!(error.raw_data().unwrap()["extra"], "outside of data");
+        }
+
+        #[test]
+        fn raw_data_is_none_for_typed_variants() {
+            let json = json!({
+                "code": 3,
+                "message": "No data"
+            });
+
+            let error: EvaluationError = serde_json::from_value(json).unwrap();
+
+            assert_eq!(error.raw_data(), None);
+        }
     }
 
     mod error_cases {
@@ -1,10 +1,12 @@
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
 
-use super::{ExecutionUnits, Language, RedeemerPointer, TxOutputPointer};
+use super::{
+    Certificate, ExecutionUnits, GovernanceProposal, Language, RedeemerPointer, RewardAccount,
+    TxOutputPointer, Voter,
+};
 use crate::define_ogmios_error;
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 #[serde(tag = "language")]
 pub enum Script {
     #[serde(rename = "native")]
@@ -32,7 +34,7 @@ impl Script {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 #[serde(tag = "clause")]
 #[serde(rename_all = "camelCase")]
 pub enum ScriptClause {
@@ -58,14 +60,14 @@ pub enum ScriptClause {
     },
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, PartialEq)]
 pub struct ScriptError {
     pub validator: RedeemerPointer,
     pub error: ScriptExecutionError,
 }
 
 define_ogmios_error! {
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq)]
     pub enum ScriptExecutionError {
         3011 => InvalidRedeemerPointers {
             missing_scripts: Vec<RedeemerPointer>,
@@ -96,9 +98,35 @@ define_ogmios_error! {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(tag = "purpose")]
-#[serde(rename_all = "camelCase")]
+impl ScriptExecutionError {
+    /// Traces emitted by the script before failing, empty for anything other than a
+    /// validation failure
+    pub fn traces(&self) -> &[String] {
+        match self {
+            ScriptExecutionError::ValidationFailure { traces, .. } => traces,
+            _ => &[],
+        }
+    }
+
+    /// For 3161 (execution budget out of bounds), how far the used budget exceeds `max` as
+    /// `(memory, cpu)`, see [`ExecutionUnits::overage`]
+    pub fn budget_overage(&self, max: &ExecutionUnits) -> Option<(i128, i128)> {
+        match self {
+            ScriptExecutionError::ExecutionBudgetOutOfBounds { budget_used, .. } => {
+                budget_used.overage(max)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether any trace line contains `substring`
+    pub fn matches_trace(&self, substring: &str) -> bool {
+        self.traces().iter().any(|trace| trace.contains(substring))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(tag = "purpose", rename_all_fields = "camelCase")]
 pub enum ScriptPurpose {
     #[serde(rename = "spend")]
     Spend { output_reference: TxOutputPointer },
@@ -108,20 +136,114 @@ pub enum ScriptPurpose {
         policy: String,
     },
     #[serde(rename = "publish")]
-    Publish {
-        certificate: Value, // TODO:
-    },
+    Publish { certificate: Box<Certificate> },
     #[serde(rename = "withdraw")]
-    Withdraw {
-        /// Stake address (stake1...)
-        reward_account: String,
-    },
+    Withdraw { reward_account: RewardAccount },
     #[serde(rename = "propose")]
-    Propose {
-        proposal: Value, // TODO:
-    },
+    Propose { proposal: Box<GovernanceProposal> },
     #[serde(rename = "vote")]
-    Vote {
-        issuer: Value, // TODO:
-    },
+    Vote { issuer: Voter },
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::codec::{CredentialOrigin, GovernanceAction, VoterRole};
+
+    #[test]
+    fn validation_failure_traces() {
+        let json = json!({
+            "code": 3012,
+            "message": "Some scripts of the transactions terminated with error(s).",
+            "data": {
+                "validationError": "An error has occurred: The machine terminated because of an error, either from a built-in function or from an explicit use of 'error'.",
+                "traces": ["checking deadline", "PT5: deadline not reached"]
+            }
+        });
+        let error: ScriptExecutionError = serde_json::from_value(json).unwrap();
+
+        assert_eq!(error.traces().len(), 2);
+        assert!(error.matches_trace("deadline not reached"));
+        assert!(!error.matches_trace("signature missing"));
+    }
+
+    #[test]
+    fn execution_budget_overage() {
+        let json = json!({
+            "code": 3161,
+            "message": "The transaction ran out of execution budget!",
+            "data": { "budgetUsed": { "memory": 15_000_000, "cpu": 10_000_000_001u64 } }
+        });
+        let error: ScriptExecutionError = serde_json::from_value(json).unwrap();
+        let max: ExecutionUnits =
+            serde_json::from_value(json!({ "memory": 14_000_000, "cpu": 10_000_000_000u64 }))
+                .unwrap();
+
+        assert_eq!(error.budget_overage(&max), Some((1_000_000, 1)));
+    }
+
+    #[test]
+    fn other_errors_have_no_traces() {
+        let json = json!({
+            "code": 3111,
+            "message": "Missing datums",
+            "data": { "missingDatums": [] }
+        });
+        let error: ScriptExecutionError = serde_json::from_value(json).unwrap();
+
+        assert!(error.traces().is_empty());
+        assert!(!error.matches_trace(""));
+    }
+
+    #[test]
+    fn deserialize_governance_purposes() {
+        let purposes: Vec<ScriptPurpose> = serde_json::from_value(json!([
+            {
+                "purpose": "withdraw",
+                "rewardAccount": "stake1u9x9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m"
+            },
+            {
+                "purpose": "propose",
+                "proposal": {
+                    "deposit": { "ada": { "lovelace": 100000000000u64 } },
+                    "returnAccount": "stake1u9x9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m",
+                    "metadata": { "url": "https://example.com/proposal.json", "hash": "ab".repeat(32) },
+                    "action": { "type": "information" }
+                }
+            },
+            {
+                "purpose": "vote",
+                "issuer": { "role": "delegateRepresentative", "id": "bb".repeat(28), "from": "script" }
+            }
+        ]))
+        .unwrap();
+
+        let ScriptPurpose::Withdraw { reward_account } = &purposes[0] else {
+            panic!("Expected a withdrawal, got {:?}", purposes[0]);
+        };
+        assert_eq!(
+            *reward_account,
+            "stake1u9x9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m"
+        );
+
+        let ScriptPurpose::Propose { proposal } = &purposes[1] else {
+            panic!("Expected a proposal, got {:?}", purposes[1]);
+        };
+        assert_eq!(proposal.deposit.lovelace, 100000000000);
+        assert_eq!(proposal.return_account, *reward_account);
+        assert_eq!(proposal.action, GovernanceAction::Info);
+
+        assert_eq!(
+            purposes[2],
+            ScriptPurpose::Vote {
+                issuer: Voter {
+                    role: VoterRole::DelegateRepresentative,
+                    id: "bb".repeat(28),
+                    from: Some(CredentialOrigin::Script),
+                },
+            }
+        );
+    }
 }
@@ -0,0 +1,218 @@
+use std::fmt;
+use std::ops::{Add, Sub};
+
+use serde::{Deserialize, Serialize};
+
+/// Absolute slot number, counted from the start of the chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SlotNo(pub u64);
+
+impl SlotNo {
+    /// Number of slots from `earlier` to `self`, `None` if `earlier` is after `self`
+    pub fn checked_since(self, earlier: SlotNo) -> Option<u64> {
+        self.0.checked_sub(earlier.0)
+    }
+
+    /// The slot `slots` after this one, `None` past `u64::MAX`
+    pub fn checked_add(self, slots: u64) -> Option<SlotNo> {
+        self.0.checked_add(slots).map(SlotNo)
+    }
+
+    /// The slot `slots` before this one, `None` before the first slot
+    pub fn checked_sub(self, slots: u64) -> Option<SlotNo> {
+        self.0.checked_sub(slots).map(SlotNo)
+    }
+
+    /// The slot `slots` after this one, stopping at `u64::MAX`
+    pub fn saturating_add(self, slots: u64) -> SlotNo {
+        SlotNo(self.0.saturating_add(slots))
+    }
+
+    /// The slot `slots` before this one, stopping at the first slot
+    pub fn saturating_sub(self, slots: u64) -> SlotNo {
+        SlotNo(self.0.saturating_sub(slots))
+    }
+}
+
+impl From<u64> for SlotNo {
+    fn from(slot: u64) -> Self {
+        SlotNo(slot)
+    }
+}
+
+impl From<SlotNo> for u64 {
+    fn from(slot: SlotNo) -> Self {
+        slot.0
+    }
+}
+
+impl fmt::Display for SlotNo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The slot `slots` after this one. Panics past `u64::MAX`, in release builds too, see
+/// [`SlotNo::checked_add`].
+impl Add<u64> for SlotNo {
+    type Output = SlotNo;
+
+    fn add(self, slots: u64) -> SlotNo {
+        self.checked_add(slots).expect("slot number overflow")
+    }
+}
+
+/// The slot `slots` before this one. Panics before the first slot, in release builds too, see
+/// [`SlotNo::checked_sub`].
+impl Sub<u64> for SlotNo {
+    type Output = SlotNo;
+
+    fn sub(self, slots: u64) -> SlotNo {
+        self.checked_sub(slots).expect("slot number underflow")
+    }
+}
+
+/// Number of slots between two slots. Panics if `earlier` is after `self`, in release builds
+/// too, see [`SlotNo::checked_since`].
+impl Sub<SlotNo> for SlotNo {
+    type Output = u64;
+
+    fn sub(self, earlier: SlotNo) -> u64 {
+        self.checked_since(earlier)
+            .expect("slot is before the earlier one")
+    }
+}
+
+/// Epoch number, counted from the start of the chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EpochNo(pub u64);
+
+impl EpochNo {
+    pub fn next(self) -> EpochNo {
+        EpochNo(self.0 + 1)
+    }
+
+    /// The preceding epoch, `None` for the first one
+    pub fn previous(self) -> Option<EpochNo> {
+        self.0.checked_sub(1).map(EpochNo)
+    }
+}
+
+impl From<u64> for EpochNo {
+    fn from(epoch: u64) -> Self {
+        EpochNo(epoch)
+    }
+}
+
+impl From<EpochNo> for u64 {
+    fn from(epoch: EpochNo) -> Self {
+        epoch.0
+    }
+}
+
+impl fmt::Display for EpochNo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Slots from `start` included to `end` excluded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SlotRange {
+    pub start: SlotNo,
+    pub end: SlotNo,
+}
+
+impl SlotRange {
+    pub fn new(start: SlotNo, end: SlotNo) -> Self {
+        Self { start, end }
+    }
+
+    pub fn contains(&self, slot: SlotNo) -> bool {
+        self.start <= slot && slot < self.end
+    }
+
+    /// Number of slots in the range, zero if `end` isn't after `start`
+    pub fn len(&self) -> u64 {
+        self.end.checked_since(self.start).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The slots shared with `other`, `None` if there are none
+    pub fn intersection(&self, other: &SlotRange) -> Option<SlotRange> {
+        let range = SlotRange::new(self.start.max(other.start), self.end.min(other.end));
+        (!range.is_empty()).then_some(range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slot_arithmetic() {
+        let slot = SlotNo(4492800);
+        assert_eq!(slot + 21600, SlotNo(4514400));
+        assert_eq!(slot - 800, SlotNo(4492000));
+        assert_eq!(SlotNo(4514400) - slot, 21600);
+        assert_eq!(SlotNo(4514400).checked_since(slot), Some(21600));
+        assert_eq!(slot.checked_since(SlotNo(4514400)), None);
+        assert_eq!(serde_json::to_string(&slot).unwrap(), "4492800");
+        assert_eq!(serde_json::from_str::<SlotNo>("4492800").unwrap(), slot);
+        assert_eq!(u64::from(slot), 4492800);
+        assert_eq!(SlotNo::from(4492800), slot);
+    }
+
+    #[test]
+    fn slot_arithmetic_bounds() {
+        assert_eq!(SlotNo(u64::MAX - 1).checked_add(1), Some(SlotNo(u64::MAX)));
+        assert_eq!(SlotNo(u64::MAX).checked_add(1), None);
+        assert_eq!(SlotNo(1).checked_sub(1), Some(SlotNo(0)));
+        assert_eq!(SlotNo(0).checked_sub(1), None);
+        assert_eq!(SlotNo(u64::MAX).saturating_add(1), SlotNo(u64::MAX));
+        assert_eq!(SlotNo(5).saturating_sub(10), SlotNo(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "slot number overflow")]
+    fn slot_addition_overflow_panics() {
+        let _ = SlotNo(u64::MAX) + 1;
+    }
+
+    #[test]
+    #[should_panic(expected = "slot number underflow")]
+    fn slot_subtraction_underflow_panics() {
+        let _ = SlotNo(0) - 1;
+    }
+
+    #[test]
+    fn epoch_succession() {
+        assert_eq!(EpochNo(525).next(), EpochNo(526));
+        assert_eq!(EpochNo(526).previous(), Some(EpochNo(525)));
+        assert_eq!(EpochNo(0).previous(), None);
+        assert!(EpochNo(525) < EpochNo(525).next());
+    }
+
+    #[test]
+    fn slot_ranges() {
+        let range = SlotRange::new(SlotNo(100), SlotNo(200));
+        assert!(range.contains(SlotNo(100)));
+        assert!(range.contains(SlotNo(199)));
+        assert!(!range.contains(SlotNo(200)));
+        assert_eq!(range.len(), 100);
+        assert!(SlotRange::new(SlotNo(200), SlotNo(100)).is_empty());
+
+        let other = SlotRange::new(SlotNo(150), SlotNo(300));
+        assert_eq!(
+            range.intersection(&other),
+            Some(SlotRange::new(SlotNo(150), SlotNo(200)))
+        );
+        let disjoint = SlotRange::new(SlotNo(200), SlotNo(300));
+        assert_eq!(range.intersection(&disjoint), None);
+    }
+}
@@ -0,0 +1,139 @@
+use std::fmt;
+use std::sync::Arc;
+
+/// Errors detected locally, before a request is sent to Ogmios
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputError {
+    /// The input is not valid hex (odd length or a non-hex character)
+    InvalidHex(String),
+    /// The input is not a valid cardano-cli text envelope
+    InvalidEnvelope(String),
+    /// The decoded bytes are not a CBOR-encoded transaction
+    InvalidCbor(String),
+    /// The input is not a stake credential, as a bech32 stake address or hash, or hex hash
+    InvalidCredential(String),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::InvalidHex(reason) => write!(f, "invalid hex: {}", reason),
+            InputError::InvalidEnvelope(reason) => write!(f, "invalid text envelope: {}", reason),
+            InputError::InvalidCbor(reason) => write!(f, "invalid CBOR: {}", reason),
+            InputError::InvalidCredential(reason) => write!(f, "invalid credential: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// JSON-RPC code Ogmios answers with when it doesn't implement the requested method, typically
+/// one introduced by a later Ogmios version
+pub const METHOD_NOT_FOUND: i32 = -32601;
+
+/// The JSON-RPC code of an Ogmios error, implemented by every error type generated with
+/// [`define_ogmios_error!`](crate::define_ogmios_error)
+pub trait OgmiosErrorCode {
+    fn code(&self) -> i32;
+
+    /// Whether Ogmios doesn't support the method at all (-32601)
+    fn is_method_not_found(&self) -> bool {
+        self.code() == METHOD_NOT_FOUND
+    }
+}
+
+/// Error returned by client methods that can fail before or around the RPC call, wrapping the
+/// method's own Ogmios error type `E`
+#[derive(Debug)]
+pub enum OgmiosError<E> {
+    /// The input was rejected locally, no request was sent
+    Input(InputError),
+    /// Ogmios answered with an error
+    Rpc(E),
+    /// The request was cancelled by the caller before a response was received
+    Cancelled,
+    /// The connected Ogmios doesn't support `method`
+    MethodNotFound { method: String },
+    /// The request couldn't be sent, or its response couldn't be read or decoded
+    Transport(anyhow::Error),
+}
+
+impl<E> OgmiosError<E> {
+    /// The error Ogmios answered with, if any
+    pub fn rpc(&self) -> Option<&E> {
+        match self {
+            OgmiosError::Rpc(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Converts the error Ogmios answered with, leaving the other variants as they are
+    pub fn map_rpc<F>(self, f: impl FnOnce(E) -> F) -> OgmiosError<F> {
+        match self {
+            OgmiosError::Input(error) => OgmiosError::Input(error),
+            OgmiosError::Rpc(error) => OgmiosError::Rpc(f(error)),
+            OgmiosError::Cancelled => OgmiosError::Cancelled,
+            OgmiosError::MethodNotFound { method } => OgmiosError::MethodNotFound { method },
+            OgmiosError::Transport(error) => OgmiosError::Transport(error),
+        }
+    }
+}
+
+impl<E: OgmiosErrorCode> OgmiosError<E> {
+    /// Wraps an error returned by Ogmios for `method`, surfacing -32601 as
+    /// [`OgmiosError::MethodNotFound`]
+    pub fn from_rpc(method: &str, error: E) -> Self {
+        if error.is_method_not_found() {
+            OgmiosError::MethodNotFound {
+                method: method.to_string(),
+            }
+        } else {
+            OgmiosError::Rpc(error)
+        }
+    }
+}
+
+impl<E> From<InputError> for OgmiosError<E> {
+    fn from(error: InputError) -> Self {
+        OgmiosError::Input(error)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for OgmiosError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OgmiosError::Input(error) => write!(f, "{}", error),
+            OgmiosError::Rpc(error) => write!(f, "{}", error),
+            OgmiosError::Cancelled => write!(f, "request cancelled"),
+            OgmiosError::MethodNotFound { method } => {
+                write!(f, "method {} not supported by this Ogmios version", method)
+            }
+            OgmiosError::Transport(error) => write!(f, "{:#}", error),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for OgmiosError<E> {}
+
+/// Callback invoked with the method name, the raw response body and the error whenever a
+/// response fails to deserialize, e.g. to save failing payloads for later schema work
+#[derive(Clone)]
+pub struct DeserializeErrorHook(Arc<DeserializeErrorFn>);
+
+type DeserializeErrorFn = dyn Fn(&str, &str, &serde_json::Error) + Send + Sync;
+
+impl DeserializeErrorHook {
+    pub fn new(hook: impl Fn(&str, &str, &serde_json::Error) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(hook))
+    }
+
+    pub fn call(&self, method: &str, raw_body: &str, error: &serde_json::Error) {
+        (self.0)(method, raw_body, error)
+    }
+}
+
+impl fmt::Debug for DeserializeErrorHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DeserializeErrorHook")
+    }
+}
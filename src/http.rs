@@ -1,33 +1,388 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use anyhow::Context;
+use anyhow::{Context, anyhow, bail};
+use futures_util::future::{self, join_all};
+use futures_util::stream::{self, Stream, StreamExt};
 pub use reqwest::Url;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use serde::Serialize;
 use serde::de::DeserializeOwned;
+use tokio::sync::{Mutex, OnceCell, Semaphore, SemaphorePermit};
+use tokio_util::sync::CancellationToken;
 
-use crate::codec::{RpcRequest, RpcResponse, TxCbor};
-use crate::method::evaluate::{EvaluateRequestParams, Evaluation, EvaluationError};
+use crate::cache::ResponseCache;
+use crate::codec::{
+    Credential, Era, GovernanceProposalReference, Id, RewardAccount, RpcError, RpcRequest,
+    RpcResponse, RpcSuccess, StakePoolParameters, TxCbor, TxOutputPointer, TxPointer,
+    to_canonical_string, transaction_inputs,
+};
+use crate::error::{DeserializeErrorHook, InputError, OgmiosError, OgmiosErrorCode};
+use crate::method::block_height::{BlockHeight, BlockHeightError};
+use crate::method::constitution::{Constitution, ConstitutionError};
+use crate::method::epoch::EpochError;
+#[cfg(feature = "chrono")]
+use crate::method::era_summaries::{EraHistory, EraHistoryError};
+use crate::method::era_summaries::{EraSummariesError, EraSummary};
+use crate::method::evaluate::{
+    EvaluateAutoContextError, EvaluateRequestParams, Evaluation, EvaluationError,
+};
+use crate::method::governance::{
+    GovernanceProposalState, GovernanceProposalsError, GovernanceProposalsParams,
+};
+use crate::method::mempool::MempoolError;
+#[cfg(feature = "chrono")]
+use crate::method::network::StartTime;
+use crate::method::network::{GenesisConfig, GenesisConfigurationParams, NetworkQueryError};
 use crate::method::pparams::{ProtocolParams, ProtocolParamsError};
 use crate::method::rewards::{
+    DelegationAndReward, DelegationsAndRewardsError, DelegationsAndRewardsParams,
     RewardAccountSummariesError, RewardAccountSummariesParams, RewardAccountSummary,
+    RewardsProvenance, RewardsProvenanceError,
+};
+use crate::method::stake_distribution::{StakeDistribution, StakeDistributionError};
+use crate::method::stake_pools::{StakePoolsError, StakePoolsParams};
+use crate::method::submit::{
+    SubmitError, SubmitRequestParams, SubmitResult, TxStatus, TxStatusError,
 };
-use crate::method::submit::{SubmitError, SubmitRequestParams, SubmitResult};
 use crate::method::tip::{Tip, TipError};
+use crate::method::utxo::{Utxo, UtxoError, UtxoRequestParams};
+use crate::version::ServerVersion;
+use crate::ws::{MempoolSnapshot, OgmiosWsClient};
 
 pub struct OgmiosHttpClient {
     url: Url,
     client: reqwest::Client,
+    concurrency_limit: Option<Semaphore>,
+    on_deserialize_error: Option<DeserializeErrorHook>,
+    protocol_params_ttl: Duration,
+    protocol_params_cache: Mutex<Option<CachedProtocolParams>>,
+    response_cache: Option<Arc<dyn ResponseCache>>,
+    /// Detected through `/health` the first time a versioned rename applies, unless configured
+    server_version: OnceCell<Option<ServerVersion>>,
+    method_names: HashMap<String, String>,
+    versioned_method_names: HashMap<String, Vec<(ServerVersion, String)>>,
+    canonical_requests: bool,
+    require_id_match: bool,
 }
 
-// TODO: handle reqwest error
-impl OgmiosHttpClient {
+struct CachedProtocolParams {
+    epoch: u64,
+    checked_at: Instant,
+    params: Arc<ProtocolParams>,
+}
+
+pub struct OgmiosHttpClientBuilder {
+    url: Url,
+    local_address: Option<IpAddr>,
+    resolve: Vec<(String, SocketAddr)>,
+    timeout: Option<Duration>,
+    headers: HeaderMap,
+    client: Option<reqwest::Client>,
+    max_concurrent_requests: Option<NonZeroUsize>,
+    on_deserialize_error: Option<DeserializeErrorHook>,
+    protocol_params_ttl: Duration,
+    response_cache: Option<Arc<dyn ResponseCache>>,
+    server_version: Option<ServerVersion>,
+    method_names: HashMap<String, String>,
+    versioned_method_names: HashMap<String, Vec<(ServerVersion, String)>>,
+    canonical_requests: bool,
+    require_id_match: bool,
+}
+
+impl OgmiosHttpClientBuilder {
     pub fn new(url: Url) -> Self {
         Self {
             url,
-            client: reqwest::Client::new(),
+            local_address: None,
+            resolve: vec![],
+            timeout: None,
+            headers: HeaderMap::new(),
+            client: None,
+            max_concurrent_requests: None,
+            on_deserialize_error: None,
+            protocol_params_ttl: Duration::from_secs(60),
+            response_cache: None,
+            server_version: None,
+            method_names: HashMap::new(),
+            versioned_method_names: HashMap::new(),
+            canonical_requests: false,
+            require_id_match: false,
+        }
+    }
+
+    /// Bind outgoing connections to the given local address, for hosts where Ogmios is only
+    /// reachable through a specific interface
+    pub fn local_address(mut self, addr: IpAddr) -> Self {
+        self.local_address = Some(addr);
+        self
+    }
+
+    /// Override DNS resolution for `domain`, connecting to `addr` instead
+    pub fn resolve(mut self, domain: impl Into<String>, addr: SocketAddr) -> Self {
+        self.resolve.push((domain.into(), addr));
+        self
+    }
+
+    /// Fail requests not answered within `timeout`, from sending until the response body is
+    /// read, with [`OgmiosError::Transport`]. No timeout is applied by default.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Send `name: value` with every request, including `/health`, e.g. an `Authorization` or
+    /// API key header for Ogmios behind an authenticating proxy. Mark credentials with
+    /// [`HeaderValue::set_sensitive`] to keep them out of debug output.
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Send requests through `client` instead of building one, e.g. to share a connection pool
+    /// or reuse proxy and TLS settings. [`local_address`](Self::local_address),
+    /// [`resolve`](Self::resolve), [`timeout`](Self::timeout) and [`header`](Self::header) are
+    /// ignored, configure them on `client` instead.
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Bound the number of requests in flight at once, to avoid overwhelming a shared Ogmios
+    /// instance. Requests over the limit wait for a slot before being sent.
+    pub fn max_concurrent_requests(mut self, max: NonZeroUsize) -> Self {
+        self.max_concurrent_requests = Some(max);
+        self
+    }
+
+    /// Called with `(method, raw_body, error)` whenever a response fails to deserialize
+    pub fn on_deserialize_error(
+        mut self,
+        hook: impl Fn(&str, &str, &serde_json::Error) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_deserialize_error = Some(DeserializeErrorHook::new(hook));
+        self
+    }
+
+    /// How long [`OgmiosHttpClient::protocol_params_cached`] trusts its cached value before
+    /// checking whether the epoch has changed. Defaults to 60 seconds.
+    pub fn protocol_params_ttl(mut self, ttl: Duration) -> Self {
+        self.protocol_params_ttl = ttl;
+        self
+    }
+
+    /// Serve responses from `cache` when possible, e.g. an [`InMemoryResponseCache`] with
+    /// TTLs for rarely changing queries. No cache is used by default.
+    ///
+    /// [`InMemoryResponseCache`]: crate::InMemoryResponseCache
+    pub fn response_cache(mut self, cache: impl ResponseCache + 'static) -> Self {
+        self.response_cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Version of the Ogmios server, used to pick between the renames given to
+    /// [`Self::method_name_before`]. Detected through `/health` when not set.
+    pub fn server_version(mut self, version: ServerVersion) -> Self {
+        self.server_version = Some(version);
+        self
+    }
+
+    /// Send every request for `method` as `wire_name`, e.g. for a server or proxy exposing it
+    /// under another name. Takes precedence over [`Self::method_name_before`].
+    pub fn method_name(mut self, method: impl Into<String>, wire_name: impl Into<String>) -> Self {
+        self.method_names.insert(method.into(), wire_name.into());
+        self
+    }
+
+    /// Send `method` as `wire_name` to servers older than `version`, for methods Ogmios renamed
+    /// across versions. With several renames, the one for the oldest `version` still newer
+    /// than the server applies. The server version is the configured one, or else detected
+    /// through `/health` the first time `method` is sent; if it can't be determined, `method`
+    /// is sent as is.
+    pub fn method_name_before(
+        mut self,
+        method: impl Into<String>,
+        version: ServerVersion,
+        wire_name: impl Into<String>,
+    ) -> Self {
+        self.versioned_method_names
+            .entry(method.into())
+            .or_default()
+            .push((version, wire_name.into()));
+        self
+    }
+
+    /// Serialize request bodies with object keys sorted, so identical requests are sent as
+    /// identical bytes even when their params contain maps. Disabled by default.
+    pub fn canonical_requests(mut self, canonical: bool) -> Self {
+        self.canonical_requests = canonical;
+        self
+    }
+
+    /// Only accept batch responses carrying the id of their request. Disabled by default: when
+    /// responses come back without ids, e.g. behind a proxy stripping them, they are matched to
+    /// requests by position instead. This relies on the server answering in request order,
+    /// which Ogmios does but JSON-RPC doesn't guarantee. When enabled, id-less batch
+    /// responses are treated as if batching was unsupported.
+    pub fn require_id_match(mut self, require: bool) -> Self {
+        self.require_id_match = require;
+        self
+    }
+
+    /// Serve requests under `path` on the configured host, for Ogmios reverse-proxied under a
+    /// prefix, e.g. `/ogmios` for `https://gw.example.com/ogmios/`. JSON-RPC requests are sent
+    /// to the prefix itself and `/health` is resolved relative to it.
+    pub fn base_path(mut self, path: &str) -> Self {
+        self.url.set_path(path);
+        self
+    }
+
+    pub fn build(mut self) -> Result<OgmiosHttpClient, reqwest::Error> {
+        let client = match self.client.take() {
+            Some(client) => client,
+            None => {
+                let mut builder = reqwest::Client::builder()
+                    .local_address(self.local_address)
+                    .default_headers(std::mem::take(&mut self.headers));
+                for (domain, addr) in &self.resolve {
+                    builder = builder.resolve(domain, *addr);
+                }
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                builder.build()?
+            }
+        };
+        Ok(self.build_with(client))
+    }
+
+    /// Builds the client around `client`, which can't fail
+    fn build_with(self, client: reqwest::Client) -> OgmiosHttpClient {
+        OgmiosHttpClient {
+            url: self.url,
+            client,
+            concurrency_limit: self
+                .max_concurrent_requests
+                .map(|max| Semaphore::new(max.get())),
+            on_deserialize_error: self.on_deserialize_error,
+            protocol_params_ttl: self.protocol_params_ttl,
+            protocol_params_cache: Mutex::new(None),
+            response_cache: self.response_cache,
+            server_version: OnceCell::new_with(self.server_version.map(Some)),
+            method_names: self.method_names,
+            versioned_method_names: self.versioned_method_names,
+            canonical_requests: self.canonical_requests,
+            require_id_match: self.require_id_match,
         }
     }
+}
+
+impl OgmiosHttpClient {
+    /// A client with the default configuration
+    ///
+    /// # Panics
+    ///
+    /// If the HTTP client can't be built, e.g. when no TLS backend can be initialized. Use
+    /// [`OgmiosHttpClient::builder`] and [`OgmiosHttpClientBuilder::build`] to handle this
+    /// error instead.
+    pub fn new(url: Url) -> Self {
+        Self::builder(url)
+            .build()
+            .expect("failed to build HTTP client")
+    }
+
+    /// A client failing requests not answered within `timeout`, see
+    /// [`OgmiosHttpClientBuilder::timeout`]
+    ///
+    /// # Panics
+    ///
+    /// If the HTTP client can't be built, like [`OgmiosHttpClient::new`].
+    pub fn with_timeout(url: Url, timeout: Duration) -> Self {
+        Self::builder(url)
+            .timeout(timeout)
+            .build()
+            .expect("failed to build HTTP client")
+    }
+
+    /// A client sending requests through an already configured `client`, see
+    /// [`OgmiosHttpClientBuilder::client`]. Unlike [`OgmiosHttpClient::new`], this never
+    /// panics as `client` is used as is.
+    pub fn from_client(url: Url, client: reqwest::Client) -> Self {
+        Self::builder(url).build_with(client)
+    }
+
+    pub fn builder(url: Url) -> OgmiosHttpClientBuilder {
+        OgmiosHttpClientBuilder::new(url)
+    }
+
+    /// Version of the Ogmios server, as configured or reported by `/health`. `None` if the
+    /// endpoint can't be reached or reports no recognizable version.
+    pub async fn server_version(&self) -> Option<ServerVersion> {
+        *self
+            .server_version
+            .get_or_init(|| async {
+                match self.fetch_server_version().await {
+                    Ok(version) => Some(version),
+                    Err(e) => {
+                        tracing::warn!("Failed to detect Ogmios server version: {:#}", e);
+                        None
+                    }
+                }
+            })
+            .await
+    }
+
+    async fn fetch_server_version(&self) -> anyhow::Result<ServerVersion> {
+        #[derive(serde::Deserialize)]
+        struct Health {
+            version: String,
+        }
+
+        let health: Health = self
+            .client
+            .get(self.endpoint("health")?)
+            .send()
+            .await?
+            .json()
+            .await
+            .context("Failed to decode health response")?;
+        Ok(health.version.parse()?)
+    }
+
+    /// Resolves `path` under the base URL, which is treated as a directory whether or not it
+    /// ends with a slash, so `https://host/ogmios` and `https://host/ogmios/` both yield
+    /// `https://host/ogmios/health` for `health`
+    fn endpoint(&self, path: &str) -> anyhow::Result<Url> {
+        let mut base = self.url.clone();
+        if !base.path().ends_with('/') {
+            base.set_path(&format!("{}/", base.path()));
+        }
+        Ok(base.join(path)?)
+    }
+
+    /// Name `method` is sent as, after overrides and renames for the server version. The
+    /// version is only looked up for methods with renames.
+    async fn wire_method_name<'a>(&'a self, method: &'a str) -> &'a str {
+        if let Some(name) = self.method_names.get(method) {
+            return name;
+        }
+        let Some(renames) = self.versioned_method_names.get(method) else {
+            return method;
+        };
+        let Some(version) = self.server_version().await else {
+            return method;
+        };
+        renames
+            .iter()
+            .filter(|(before, _)| version < *before)
+            .min_by_key(|(before, _)| *before)
+            .map(|(_, name)| name.as_str())
+            .unwrap_or(method)
+    }
 
     async fn request<
         T: Serialize + Clone + fmt::Debug,
@@ -38,15 +393,28 @@ impl OgmiosHttpClient {
         method: &str,
         params: Option<T>,
     ) -> anyhow::Result<RpcResponse<U, E>> {
+        let method = self.wire_method_name(method).await;
+        let cache_key = match &self.response_cache {
+            Some(cache) => {
+                let key = to_canonical_string(&params)?;
+                if let Some(cached) = cache.get(method, &key)
+                    && let Ok(response) = serde_json::from_str(&cached)
+                {
+                    return Ok(response);
+                }
+                Some((cache, key))
+            }
+            None => None,
+        };
+
+        let _permit = self.acquire_permit().await?;
         let res = self
-            .client
-            .post(self.url.clone())
-            .json(&RpcRequest {
+            .post(&RpcRequest {
                 jsonrpc: "2.0".to_string(),
                 method: method.to_string(),
                 params: params.clone(),
                 id: None,
-            })
+            })?
             .send()
             .await
             .with_context(|| format!("Failed to send request for method '{}'", method))?;
@@ -57,7 +425,14 @@ impl OgmiosHttpClient {
             .await
             .with_context(|| format!("Failed to read response body for method '{}'", method))?;
 
-        serde_json::from_str(&response_text).with_context(|| {
+        let response = serde_json::from_str(&response_text);
+        if let (Err(e), Some(hook)) = (&response, &self.on_deserialize_error) {
+            hook.call(method, &response_text, e);
+        }
+        if let (Ok(RpcResponse::Success(_)), Some((cache, key))) = (&response, cache_key) {
+            cache.insert(method, &key, response_text.clone());
+        }
+        response.with_context(|| {
             format!(
                 "Failed to deserialize JSON response for method '{}'\n- Response status: {}\n- Response body:\n{}\n- Request body:\n{}",
                 method,
@@ -74,53 +449,1977 @@ impl OgmiosHttpClient {
         })
     }
 
-    pub async fn evaluate(&self, tx_cbor: &[u8]) -> Result<Vec<Evaluation>, EvaluationError> {
+    /// Sends a request and decodes its result, folding transport failures and Ogmios errors
+    /// into [`OgmiosError`]
+    async fn query<T, U, E>(&self, method: &str, params: Option<T>) -> Result<U, OgmiosError<E>>
+    where
+        T: Serialize + Clone + fmt::Debug,
+        U: DeserializeOwned,
+        E: DeserializeOwned + OgmiosErrorCode,
+    {
+        let response = self
+            .request(method, params)
+            .await
+            .map_err(OgmiosError::Transport)?;
+        Result::from(response).map_err(|e| OgmiosError::from_rpc(method, e))
+    }
+
+    /// Runs any client call until it completes or `token` is cancelled. On cancellation the
+    /// in-flight request is dropped, releasing its connection, and
+    /// [`OgmiosError::Cancelled`] is returned.
+    pub async fn cancellable<T, E>(
+        token: &CancellationToken,
+        call: impl Future<Output = Result<T, OgmiosError<E>>>,
+    ) -> Result<T, OgmiosError<E>> {
+        tokio::select! {
+            _ = token.cancelled() => Err(OgmiosError::Cancelled),
+            res = call => res,
+        }
+    }
+
+    fn post<B: Serialize>(&self, body: &B) -> anyhow::Result<reqwest::RequestBuilder> {
+        let request = self.client.post(self.url.clone());
+        if self.canonical_requests {
+            Ok(request
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(to_canonical_string(body)?))
+        } else {
+            Ok(request.json(body))
+        }
+    }
+
+    async fn acquire_permit(&self) -> anyhow::Result<Option<SemaphorePermit<'_>>> {
+        match &self.concurrency_limit {
+            Some(semaphore) => Ok(Some(semaphore.acquire().await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Sends several calls of the same method as a single JSON-RPC batch. Returns `None` when
+    /// the server doesn't answer with one response per request (i.e. batching is unsupported),
+    /// otherwise the responses in the same order as `params`.
+    async fn request_batch<T: Serialize>(
+        &self,
+        method: &str,
+        params: Vec<T>,
+    ) -> anyhow::Result<Option<Vec<RpcResponse<serde_json::Value, serde_json::Value>>>> {
+        let method = self.wire_method_name(method).await;
+        let _permit = self.acquire_permit().await?;
+        let requests: Vec<RpcRequest<T>> = params
+            .into_iter()
+            .map(|params| RpcRequest {
+                jsonrpc: "2.0".to_string(),
+                method: method.to_string(),
+                params: Some(params),
+                id: Some(Id::default()),
+            })
+            .collect();
+
+        let response_text = self
+            .post(&requests)?
+            .send()
+            .await
+            .with_context(|| format!("Failed to send batch request for method '{}'", method))?
+            .text()
+            .await
+            .with_context(|| {
+                format!("Failed to read batch response body for method '{}'", method)
+            })?;
+
+        let Ok(responses) = serde_json::from_str::<
+            Vec<RpcResponse<serde_json::Value, serde_json::Value>>,
+        >(&response_text) else {
+            return Ok(None);
+        };
+
+        let has_ids = responses.iter().all(|response| match response {
+            RpcResponse::Success(success) => success.id.is_some(),
+            RpcResponse::Error(error) => error.id.is_some(),
+        });
+        if !has_ids && !self.require_id_match {
+            return Ok((responses.len() == requests.len()).then_some(responses));
+        }
+
+        // Responses may come back in any order, so align them with the requests by id
+        let mut by_id: HashMap<serde_json::Value, _> = responses
+            .into_iter()
+            .filter_map(|response| {
+                let id = match &response {
+                    RpcResponse::Success(success) => success.id.clone(),
+                    RpcResponse::Error(error) => error.id.clone(),
+                }?;
+                Some((id, response))
+            })
+            .collect();
+        let aligned: Option<Vec<_>> = requests
+            .iter()
+            .map(|request| {
+                let id = serde_json::to_value(request.id.as_ref()?).ok()?;
+                by_id.remove(&id)
+            })
+            .collect();
+        Ok(aligned)
+    }
+
+    /// Sends several calls of `method` as a single JSON-RPC batch, decoding each element of
+    /// the response as a success or an error of the method's types, in the order of `params`.
+    /// Fails if the server doesn't support batching or answers for another method.
+    pub async fn batch_typed<P, T, E>(
+        &self,
+        method: &str,
+        params: Vec<P>,
+    ) -> anyhow::Result<Vec<RpcResponse<T, E>>>
+    where
+        P: Serialize,
+        T: DeserializeOwned,
+        E: DeserializeOwned,
+    {
+        let Some(responses) = self.request_batch(method, params).await? else {
+            bail!(
+                "Server did not answer the batch request for method '{}'",
+                method
+            );
+        };
+        // Responses echo the name the requests were sent as
+        let method = self.wire_method_name(method).await;
+        responses
+            .into_iter()
+            .enumerate()
+            .map(|(i, response)| {
+                let response_method = match &response {
+                    RpcResponse::Success(success) => success.method.as_deref(),
+                    RpcResponse::Error(error) => error.method.as_deref(),
+                };
+                if let Some(response_method) = response_method
+                    && response_method != method
+                {
+                    bail!(
+                        "Batch response {} is for method '{}', expected '{}'",
+                        i,
+                        response_method,
+                        method
+                    );
+                }
+                decode_batch_response(response).with_context(|| {
+                    format!(
+                        "Failed to deserialize batch response {} for method '{}'",
+                        i, method
+                    )
+                })
+            })
+            .collect()
+    }
+
+    pub async fn evaluate(
+        &self,
+        tx_cbor: &[u8],
+    ) -> Result<Vec<Evaluation>, OgmiosError<EvaluationError>> {
+        self.evaluate_tx(TxCbor::from_bytes(tx_cbor)).await
+    }
+
+    /// Evaluates a hex-encoded transaction, validating it locally first
+    pub async fn evaluate_hex(
+        &self,
+        tx_hex: &str,
+    ) -> Result<Vec<Evaluation>, OgmiosError<EvaluationError>> {
+        let transaction = TxCbor::from_hex(tx_hex)?;
+        self.evaluate_tx(transaction).await
+    }
+
+    /// Evaluates many transactions at once, returning results in the same order as `txs`.
+    /// Uses a single JSON-RPC batch request if the server supports it, falling back to
+    /// concurrent individual requests otherwise.
+    pub async fn evaluate_batch(
+        &self,
+        txs: Vec<&[u8]>,
+    ) -> Vec<Result<Vec<Evaluation>, OgmiosError<EvaluationError>>> {
+        let params: Vec<EvaluateRequestParams> = txs
+            .iter()
+            .map(|tx| EvaluateRequestParams {
+                transaction: TxCbor::from_bytes(tx),
+                additional_utxo: vec![],
+            })
+            .collect();
+
+        let mut results = Vec::with_capacity(txs.len());
+        match self.request_batch("evaluateTransaction", params).await {
+            Ok(Some(responses)) => {
+                for response in responses {
+                    let result =
+                        match decode_batch_response::<Vec<Evaluation>, EvaluationError>(response) {
+                            Ok(response) => Result::from(response)
+                                .map_err(|e| OgmiosError::from_rpc("evaluateTransaction", e)),
+                            Err(e) => Err(OgmiosError::Transport(
+                                anyhow::Error::new(e)
+                                    .context("Failed to deserialize batch evaluation"),
+                            )),
+                        };
+                    results.push(result);
+                }
+            }
+            Ok(None) => results = join_all(txs.into_iter().map(|tx| self.evaluate(tx))).await,
+            // The error can't be cloned, each transaction gets its message
+            Err(e) => {
+                for _ in &txs {
+                    results.push(Err(OgmiosError::Transport(anyhow!(
+                        "Failed to send batch evaluation: {:#}",
+                        e
+                    ))));
+                }
+            }
+        }
+        results
+    }
+
+    pub async fn evaluate_cancellable(
+        &self,
+        tx_cbor: &[u8],
+        token: &CancellationToken,
+    ) -> Result<Vec<Evaluation>, OgmiosError<EvaluationError>> {
+        Self::cancellable(token, self.evaluate(tx_cbor)).await
+    }
+
+    async fn evaluate_tx(
+        &self,
+        transaction: TxCbor,
+    ) -> Result<Vec<Evaluation>, OgmiosError<EvaluationError>> {
         let params = EvaluateRequestParams {
-            transaction: TxCbor {
-                cbor: hex::encode(tx_cbor),
-            },
+            transaction,
+            additional_utxo: vec![],
         };
-        self.request("evaluateTransaction", Some(params))
-            .await
-            .unwrap()
-            .into()
+        self.query("evaluateTransaction", Some(params)).await
     }
 
-    pub async fn submit(&self, tx_cbor: &[u8]) -> Result<SubmitResult, SubmitError> {
-        let params = SubmitRequestParams {
-            transaction: TxCbor {
-                cbor: hex::encode(tx_cbor),
-            },
+    /// Evaluates a transaction spending or referencing outputs that may not be on chain yet,
+    /// e.g. outputs of a transaction still in the mempool. Inputs not found on chain are looked
+    /// up in `pending` and sent along as additional UTxOs; without them Ogmios fails to build
+    /// the evaluation context (3004).
+    pub async fn evaluate_auto_context(
+        &self,
+        tx_cbor: &[u8],
+        pending: &[Utxo],
+    ) -> Result<Vec<Evaluation>, OgmiosError<EvaluateAutoContextError>> {
+        let inputs = transaction_inputs(tx_cbor).map_err(InputError::InvalidCbor)?;
+        let on_chain = self
+            .query_utxos_by_reference(inputs.clone())
+            .await
+            .map_err(|e| e.map_rpc(EvaluateAutoContextError::Lookup))?;
+        let is_input = |utxo: &Utxo, input: &TxOutputPointer| {
+            utxo.transaction == input.transaction && utxo.index == input.index
+        };
+        let additional_utxo = inputs
+            .iter()
+            .filter(|input| !on_chain.iter().any(|utxo| is_input(utxo, input)))
+            .filter_map(|input| pending.iter().find(|utxo| is_input(utxo, input)))
+            .cloned()
+            .collect();
+
+        let params = EvaluateRequestParams {
+            transaction: TxCbor::from_bytes(tx_cbor),
+            additional_utxo,
         };
-        self.request("submitTransaction", Some(params))
+        self.query("evaluateTransaction", Some(params))
             .await
-            .unwrap()
-            .into()
+            .map_err(|e| e.map_rpc(EvaluateAutoContextError::Evaluation))
+    }
+
+    pub async fn submit(&self, tx_cbor: &[u8]) -> Result<SubmitResult, OgmiosError<SubmitError>> {
+        self.submit_tx(TxCbor::from_bytes(tx_cbor)).await
+    }
+
+    /// Submits a hex-encoded transaction, validating it locally first
+    pub async fn submit_hex(&self, tx_hex: &str) -> Result<SubmitResult, OgmiosError<SubmitError>> {
+        let transaction = TxCbor::from_hex(tx_hex)?;
+        self.submit_tx(transaction).await
+    }
+
+    /// Submits a transaction read from a cardano-cli text envelope (the contents of a signed
+    /// transaction file)
+    pub async fn submit_text_envelope(
+        &self,
+        envelope: &str,
+    ) -> Result<SubmitResult, OgmiosError<SubmitError>> {
+        let transaction = TxCbor::from_text_envelope(envelope)?;
+        self.submit_tx(transaction).await
     }
 
-    pub async fn protocol_params(&self) -> Result<ProtocolParams, ProtocolParamsError> {
-        self.request("queryLedgerState/protocolParameters", None::<()>)
+    /// Submits many transactions concurrently, returning results in the same order as `txs`.
+    /// Concurrency is bounded by [`OgmiosHttpClientBuilder::max_concurrent_requests`] when set.
+    ///
+    /// Requests are not ordered on the wire: a transaction spending outputs of another one in
+    /// the same call may reach the node first and be rejected. Submit dependent transactions
+    /// sequentially, or in separate calls.
+    pub async fn submit_many(
+        &self,
+        txs: Vec<&[u8]>,
+    ) -> Vec<Result<SubmitResult, OgmiosError<SubmitError>>> {
+        join_all(txs.into_iter().map(|tx| self.submit(tx))).await
+    }
+
+    pub async fn submit_cancellable(
+        &self,
+        tx_cbor: &[u8],
+        token: &CancellationToken,
+    ) -> Result<SubmitResult, OgmiosError<SubmitError>> {
+        Self::cancellable(token, self.submit(tx_cbor)).await
+    }
+
+    async fn submit_tx(
+        &self,
+        transaction: TxCbor,
+    ) -> Result<SubmitResult, OgmiosError<SubmitError>> {
+        let params = SubmitRequestParams { transaction };
+        self.query("submitTransaction", Some(params)).await
+    }
+
+    pub async fn protocol_params(
+        &self,
+    ) -> Result<ProtocolParams, OgmiosError<ProtocolParamsError>> {
+        let raw = self.protocol_params_raw().await?;
+        serde_json::from_value(raw)
+            .context("Failed to deserialize protocol parameters")
+            .map_err(OgmiosError::Transport)
+    }
+
+    /// The protocol parameters as returned by Ogmios, for fields [`ProtocolParams`] doesn't
+    /// model yet
+    pub async fn protocol_params_raw(
+        &self,
+    ) -> Result<serde_json::Value, OgmiosError<ProtocolParamsError>> {
+        self.query("queryLedgerState/protocolParameters", None::<()>)
             .await
-            .expect("failed to get protocol parameters")
-            .into()
     }
 
-    pub async fn query_tip(&self) -> Result<Tip, TipError> {
-        self.request("queryLedgerState/tip", None::<()>)
+    /// Protocol parameters only change at epoch boundaries, so this keeps the last fetched
+    /// value and only refetches once the epoch has advanced. Within the configured TTL the
+    /// cached value is returned without any request; after it, only the epoch is queried.
+    pub async fn protocol_params_cached(
+        &self,
+    ) -> Result<Arc<ProtocolParams>, OgmiosError<ProtocolParamsError>> {
+        let mut cache = self.protocol_params_cache.lock().await;
+        if let Some(cached) = cache.as_ref()
+            && cached.checked_at.elapsed() < self.protocol_params_ttl
+        {
+            return Ok(cached.params.clone());
+        }
+
+        // The epoch query fails with the same ledger-state errors as the parameters query
+        let epoch: u64 = self
+            .query::<(), _, ProtocolParamsError>("queryLedgerState/epoch", None)
+            .await?;
+        if let Some(cached) = cache.as_mut()
+            && cached.epoch == epoch
+        {
+            cached.checked_at = Instant::now();
+            return Ok(cached.params.clone());
+        }
+
+        let params = Arc::new(self.protocol_params().await?);
+        *cache = Some(CachedProtocolParams {
+            epoch,
+            checked_at: Instant::now(),
+            params: params.clone(),
+        });
+        Ok(params)
+    }
+
+    pub async fn query_tip(&self) -> Result<Tip, OgmiosError<TipError>> {
+        self.query("queryLedgerState/tip", None::<()>).await
+    }
+
+    pub async fn query_block_height(&self) -> Result<BlockHeight, OgmiosError<BlockHeightError>> {
+        self.query("queryNetwork/blockHeight", None::<()>).await
+    }
+
+    /// System start of the network, the origin of slot to wall-clock conversions
+    #[cfg(feature = "chrono")]
+    pub async fn start_time(
+        &self,
+    ) -> Result<chrono::DateTime<chrono::Utc>, OgmiosError<NetworkQueryError>> {
+        let start: StartTime = self.query("queryNetwork/startTime", None::<()>).await?;
+        Ok(start.0)
+    }
+
+    /// System start and era summaries, for converting between slots and POSIX time
+    #[cfg(feature = "chrono")]
+    pub async fn era_history(&self) -> Result<EraHistory, OgmiosError<EraHistoryError>> {
+        let start: StartTime = self
+            .query("queryNetwork/startTime", None::<()>)
+            .await
+            .map_err(|e| e.map_rpc(EraHistoryError::StartTime))?;
+        let eras = self
+            .era_summaries()
             .await
-            .expect("failed to get tip")
-            .into()
+            .map_err(|e| e.map_rpc(EraHistoryError::EraSummaries))?;
+        Ok(EraHistory::new(start.into(), eras))
     }
 
+    /// Genesis configuration of `era`, which must be one of Shelley, Alonzo or Conway
+    pub async fn genesis_configuration(
+        &self,
+        era: Era,
+    ) -> Result<GenesisConfig, OgmiosError<NetworkQueryError>> {
+        let params = GenesisConfigurationParams { era };
+        self.query("queryNetwork/genesisConfiguration", Some(params))
+            .await
+    }
+
+    /// Bounds and slotting parameters of every era so far, for converting between slots and
+    /// time
+    pub async fn era_summaries(&self) -> Result<Vec<EraSummary>, OgmiosError<EraSummariesError>> {
+        self.query("queryLedgerState/eraSummaries", None::<()>)
+            .await
+    }
+
+    /// The constitution in force and its guardrails script, since Conway
+    pub async fn constitution(&self) -> Result<Constitution, OgmiosError<ConstitutionError>> {
+        self.query("queryLedgerState/constitution", None::<()>)
+            .await
+    }
+
+    pub async fn query_epoch(&self) -> Result<u64, OgmiosError<EpochError>> {
+        self.query("queryLedgerState/epoch", None::<()>).await
+    }
+
+    /// Active governance proposals, restricted to `proposals` unless empty
+    pub async fn query_governance_proposals(
+        &self,
+        proposals: Vec<GovernanceProposalReference>,
+    ) -> Result<Vec<GovernanceProposalState>, OgmiosError<GovernanceProposalsError>> {
+        let params = GovernanceProposalsParams { proposals };
+        self.query("queryLedgerState/governanceProposals", Some(params))
+            .await
+    }
+
+    pub async fn query_rewards_provenance(
+        &self,
+    ) -> Result<RewardsProvenance, OgmiosError<RewardsProvenanceError>> {
+        self.query("queryLedgerState/rewardsProvenance", None::<()>)
+            .await
+    }
+
+    pub async fn query_utxos_by_reference(
+        &self,
+        output_references: Vec<TxOutputPointer>,
+    ) -> Result<Vec<Utxo>, OgmiosError<UtxoError>> {
+        let params = UtxoRequestParams::ByOutputReference { output_references };
+        self.query("queryLedgerState/utxo", Some(params)).await
+    }
+
+    /// Queries outputs `0..output_count` of a transaction, returning those still unspent
+    /// sorted by index. Spent outputs are simply absent.
+    pub async fn query_utxos_by_transaction(
+        &self,
+        tx_id: &str,
+        output_count: u32,
+    ) -> Result<Vec<Utxo>, OgmiosError<UtxoError>> {
+        let output_references = (0..output_count)
+            .map(|index| TxOutputPointer {
+                transaction: TxPointer {
+                    id: tx_id.to_string(),
+                },
+                index,
+            })
+            .collect();
+        let mut utxos = self.query_utxos_by_reference(output_references).await?;
+        utxos.sort_by_key(|utxo| utxo.index);
+        Ok(utxos)
+    }
+
+    /// Looks a transaction up in the mempool through `ws` if given, then on chain through its
+    /// outputs `0..output_count`. The mempool is checked first so that a transaction moving
+    /// from the mempool into a block in between is still found on chain.
+    ///
+    /// Ogmios only finds UTxOs by output reference, so `output_count` bounds the outputs looked
+    /// up: the transaction is on chain as long as any of them is unspent. Mempool queries are
+    /// only available over WebSocket, hence `ws`; without it a transaction that isn't on chain
+    /// is [`TxStatus::Unknown`]. A mempool snapshot is acquired and released on `ws`, which
+    /// releases any snapshot acquired on it before; pass a dedicated connection to keep one.
+    pub async fn transaction_status(
+        &self,
+        tx_id: &str,
+        output_count: u32,
+        ws: Option<&mut OgmiosWsClient>,
+    ) -> Result<TxStatus, OgmiosError<TxStatusError>> {
+        if let Some(ws) = ws {
+            let mut snapshot = MempoolSnapshot::acquire(ws).await.map_err(mempool_error)?;
+            let in_mempool = snapshot.has(tx_id).await;
+            // Released even if the lookup failed, rather than before `ws`'s next request
+            let released = snapshot.release().await;
+            let in_mempool = in_mempool.map_err(mempool_error)?;
+            released.map_err(mempool_error)?;
+            if in_mempool {
+                return Ok(TxStatus::InMempool);
+            }
+        }
+
+        let utxos = self
+            .query_utxos_by_transaction(tx_id, output_count)
+            .await
+            .map_err(|e| e.map_rpc(TxStatusError::Lookup))?;
+        if utxos.is_empty() {
+            Ok(TxStatus::Unknown)
+        } else {
+            Ok(TxStatus::OnChain)
+        }
+    }
+
+    /// Credentials are normalized first, see [`RewardAccountSummariesParams::normalize`]. Ones
+    /// that don't parse fail locally with [`InputError::InvalidCredential`].
+    ///
+    /// [`InputError::InvalidCredential`]: crate::error::InputError::InvalidCredential
     pub async fn reward_account_summaries(
         &self,
         keys: Option<Vec<String>>,
         scripts: Option<Vec<String>>,
-    ) -> Result<HashMap<String, RewardAccountSummary>, RewardAccountSummariesError> {
+    ) -> Result<HashMap<String, RewardAccountSummary>, OgmiosError<RewardAccountSummariesError>>
+    {
         let params = RewardAccountSummariesParams { keys, scripts };
-        self.request("queryLedgerState/rewardAccountSummaries", Some(params))
+        let params = params.normalize()?;
+        self.query("queryLedgerState/rewardAccountSummaries", Some(params))
+            .await
+    }
+
+    /// Same as [`Self::reward_account_summaries`] for `credentials`, querying at most
+    /// `chunk_size` of them per request and streaming the summaries as chunks come in.
+    /// Duplicate credentials are queried once, and each account is yielded once.
+    pub fn reward_account_summaries_chunked(
+        &self,
+        credentials: Vec<Credential>,
+        chunk_size: usize,
+    ) -> impl Stream<
+        Item = Result<(String, RewardAccountSummary), OgmiosError<RewardAccountSummariesError>>,
+    > + '_ {
+        let mut queried = HashSet::new();
+        let credentials: Vec<Credential> = credentials
+            .into_iter()
+            .filter(|credential| queried.insert(credential.clone()))
+            .collect();
+        let chunks: Vec<Vec<Credential>> = credentials
+            .chunks(chunk_size.max(1))
+            .map(<[Credential]>::to_vec)
+            .collect();
+
+        let mut yielded = HashSet::new();
+        stream::iter(chunks)
+            .then(move |chunk| async move {
+                let params = RewardAccountSummariesParams::from(chunk);
+                let summaries: Result<HashMap<String, RewardAccountSummary>, _> = self
+                    .query("queryLedgerState/rewardAccountSummaries", Some(params))
+                    .await;
+                match summaries {
+                    Ok(summaries) => summaries.into_iter().map(Ok).collect(),
+                    Err(e) => vec![Err(e)],
+                }
+            })
+            .flat_map(stream::iter)
+            .filter(move |item| {
+                let new = match item {
+                    Ok((account, _)) => yielded.insert(account.clone()),
+                    Err(_) => true,
+                };
+                future::ready(new)
+            })
+    }
+
+    /// Same as [`Self::reward_account_summaries`], keyed by [`RewardAccount`]
+    pub async fn reward_account_summaries_by_account(
+        &self,
+        keys: Option<Vec<String>>,
+        scripts: Option<Vec<String>>,
+    ) -> Result<
+        HashMap<RewardAccount, RewardAccountSummary>,
+        OgmiosError<RewardAccountSummariesError>,
+    > {
+        let params = RewardAccountSummariesParams { keys, scripts };
+        let params = params.normalize()?;
+        self.query("queryLedgerState/rewardAccountSummaries", Some(params))
+            .await
+    }
+
+    /// Relative stake and VRF key hash of every pool, keyed by `pool1...` id
+    pub async fn live_stake_distribution(
+        &self,
+    ) -> Result<StakeDistribution, OgmiosError<StakeDistributionError>> {
+        self.query("queryLedgerState/liveStakeDistribution", None::<()>)
+            .await
+    }
+
+    /// Parameters of registered stake pools keyed by pool id, restricted to the `pool1...`
+    /// ids in `filter` if given
+    pub async fn stake_pools(
+        &self,
+        filter: Option<Vec<String>>,
+    ) -> Result<HashMap<String, StakePoolParameters>, OgmiosError<StakePoolsError>> {
+        let params = filter.map(StakePoolsParams::from);
+        self.query("queryLedgerState/stakePools", params).await
+    }
+
+    pub async fn query_delegations_and_rewards(
+        &self,
+        credentials: Vec<Credential>,
+    ) -> Result<HashMap<String, DelegationAndReward>, OgmiosError<DelegationsAndRewardsError>> {
+        let params = DelegationsAndRewardsParams::from(credentials);
+        self.query("queryLedgerState/delegationsAndRewards", Some(params))
+            .await
+    }
+}
+
+/// Decodes an untyped element of a batch response into the method's response type
+/// Mempool queries fail with `anyhow` errors, which carry a [`MempoolError`] when Ogmios
+/// answered with one
+fn mempool_error(error: anyhow::Error) -> OgmiosError<TxStatusError> {
+    match error.downcast::<MempoolError>() {
+        Ok(error) => OgmiosError::Rpc(TxStatusError::Mempool(error)),
+        Err(error) => OgmiosError::Transport(error),
+    }
+}
+
+fn decode_batch_response<U: DeserializeOwned, E: DeserializeOwned>(
+    response: RpcResponse<serde_json::Value, serde_json::Value>,
+) -> Result<RpcResponse<U, E>, serde_json::Error> {
+    Ok(match response {
+        RpcResponse::Success(success) => RpcResponse::Success(RpcSuccess {
+            jsonrpc: success.jsonrpc,
+            method: success.method,
+            result: serde_json::from_value(success.result)?,
+            id: success.id,
+        }),
+        RpcResponse::Error(error) => RpcResponse::Error(RpcError {
+            jsonrpc: error.jsonrpc,
+            method: error.method,
+            error: serde_json::from_value(error.error)?,
+            id: error.id,
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::cache::InMemoryResponseCache;
+    use crate::method::tip::Tip;
+    use crate::test_utils::{MockHttpServer, MockWsServer};
+
+    const TIP_RESPONSE: &str = r#"{"jsonrpc":"2.0","method":"queryLedgerState/tip","result":{"slot":1234,"id":"1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef"},"id":null}"#;
+
+    #[tokio::test]
+    async fn resolve_override_targets_overridden_address() {
+        let server = MockHttpServer::respond_with(TIP_RESPONSE).await;
+        let url = Url::parse(&format!("http://ogmios.invalid:{}/", server.addr.port())).unwrap();
+
+        let client = OgmiosHttpClient::builder(url)
+            .resolve("ogmios.invalid", server.addr)
+            .local_address(IpAddr::V4(Ipv4Addr::LOCALHOST))
+            .build()
+            .unwrap();
+        let tip = client.query_tip().await.unwrap();
+
+        assert!(matches!(tip, Tip::Point { slot: 1234, .. }));
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].json()["method"], "queryLedgerState/tip");
+        assert!(
+            requests[0]
+                .header("host")
+                .is_some_and(|host| host.starts_with("ogmios.invalid"))
+        );
+    }
+
+    fn protocol_params_server(epoch: Arc<AtomicU64>) -> impl Future<Output = MockHttpServer> {
+        let pparams =
+            std::fs::read_to_string("tests/fixtures/queryLedgerState/protocolParameters.json")
+                .unwrap();
+        MockHttpServer::start(move |request| {
+            let body = match request.json()["method"].as_str().unwrap() {
+                "queryLedgerState/epoch" => format!(
+                    r#"{{"jsonrpc":"2.0","method":"queryLedgerState/epoch","result":{},"id":null}}"#,
+                    epoch.load(Ordering::SeqCst)
+                ),
+                _ => pparams.clone(),
+            };
+            async move { body }
+        })
+    }
+
+    fn count_method(server: &MockHttpServer, method: &str) -> usize {
+        server
+            .requests()
+            .iter()
+            .filter(|request| request.json()["method"] == method)
+            .count()
+    }
+
+    #[tokio::test]
+    async fn protocol_params_raw_keeps_unmodeled_fields() {
+        let server = protocol_params_server(Arc::new(AtomicU64::new(500))).await;
+        let client = OgmiosHttpClient::new(server.url());
+
+        let raw = client.protocol_params_raw().await.unwrap();
+        assert_eq!(
+            raw["delegateRepresentativeVotingThresholds"]["noConfidence"],
+            serde_json::json!("67/100")
+        );
+
+        let params = client.protocol_params().await.unwrap();
+        assert_eq!(raw["minFeeCoefficient"], params.min_fee_coefficient);
+    }
+
+    #[tokio::test]
+    async fn protocol_params_cached_within_ttl() {
+        let server = protocol_params_server(Arc::new(AtomicU64::new(500))).await;
+        let client = OgmiosHttpClient::new(server.url());
+
+        let first = client.protocol_params_cached().await.unwrap();
+        let second = client.protocol_params_cached().await.unwrap();
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(
+            count_method(&server, "queryLedgerState/protocolParameters"),
+            1
+        );
+        assert_eq!(count_method(&server, "queryLedgerState/epoch"), 1);
+    }
+
+    #[tokio::test]
+    async fn protocol_params_cached_refetches_on_new_epoch() {
+        let epoch = Arc::new(AtomicU64::new(500));
+        let server = protocol_params_server(epoch.clone()).await;
+        let client = OgmiosHttpClient::builder(server.url())
+            .protocol_params_ttl(Duration::ZERO)
+            .build()
+            .unwrap();
+
+        let first = client.protocol_params_cached().await.unwrap();
+        let same_epoch = client.protocol_params_cached().await.unwrap();
+        assert!(Arc::ptr_eq(&first, &same_epoch));
+        assert_eq!(
+            count_method(&server, "queryLedgerState/protocolParameters"),
+            1
+        );
+
+        epoch.store(501, Ordering::SeqCst);
+        let next_epoch = client.protocol_params_cached().await.unwrap();
+        assert!(!Arc::ptr_eq(&first, &next_epoch));
+        assert_eq!(
+            count_method(&server, "queryLedgerState/protocolParameters"),
+            2
+        );
+    }
+
+    fn evaluation_result(memory: u64) -> serde_json::Value {
+        serde_json::json!([{"validator":{"index":0,"purpose":"spend"},"budget":{"memory":memory,"cpu":1000}}])
+    }
+
+    fn evaluation_memory(result: &Result<Vec<Evaluation>, OgmiosError<EvaluationError>>) -> u64 {
+        let budget = &result.as_ref().unwrap()[0].budget;
+        budget.memory.0.to_integer().try_into().unwrap()
+    }
+
+    #[tokio::test]
+    async fn evaluate_batch_single_request() {
+        let server = MockHttpServer::start(|request| {
+            let batch = request.json();
+            // Answer out of order, with the second transaction failing
+            let responses: Vec<serde_json::Value> = batch
+                .as_array()
+                .unwrap()
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(i, req)| match i {
+                    1 => serde_json::json!({"jsonrpc":"2.0","method":"evaluateTransaction","error":{"code":3004,"message":"Cannot create evaluation context","data":{"reason":"unknown inputs"}},"id":req["id"]}),
+                    _ => serde_json::json!({"jsonrpc":"2.0","method":"evaluateTransaction","result":evaluation_result(i as u64),"id":req["id"]}),
+                })
+                .collect();
+            async move { serde_json::to_string(&responses).unwrap() }
+        })
+        .await;
+        let client = OgmiosHttpClient::new(server.url());
+
+        let results = client.evaluate_batch(vec![&[0x84], &[0x84], &[0x84]]).await;
+
+        assert_eq!(server.requests().len(), 1);
+        assert_eq!(results.len(), 3);
+        assert_eq!(evaluation_memory(&results[0]), 0);
+        assert!(matches!(
+            results[1],
+            Err(OgmiosError::Rpc(
+                EvaluationError::CannotCreateEvaluationContext { .. }
+            ))
+        ));
+        assert_eq!(evaluation_memory(&results[2]), 2);
+    }
+
+    async fn id_stripping_server() -> MockHttpServer {
+        MockHttpServer::start(|request| async move {
+            let responses: Vec<_> = request
+                .json()
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|request| {
+                    json!({
+                        "jsonrpc": "2.0",
+                        "method": "queryLedgerState/epoch",
+                        "result": request["params"]["n"],
+                    })
+                })
+                .collect();
+            json!(responses).to_string()
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn batch_without_ids_aligned_by_position() {
+        let server = id_stripping_server().await;
+        let client = OgmiosHttpClient::new(server.url());
+
+        let responses: Vec<RpcResponse<u64, serde_json::Value>> = client
+            .batch_typed(
+                "queryLedgerState/epoch",
+                vec![json!({ "n": 1 }), json!({ "n": 2 })],
+            )
+            .await
+            .unwrap();
+        let epochs: Vec<_> = responses
+            .into_iter()
+            .map(|response| Result::from(response).unwrap())
+            .collect();
+        assert_eq!(epochs, [1, 2]);
+    }
+
+    #[tokio::test]
+    async fn batch_without_ids_rejected_when_required() {
+        let server = id_stripping_server().await;
+        let client = OgmiosHttpClient::builder(server.url())
+            .require_id_match(true)
+            .build()
+            .unwrap();
+
+        let result = client
+            .batch_typed::<_, u64, serde_json::Value>(
+                "queryLedgerState/epoch",
+                vec![json!({ "n": 1 }), json!({ "n": 2 })],
+            )
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn batch_typed_decodes_mixed_results() {
+        let server = MockHttpServer::start(|request| {
+            let batch = request.json();
+            let requests = batch.as_array().unwrap();
+            let responses = json!([
+                {
+                    "jsonrpc": "2.0",
+                    "method": "queryLedgerState/tip",
+                    "error": {
+                        "code": 2001,
+                        "message": "Era mismatch",
+                        "data": { "queryEra": "babbage", "ledgerEra": "conway" }
+                    },
+                    "id": requests[1]["id"]
+                },
+                {
+                    "jsonrpc": "2.0",
+                    "method": "queryLedgerState/tip",
+                    "result": { "slot": 1234, "id": "ab".repeat(32) },
+                    "id": requests[0]["id"]
+                }
+            ]);
+            async move { responses.to_string() }
+        })
+        .await;
+        let client = OgmiosHttpClient::new(server.url());
+
+        let responses: Vec<RpcResponse<Tip, TipError>> = client
+            .batch_typed("queryLedgerState/tip", vec![(), ()])
+            .await
+            .unwrap();
+
+        let results: Vec<Result<Tip, TipError>> = responses.into_iter().map(Result::from).collect();
+        assert!(matches!(&results[0], Ok(Tip::Point { slot: 1234, .. })));
+        assert!(matches!(&results[1], Err(TipError::EraMismatch { .. })));
+
+        let error = client
+            .batch_typed::<(), Tip, TipError>("queryNetwork/tip", vec![(), ()])
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("expected 'queryNetwork/tip'"));
+    }
+
+    #[tokio::test]
+    async fn batch_typed_with_method_name_override() {
+        let server = MockHttpServer::start(|request| {
+            let responses: Vec<_> = request
+                .json()
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|request| {
+                    json!({
+                        "jsonrpc": "2.0",
+                        "method": request["method"],
+                        "result": { "slot": 1234, "id": "ab".repeat(32) },
+                        "id": request["id"]
+                    })
+                })
+                .collect();
+            async move { json!(responses).to_string() }
+        })
+        .await;
+        let client = OgmiosHttpClient::builder(server.url())
+            .method_name("queryLedgerState/tip", "queryNetwork/tip")
+            .build()
+            .unwrap();
+
+        let responses: Vec<RpcResponse<Tip, TipError>> = client
+            .batch_typed("queryLedgerState/tip", vec![(), ()])
+            .await
+            .unwrap();
+        assert_eq!(responses.len(), 2);
+        assert_eq!(server.requests()[0].json()[0]["method"], "queryNetwork/tip");
+    }
+
+    #[tokio::test]
+    async fn evaluate_batch_falls_back_to_individual_requests() {
+        let server = MockHttpServer::start(|request| {
+            let body = request.json();
+            let response = if body.is_array() {
+                serde_json::json!({"jsonrpc":"2.0","error":{"code":-32600,"message":"Invalid request"},"id":null})
+            } else {
+                let cbor = body["params"]["transaction"]["cbor"].as_str().unwrap();
+                let memory = u64::from_str_radix(cbor, 16).unwrap();
+                serde_json::json!({"jsonrpc":"2.0","method":"evaluateTransaction","result":evaluation_result(memory),"id":null})
+            };
+            async move { response.to_string() }
+        })
+        .await;
+        let client = OgmiosHttpClient::new(server.url());
+
+        let results = client.evaluate_batch(vec![&[0x01], &[0x02]]).await;
+
+        assert_eq!(server.requests().len(), 3);
+        assert_eq!(evaluation_memory(&results[0]), 1);
+        assert_eq!(evaluation_memory(&results[1]), 2);
+    }
+
+    #[tokio::test]
+    async fn max_concurrent_requests_bounds_in_flight() {
+        let in_flight = Arc::new(AtomicU64::new(0));
+        let max_in_flight = Arc::new(AtomicU64::new(0));
+        let server = {
+            let in_flight = in_flight.clone();
+            let max_in_flight = max_in_flight.clone();
+            MockHttpServer::start(move |_| {
+                let in_flight = in_flight.clone();
+                let max_in_flight = max_in_flight.clone();
+                async move {
+                    let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_in_flight.fetch_max(current, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    TIP_RESPONSE.to_string()
+                }
+            })
+            .await
+        };
+        let client = Arc::new(
+            OgmiosHttpClient::builder(server.url())
+                .max_concurrent_requests(NonZeroUsize::new(2).unwrap())
+                .build()
+                .unwrap(),
+        );
+
+        let handles: Vec<_> = (0..10)
+            .map(|_| {
+                let client = client.clone();
+                tokio::spawn(async move { client.query_tip().await })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap().unwrap();
+        }
+
+        assert_eq!(server.requests().len(), 10);
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn deserialize_error_hook_receives_raw_body() {
+        let body = r#"{"jsonrpc":"2.0","method":"queryLedgerState/tip","result":{"slot":"not a number"},"id":null}"#;
+        let server = MockHttpServer::respond_with(body).await;
+        let captured = Arc::new(std::sync::Mutex::new(vec![]));
+        let client = {
+            let captured = captured.clone();
+            OgmiosHttpClient::builder(server.url())
+                .on_deserialize_error(move |method, raw_body, _| {
+                    captured
+                        .lock()
+                        .unwrap()
+                        .push((method.to_string(), raw_body.to_string()));
+                })
+                .build()
+                .unwrap()
+        };
+
+        let result = client
+            .request::<(), Tip, TipError>("queryLedgerState/tip", None)
+            .await;
+
+        assert!(result.is_err());
+        let captured = captured.lock().unwrap();
+        assert_eq!(
+            *captured,
+            vec![("queryLedgerState/tip".to_string(), body.to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn cancelled_request_returns_cancelled() {
+        let server = MockHttpServer::start(|_| async {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            TIP_RESPONSE.to_string()
+        })
+        .await;
+        let client = OgmiosHttpClient::new(server.url());
+        let token = CancellationToken::new();
+
+        let cancel = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            cancel.cancel();
+        });
+        let started = Instant::now();
+        let result = client.submit_cancellable(&[0x84], &token).await;
+
+        assert!(matches!(result, Err(OgmiosError::Cancelled)));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn reward_account_summaries_rejects_invalid_credentials() {
+        let server = MockHttpServer::start(|_| async { String::new() }).await;
+        let client = OgmiosHttpClient::new(server.url());
+
+        let result = client
+            .reward_account_summaries(Some(vec!["key1".to_string()]), None)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(OgmiosError::Input(InputError::InvalidCredential(_)))
+        ));
+        assert!(server.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn reward_account_summaries_in_chunks() {
+        let server = MockHttpServer::start(|request| {
+            let params = &request.json()["params"];
+            let mut result = serde_json::Map::new();
+            for credential in ["keys", "scripts"]
+                .iter()
+                .filter_map(|field| params[field].as_array())
+                .flatten()
+            {
+                let rewards = credential.as_str().unwrap().len() as u64;
+                result.insert(
+                    credential.as_str().unwrap().to_string(),
+                    json!({
+                        "delegate": null,
+                        "rewards": { "ada": { "lovelace": rewards } },
+                        "deposit": { "ada": { "lovelace": 2000000 } }
+                    }),
+                );
+            }
+            let response = json!({
+                "jsonrpc": "2.0",
+                "method": "queryLedgerState/rewardAccountSummaries",
+                "result": result,
+                "id": null
+            });
+            async move { response.to_string() }
+        })
+        .await;
+        let client = OgmiosHttpClient::new(server.url());
+
+        let credentials = vec![
+            Credential::Key("aa".repeat(28)),
+            Credential::Script("bb".repeat(28)),
+            Credential::Key("cc".repeat(28)),
+            Credential::Key("aa".repeat(28)),
+            Credential::Key("dd".repeat(28)),
+            Credential::Script("ee".repeat(28)),
+        ];
+        let summaries: Vec<_> = client
+            .reward_account_summaries_chunked(credentials, 2)
+            .collect()
+            .await;
+
+        let mut accounts: Vec<String> = summaries
+            .into_iter()
+            .map(|summary| summary.unwrap().0)
+            .collect();
+        accounts.sort();
+        assert_eq!(
+            accounts,
+            ["aa", "bb", "cc", "dd", "ee"].map(|byte| byte.repeat(28))
+        );
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(
+            requests[1].json()["params"],
+            json!({ "keys": ["cc".repeat(28), "dd".repeat(28)] })
+        );
+        assert_eq!(
+            requests[2].json()["params"],
+            json!({ "scripts": ["ee".repeat(28)] })
+        );
+    }
+
+    #[tokio::test]
+    async fn utxo_quantities_beyond_u64() {
+        let server = MockHttpServer::respond_with(format!(
+            r#"{{"jsonrpc":"2.0","method":"queryLedgerState/utxo","result":[{{"transaction":{{"id":"{}"}},"index":0,"address":"addr_test1vz09v9yfxguvlp0zsnrpa3tdtm7el8xufp3m5lsm7qxzclgmzkket","value":{{"ada":{{"lovelace":2000000}},"{}":{{"01":340282366920938463463374607431768211456}}}}}}],"id":null}}"#,
+            "aa".repeat(32),
+            "bb".repeat(28)
+        ))
+        .await;
+        let client = OgmiosHttpClient::new(server.url());
+
+        let utxos = client.query_utxos_by_reference(vec![]).await.unwrap();
+        assert_eq!(
+            utxos[0].value.assets[&"bb".repeat(28)]["01"],
+            num::BigInt::from(u128::MAX) + 1
+        );
+    }
+
+    #[tokio::test]
+    async fn live_stake_distribution_two_pools() {
+        let server = MockHttpServer::respond_with(
+            r#"{"jsonrpc":"2.0","method":"queryLedgerState/liveStakeDistribution","result":{"pool1qqqqqdk4zhsjuxxd8jyvwncf5eucfskz0xjjj64fdmlgj735lr9":{"stake":"3/1000","vrf":"d9f8ba8a1b8e3e3b5c9c7e0b6c2a1f9d8e7c6b5a49382716f5e4d3c2b1a09f8e"},"pool1z5uqdk7dzdxaae5633fqfcu2eqzy3a3rgtuvy087fdld7yws0xt":{"stake":"997/1000","vrf":"a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90"}},"id":null}"#,
+        )
+        .await;
+        let client = OgmiosHttpClient::new(server.url());
+
+        let distribution = client.live_stake_distribution().await.unwrap();
+        assert_eq!(distribution.len(), 2);
+        let (pool, entry) = distribution.top_n_pools(1)[0];
+        assert_eq!(
+            *pool,
+            "pool1z5uqdk7dzdxaae5633fqfcu2eqzy3a3rgtuvy087fdld7yws0xt"
+        );
+        assert_eq!(
+            entry.stake.0,
+            num::BigRational::new(997.into(), 1000.into())
+        );
+        assert_eq!(
+            entry.vrf,
+            "a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90"
+        );
+        assert_eq!(
+            server.requests()[0].json()["method"],
+            "queryLedgerState/liveStakeDistribution"
+        );
+    }
+
+    #[tokio::test]
+    async fn evaluate_auto_context_supplies_pending_inputs() {
+        let on_chain = "aa".repeat(32);
+        let pending = "bb".repeat(32);
+        let server = MockHttpServer::start({
+            let on_chain = on_chain.clone();
+            move |request| {
+                let on_chain = on_chain.clone();
+                async move {
+                    let request = request.json();
+                    let result = match request["method"].as_str().unwrap() {
+                        "queryLedgerState/utxo" => serde_json::json!([{
+                            "transaction": { "id": on_chain },
+                            "index": 0,
+                            "address": "addr_test1vz09v9yfxguvlp0zsnrpa3tdtm7el8xufp3m5lsm7qxzclgmzkket",
+                            "value": { "ada": { "lovelace": 5_000_000 } }
+                        }]),
+                        _ => evaluation_result(42),
+                    };
+                    serde_json::json!({ "jsonrpc": "2.0", "method": request["method"], "result": result, "id": null })
+                        .to_string()
+                }
+            }
+        })
+        .await;
+        let client = OgmiosHttpClient::new(server.url());
+
+        let utxo = |id: &str, index: u32| -> Utxo {
+            serde_json::from_value(serde_json::json!({
+                "transaction": { "id": id },
+                "index": index,
+                "address": "addr_test1wpnlxv2xv9a9ucvnvzqakwepzl9ltx7jzgm53av2e9ncv4sysemm8",
+                "value": { "ada": { "lovelace": 2_000_000 } },
+                "datum": "d87980"
+            }))
+            .unwrap()
+        };
+        // [{0: [[h'aa..', 0], [h'bb..', 1]]}, {}, true, null]
+        let tx = hex::decode(format!(
+            "84a10082825820{}00825820{}01a0f5f6",
+            on_chain, pending
+        ))
+        .unwrap();
+
+        let evaluations = client
+            .evaluate_auto_context(&tx, &[utxo(&pending, 0), utxo(&pending, 1)])
+            .await
+            .unwrap();
+        assert_eq!(evaluations.len(), 1);
+
+        let requests = server.requests();
+        let queried = &requests[0].json()["params"]["outputReferences"];
+        assert_eq!(queried.as_array().unwrap().len(), 2);
+        let additional = &requests[1].json()["params"]["additionalUtxo"];
+        assert_eq!(
+            *additional,
+            serde_json::json!([{
+                "transaction": { "id": pending },
+                "index": 1,
+                "address": "addr_test1wpnlxv2xv9a9ucvnvzqakwepzl9ltx7jzgm53av2e9ncv4sysemm8",
+                "value": { "ada": { "lovelace": 2_000_000 } },
+                "datum": "d87980"
+            }])
+        );
+    }
+
+    #[tokio::test]
+    async fn evaluate_auto_context_reports_lookup_errors() {
+        let server = MockHttpServer::respond_with(
+            r#"{"jsonrpc":"2.0","method":"queryLedgerState/utxo","error":{"code":2002,"message":"unavailable in current era"},"id":null}"#,
+        )
+        .await;
+        let client = OgmiosHttpClient::new(server.url());
+        // [{0: [[h'aa..', 0]]}, {}, true, null]
+        let tx = hex::decode(format!("84a10081825820{}00a0f5f6", "aa".repeat(32))).unwrap();
+
+        let result = client.evaluate_auto_context(&tx, &[]).await;
+
+        assert!(matches!(
+            result,
+            Err(OgmiosError::Rpc(EvaluateAutoContextError::Lookup(
+                UtxoError::UnavailableInCurrentEra { .. }
+            )))
+        ));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn evaluate_auto_context_rejects_invalid_cbor() {
+        let server = MockHttpServer::start(|_| async { String::new() }).await;
+        let client = OgmiosHttpClient::new(server.url());
+
+        let result = client.evaluate_auto_context(&[0xff], &[]).await;
+
+        assert!(matches!(
+            result,
+            Err(OgmiosError::Input(InputError::InvalidCbor(_)))
+        ));
+        assert!(server.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn empty_evaluation() {
+        let server = MockHttpServer::respond_with(
+            r#"{"jsonrpc":"2.0","method":"evaluateTransaction","result":[],"id":null}"#,
+        )
+        .await;
+        let client = OgmiosHttpClient::new(server.url());
+
+        // [{}, {5: [[0, 0, 121([]), [0, 0]]]}, true, null]
+        let with_redeemer = hex::decode("84a0a10581840000d87980820000f5f6").unwrap();
+        assert!(client.evaluate(&with_redeemer).await.unwrap().is_empty());
+        assert!(client.evaluate_hex("84a0a0f5f6").await.unwrap().is_empty());
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn malformed_transactions_are_left_to_the_server() {
+        let server = MockHttpServer::respond_with(
+            r#"{"jsonrpc":"2.0","method":"evaluateTransaction","error":{"code":-32602,"message":"Invalid transaction","data":{"byron":"","shelley":"","allegra":"","mary":"","alonzo":"","babbage":"","conway":"invalid body"}},"id":null}"#,
+        )
+        .await;
+        let client = OgmiosHttpClient::new(server.url());
+
+        // [0, {}]
+        assert!(matches!(
+            client.evaluate(&[0x82, 0x00, 0xa0]).await,
+            Err(OgmiosError::Rpc(EvaluationError::Deserialization { .. }))
+        ));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn method_not_found_is_surfaced() {
+        let server = MockHttpServer::respond_with(
+            r#"{"jsonrpc":"2.0","error":{"code":-32601,"message":"Unknown method: queryLedgerState/constitution"},"id":null}"#,
+        )
+        .await;
+        let client = OgmiosHttpClient::new(server.url());
+
+        let result = client.constitution().await;
+        let Err(OgmiosError::MethodNotFound { method }) = result else {
+            panic!("Expected MethodNotFound, got {:?}", result);
+        };
+        assert_eq!(method, "queryLedgerState/constitution");
+        let error: ConstitutionError = serde_json::from_str(
+            r#"{"code":-32601,"message":"Unknown method: queryLedgerState/constitution"}"#,
+        )
+        .unwrap();
+        assert!(
+            error
+                .to_string()
+                .contains("method not supported by this Ogmios version")
+        );
+
+        let result = client.submit_hex("84a0a0f5f6").await;
+        let Err(OgmiosError::MethodNotFound { method }) = result else {
+            panic!("Expected MethodNotFound, got {:?}", result);
+        };
+        assert_eq!(method, "submitTransaction");
+    }
+
+    #[tokio::test]
+    async fn default_headers_are_sent() {
+        let server = MockHttpServer::respond_with(
+            r#"{"jsonrpc":"2.0","method":"queryLedgerState/epoch","result":528}"#,
+        )
+        .await;
+        let mut api_key = HeaderValue::from_static("secret");
+        api_key.set_sensitive(true);
+        let client = OgmiosHttpClient::builder(server.url())
+            .header(
+                reqwest::header::AUTHORIZATION,
+                HeaderValue::from_static("Bearer token"),
+            )
+            .header(HeaderName::from_static("x-api-key"), api_key)
+            .build()
+            .unwrap();
+
+        assert_eq!(client.query_epoch().await.unwrap(), 528);
+        let request = &server.requests()[0];
+        assert_eq!(request.header("authorization"), Some("Bearer token"));
+        assert_eq!(request.header("x-api-key"), Some("secret"));
+    }
+
+    #[tokio::test]
+    async fn from_client_sends_through_given_client() {
+        let server = MockHttpServer::respond_with(
+            r#"{"jsonrpc":"2.0","method":"queryLedgerState/epoch","result":528}"#,
+        )
+        .await;
+        let mut headers = HeaderMap::new();
+        headers.insert("x-shared-client", HeaderValue::from_static("yes"));
+        let shared = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .unwrap();
+        let client = OgmiosHttpClient::from_client(server.url(), shared);
+
+        assert_eq!(client.query_epoch().await.unwrap(), 528);
+        assert!(
+            server
+                .requests()
+                .iter()
+                .all(|request| request.header("x-shared-client") == Some("yes"))
+        );
+    }
+
+    #[tokio::test]
+    async fn slow_server_times_out() {
+        let server = MockHttpServer::start(|_| async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            String::new()
+        })
+        .await;
+        let client = OgmiosHttpClient::with_timeout(server.url(), Duration::from_millis(100));
+
+        let started = Instant::now();
+        let result = client.query_tip().await;
+        let Err(OgmiosError::Transport(error)) = result else {
+            panic!("Expected a transport error, got {:?}", result);
+        };
+        assert!(error.chain().any(|e| {
+            e.downcast_ref::<reqwest::Error>()
+                .is_some_and(reqwest::Error::is_timeout)
+        }));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn unreachable_server_is_a_transport_error() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        drop(listener);
+        let client = OgmiosHttpClient::new(url);
+
+        let result = client.query_tip().await;
+        assert!(matches!(result, Err(OgmiosError::Transport(_))));
+    }
+
+    #[tokio::test]
+    async fn malformed_hex_is_rejected_before_sending() {
+        let server = MockHttpServer::respond_with("{}").await;
+        let client = OgmiosHttpClient::new(server.url());
+
+        let result = client.submit_hex("84zz").await;
+        assert!(matches!(
+            result,
+            Err(OgmiosError::Input(InputError::InvalidHex(_)))
+        ));
+        let result = client.evaluate_hex("a0").await;
+        assert!(matches!(
+            result,
+            Err(OgmiosError::Input(InputError::InvalidCbor(_)))
+        ));
+        let result = client.submit_text_envelope("not json").await;
+        assert!(matches!(
+            result,
+            Err(OgmiosError::Input(InputError::InvalidEnvelope(_)))
+        ));
+        assert!(server.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn query_utxos_by_transaction_returns_unspent_outputs() {
+        let tx_id = "ab".repeat(32);
+        let server = MockHttpServer::start({
+            let tx_id = tx_id.clone();
+            move |_| {
+                let utxo = |index: u32| {
+                    json!({
+                        "transaction": { "id": tx_id },
+                        "index": index,
+                        "address": "addr_test1vz09v9yfxguvlp0zsnrpa3tdtm7el8xufp3m5lsm7qxzclgmzkket",
+                        "value": { "ada": { "lovelace": 2_000_000 } }
+                    })
+                };
+                // Output 1 is spent
+                let body = json!({
+                    "jsonrpc": "2.0",
+                    "method": "queryLedgerState/utxo",
+                    "result": [utxo(2), utxo(0)]
+                })
+                .to_string();
+                async move { body }
+            }
+        })
+        .await;
+        let client = OgmiosHttpClient::new(server.url());
+
+        let utxos = client.query_utxos_by_transaction(&tx_id, 3).await.unwrap();
+
+        let indices: Vec<u32> = utxos.iter().map(|utxo| utxo.index).collect();
+        assert_eq!(indices, vec![0, 2]);
+        let request = server.requests()[0].json();
+        assert_eq!(request["method"], "queryLedgerState/utxo");
+        let references = request["params"]["outputReferences"].as_array().unwrap();
+        assert_eq!(references.len(), 3);
+        assert_eq!(
+            references[1],
+            json!({ "transaction": { "id": tx_id }, "index": 1 })
+        );
+    }
+
+    #[tokio::test]
+    async fn submit_many_preserves_order() {
+        let server = MockHttpServer::start(|request| {
+            let cbor = request.json()["params"]["transaction"]["cbor"]
+                .as_str()
+                .unwrap()
+                .to_string();
+            async move {
+                if cbor == "84a2" {
+                    // Delay the failure so it completes after the success
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    json!({
+                        "jsonrpc": "2.0",
+                        "method": "submitTransaction",
+                        "error": { "code": 3121, "message": "Empty input set" }
+                    })
+                    .to_string()
+                } else {
+                    json!({
+                        "jsonrpc": "2.0",
+                        "method": "submitTransaction",
+                        "result": { "transaction": { "id": "ab".repeat(32) } }
+                    })
+                    .to_string()
+                }
+            }
+        })
+        .await;
+        let client = OgmiosHttpClient::new(server.url());
+
+        let results = client.submit_many(vec![&[0x84, 0xa2], &[0x84, 0xa1]]).await;
+
+        assert_eq!(results.len(), 2);
+        assert!(matches!(
+            results[0],
+            Err(OgmiosError::Rpc(SubmitError::EmptyInputSet { .. }))
+        ));
+        assert_eq!(results[1].as_ref().unwrap().transaction.id, "ab".repeat(32));
+    }
+
+    async fn transaction_status_servers(
+        on_chain: bool,
+        in_mempool: bool,
+    ) -> (MockHttpServer, MockWsServer) {
+        let http = MockHttpServer::start(move |request| {
+            let request = request.json();
+            let body = match request["method"].as_str().unwrap() {
+                "queryLedgerState/utxo" => {
+                    let reference = &request["params"]["outputReferences"][0];
+                    let utxos = if on_chain {
+                        json!([{
+                            "transaction": reference["transaction"],
+                            "index": reference["index"],
+                            "address": "addr_test1vz09v9yfxguvlp0zsnrpa3tdtm7el8xufp3m5lsm7qxzclgmzkket",
+                            "value": { "ada": { "lovelace": 2_000_000 } }
+                        }])
+                    } else {
+                        json!([])
+                    };
+                    json!({ "jsonrpc": "2.0", "method": "queryLedgerState/utxo", "result": utxos })
+                        .to_string()
+                }
+                _ => TIP_RESPONSE.to_string(),
+            };
+            async move { body }
+        })
+        .await;
+        let ws = MockWsServer::rpc(move |method, _| match method {
+            "acquireMempool" => json!({ "result": { "acquired": "mempool", "slot": 1240 } }),
+            "hasTransaction" => json!({ "result": in_mempool }),
+            _ => json!({ "result": { "released": "mempool" } }),
+        })
+        .await;
+        (http, ws)
+    }
+
+    #[tokio::test]
+    async fn transaction_status_on_chain() {
+        let (http, ws) = transaction_status_servers(true, false).await;
+        let client = OgmiosHttpClient::new(http.url());
+        let mut ws = OgmiosWsClient::connect(ws.url()).await.unwrap();
+
+        let status = client
+            .transaction_status(&"ab".repeat(32), 2, Some(&mut ws))
             .await
+            .unwrap();
+
+        assert_eq!(status, TxStatus::OnChain);
+        assert_eq!(http.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn transaction_status_in_mempool() {
+        let (http, ws) = transaction_status_servers(false, true).await;
+        let client = OgmiosHttpClient::new(http.url());
+        let mut ws = OgmiosWsClient::connect(ws.url()).await.unwrap();
+
+        let status = client
+            .transaction_status(&"ab".repeat(32), 2, Some(&mut ws))
+            .await
+            .unwrap();
+
+        assert_eq!(status, TxStatus::InMempool);
+        // The mempool is checked first, the chain only for transactions not found there
+        assert!(http.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn transaction_status_unknown() {
+        let (http, ws) = transaction_status_servers(false, false).await;
+        let client = OgmiosHttpClient::new(http.url());
+        let mut ws = OgmiosWsClient::connect(ws.url()).await.unwrap();
+
+        let status = client
+            .transaction_status(&"ab".repeat(32), 2, Some(&mut ws))
+            .await
+            .unwrap();
+        assert_eq!(status, TxStatus::Unknown);
+
+        let (http, _) = transaction_status_servers(false, true).await;
+        let client = OgmiosHttpClient::new(http.url());
+        let status = client
+            .transaction_status(&"ab".repeat(32), 2, None)
+            .await
+            .unwrap();
+        assert_eq!(status, TxStatus::Unknown);
+    }
+
+    #[tokio::test]
+    async fn transaction_status_releases_snapshot_on_error() {
+        let (http, _) = transaction_status_servers(false, false).await;
+        let methods = Arc::new(Mutex::new(Vec::new()));
+        let ws = MockWsServer::rpc({
+            let methods = methods.clone();
+            move |method, _| {
+                methods.lock().unwrap().push(method.to_string());
+                match method {
+                    "acquireMempool" => {
+                        json!({ "result": { "acquired": "mempool", "slot": 1240 } })
+                    }
+                    "hasTransaction" => {
+                        json!({ "error": { "code": -32602, "message": "Invalid transaction id" } })
+                    }
+                    _ => json!({ "result": { "released": "mempool" } }),
+                }
+            }
+        })
+        .await;
+        let client = OgmiosHttpClient::new(http.url());
+        let mut ws = OgmiosWsClient::connect(ws.url()).await.unwrap();
+
+        let result = client
+            .transaction_status(&"ab".repeat(32), 2, Some(&mut ws))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(OgmiosError::Rpc(TxStatusError::Mempool(
+                MempoolError::Unknown { .. }
+            )))
+        ));
+        assert_eq!(
+            *methods.lock().unwrap(),
+            ["acquireMempool", "hasTransaction", "releaseMempool"]
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[tokio::test]
+    async fn era_history_combines_start_time_and_era_summaries() {
+        let server = MockHttpServer::start(|request| {
+            let method = request.json()["method"].as_str().unwrap().to_string();
+            let result = match method.as_str() {
+                "queryNetwork/startTime" => json!("2017-09-23T21:44:51Z"),
+                _ => json!([{
+                    "start": { "time": { "seconds": 0 }, "slot": 0, "epoch": 0 },
+                    "end": null,
+                    "parameters": { "epochLength": 21600, "slotLength": { "milliseconds": 20000 }, "safeZone": null }
+                }]),
+            };
+            let response = json!({"jsonrpc":"2.0","method":method,"result":result});
+            async move { response.to_string() }
+        })
+        .await;
+        let client = OgmiosHttpClient::builder(server.url()).build().unwrap();
+
+        let history = client.era_history().await.unwrap();
+        assert_eq!(
+            history.slot_to_posix_time(crate::codec::SlotNo(1)),
+            Some(1506203111000)
+        );
+        assert_eq!(
+            history.posix_time_to_slot(1506203111000),
+            Some(crate::codec::SlotNo(1))
+        );
+    }
+
+    #[tokio::test]
+    async fn response_cache_serves_repeated_queries() {
+        let server = MockHttpServer::respond_with(
+            r#"{"jsonrpc":"2.0","method":"queryLedgerState/eraSummaries","result":[]}"#,
+        )
+        .await;
+        let client = OgmiosHttpClient::builder(server.url())
+            .response_cache(
+                InMemoryResponseCache::new(16)
+                    .ttl("queryLedgerState/eraSummaries", Duration::from_secs(60)),
+            )
+            .build()
+            .unwrap();
+
+        for _ in 0..3 {
+            assert!(client.era_summaries().await.unwrap().is_empty());
+        }
+        assert_eq!(count_method(&server, "queryLedgerState/eraSummaries"), 1);
+
+        // Methods without a TTL always hit the network
+        let server = MockHttpServer::respond_with(TIP_RESPONSE).await;
+        let client = OgmiosHttpClient::builder(server.url())
+            .response_cache(InMemoryResponseCache::new(16))
+            .build()
+            .unwrap();
+        client.query_tip().await.unwrap();
+        client.query_tip().await.unwrap();
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn query_epoch() {
+        let server = MockHttpServer::respond_with(
+            r#"{"jsonrpc":"2.0","method":"queryLedgerState/epoch","result":528,"id":null}"#,
+        )
+        .await;
+        let client = OgmiosHttpClient::new(server.url());
+
+        assert_eq!(client.query_epoch().await.unwrap(), 528);
+        assert_eq!(
+            server.requests()[0].json()["method"],
+            "queryLedgerState/epoch"
+        );
+    }
+
+    const REWARDS_PROVENANCE_RESPONSE: &str = r#"{
+        "jsonrpc": "2.0",
+        "result": {
+            "desiredNumberOfStakePools": 500,
+            "stakePoolPledgeInfluence": "3/10",
+            "totalRewardsInEpoch": { "ada": { "lovelace": 9000000000 } },
+            "activeStakeInEpoch": { "ada": { "lovelace": 21000000000000 } },
+            "totalStakeInEpoch": { "ada": { "lovelace": 33000000000000 } },
+            "stakePools": {}
+        },
+        "id": null
+    }"#;
+
+    async fn health_server(version: &'static str) -> MockHttpServer {
+        MockHttpServer::start(move |request| async move {
+            if request.path == "/health" {
+                json!({ "version": version }).to_string()
+            } else {
+                REWARDS_PROVENANCE_RESPONSE.to_string()
+            }
+        })
+        .await
+    }
+
+    fn rpc_methods(server: &MockHttpServer) -> Vec<String> {
+        server
+            .requests()
+            .iter()
+            .filter(|request| request.path != "/health")
+            .map(|request| request.json()["method"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    fn with_legacy_provenance(builder: OgmiosHttpClientBuilder) -> OgmiosHttpClientBuilder {
+        builder
+            .method_name_before(
+                "queryLedgerState/rewardsProvenance",
+                ServerVersion::new(6, 0, 0),
+                "queryLedgerState/rewardsProvenanceLegacy",
+            )
+            .method_name_before(
+                "queryLedgerState/rewardsProvenance",
+                ServerVersion::new(5, 0, 0),
+                "queryLedgerState/rewardsProvenanceV4",
+            )
+    }
+
+    #[tokio::test]
+    async fn methods_sent_as_is_without_health_check() {
+        let server = health_server("v5.6.0").await;
+        let client = OgmiosHttpClient::new(server.url());
+        let provenance = client.query_rewards_provenance().await.unwrap();
+        assert_eq!(provenance.desired_number_of_stake_pools, 500);
+        assert_eq!(rpc_methods(&server), ["queryLedgerState/rewardsProvenance"]);
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn renamed_method_picked_from_configured_version() {
+        let server = health_server("v6.11.2").await;
+        for (version, expected) in [
+            (
+                ServerVersion::new(5, 6, 0),
+                "queryLedgerState/rewardsProvenanceLegacy",
+            ),
+            (
+                ServerVersion::new(4, 2, 0),
+                "queryLedgerState/rewardsProvenanceV4",
+            ),
+            (
+                ServerVersion::new(6, 0, 0),
+                "queryLedgerState/rewardsProvenance",
+            ),
+        ] {
+            let client = with_legacy_provenance(OgmiosHttpClient::builder(server.url()))
+                .server_version(version)
+                .build()
+                .unwrap();
+            client.query_rewards_provenance().await.unwrap();
+            assert_eq!(rpc_methods(&server).last().unwrap(), expected);
+        }
+        // The configured version is used as is
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn renamed_method_picked_from_detected_version() {
+        for (reported, expected) in [
+            (
+                "v5.6.0 (8a1b2c3d)",
+                "queryLedgerState/rewardsProvenanceLegacy",
+            ),
+            ("v6.11.2 (c6a4b2d9)", "queryLedgerState/rewardsProvenance"),
+            // Undetectable, sent as is
+            ("nightly", "queryLedgerState/rewardsProvenance"),
+        ] {
+            let server = health_server(reported).await;
+            let client = with_legacy_provenance(OgmiosHttpClient::builder(server.url()))
+                .build()
+                .unwrap();
+            client.query_rewards_provenance().await.unwrap();
+            client.query_rewards_provenance().await.unwrap();
+            assert_eq!(rpc_methods(&server), [expected, expected]);
+            // Detected once
+            assert_eq!(server.requests().len(), 3);
+        }
+    }
+
+    #[tokio::test]
+    async fn requests_sent_under_base_path() {
+        let server = MockHttpServer::respond_with(TIP_RESPONSE).await;
+
+        for base in ["ogmios", "ogmios/"] {
+            let client = OgmiosHttpClient::new(server.url().join(base).unwrap());
+            client.query_tip().await.unwrap();
+        }
+        let client = OgmiosHttpClient::builder(server.url())
+            .base_path("/ogmios")
+            .build()
+            .unwrap();
+        client.query_tip().await.unwrap();
+        assert_eq!(client.url.path(), "/ogmios");
+
+        let paths: Vec<_> = server
+            .requests()
+            .iter()
+            .map(|request| request.path.clone())
+            .collect();
+        assert_eq!(paths, ["/ogmios", "/ogmios/", "/ogmios"]);
+    }
+
+    #[tokio::test]
+    async fn health_resolved_under_base_path() {
+        let server = MockHttpServer::start(|request| async move {
+            match request.path.as_str() {
+                "/ogmios/health" => json!({ "version": "v6.11.2" }).to_string(),
+                _ => "not found".to_string(),
+            }
+        })
+        .await;
+
+        for base in ["ogmios", "ogmios/"] {
+            let client = OgmiosHttpClient::new(server.url().join(base).unwrap());
+            assert_eq!(
+                client.server_version().await,
+                Some(ServerVersion::new(6, 11, 2))
+            );
+        }
+
+        let client = OgmiosHttpClient::builder(server.url())
+            .base_path("/ogmios")
+            .build()
+            .unwrap();
+        assert_eq!(
+            client.server_version().await,
+            Some(ServerVersion::new(6, 11, 2))
+        );
+        assert_eq!(client.endpoint("health").unwrap().path(), "/ogmios/health");
+    }
+
+    #[tokio::test]
+    async fn method_name_overrides_apply_to_every_request() {
+        let server = MockHttpServer::respond_with(REWARDS_PROVENANCE_RESPONSE).await;
+        let client = OgmiosHttpClient::builder(server.url())
+            .method_name(
+                "queryLedgerState/rewardsProvenance",
+                "queryLedgerState/rewardsProvenanceV2",
+            )
+            .method_name("queryLedgerState/epoch", "queryLedgerState/currentEpoch")
+            .build()
+            .unwrap();
+        client.query_rewards_provenance().await.unwrap();
+        let _ = client.query_epoch().await;
+        assert_eq!(
+            rpc_methods(&server),
+            [
+                "queryLedgerState/rewardsProvenanceV2",
+                "queryLedgerState/currentEpoch"
+            ]
+        );
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn stake_pools_filter_params() {
+        let server = MockHttpServer::respond_with(
+            r#"{"jsonrpc":"2.0","method":"queryLedgerState/stakePools","result":{}}"#,
+        )
+        .await;
+        let client = OgmiosHttpClient::new(server.url());
+
+        client.stake_pools(None).await.unwrap();
+        let pool = "pool1prc9hna2mgamtspchrygc66s9n4tlkvh39e3t9zccef4kzc3ns2";
+        client
+            .stake_pools(Some(vec![pool.to_string()]))
+            .await
+            .unwrap();
+
+        let requests = server.requests();
+        assert!(requests[0].json()["params"].is_null());
+        assert_eq!(
+            requests[1].json()["params"],
+            json!({ "stakePools": [{ "id": pool }] })
+        );
+    }
+
+    #[tokio::test]
+    async fn canonical_requests_sort_map_keys() {
+        let server = MockHttpServer::respond_with(TIP_RESPONSE).await;
+        let client = OgmiosHttpClient::builder(server.url())
+            .canonical_requests(true)
+            .build()
+            .unwrap();
+
+        for _ in 0..2 {
+            // A fresh map each time, with its own random iteration order
+            let params: HashMap<String, u64> =
+                (0..32).map(|i| (format!("key{:02}", i), i)).collect();
+            let _: RpcResponse<serde_json::Value, serde_json::Value> = client
+                .request("queryLedgerState/tip", Some(params))
+                .await
+                .unwrap();
+        }
+
+        let requests = server.requests();
+        assert_eq!(requests[0].body, requests[1].body);
+        assert_eq!(requests[0].header("content-type"), Some("application/json"));
+        let keys: Vec<_> = requests[0].json()["params"]
+            .as_object()
             .unwrap()
-            .into()
+            .keys()
+            .cloned()
+            .collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(keys, sorted);
+        assert!(
+            requests[0]
+                .body
+                .starts_with(r#"{"id":null,"jsonrpc":"2.0","method""#)
+        );
     }
 }
@@ -1,7 +1,18 @@
+mod cache;
+mod chain_sync;
 pub mod codec;
+mod error;
 mod http;
 pub mod method;
+mod version;
 mod ws;
 
+#[cfg(test)]
+mod test_utils;
+
+pub use cache::*;
+pub use chain_sync::*;
+pub use error::*;
 pub use http::*;
+pub use version::*;
 pub use ws::*;
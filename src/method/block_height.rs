@@ -0,0 +1,88 @@
+use serde::{Deserialize, Deserializer};
+
+use crate::codec::*;
+use crate::define_ogmios_error;
+
+/// Height of the most recent block, or origin for a chain without blocks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BlockHeight {
+    Origin,
+    Height(u64),
+}
+
+impl BlockHeight {
+    /// Height of the block following this one, the first block being at height 0
+    pub fn next(self) -> BlockHeight {
+        match self {
+            BlockHeight::Origin => BlockHeight::Height(0),
+            BlockHeight::Height(height) => BlockHeight::Height(height + 1),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BlockHeight {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+
+        match &value {
+            serde_json::Value::String(s) if s == "origin" => Ok(BlockHeight::Origin),
+            serde_json::Value::Number(n) => n
+                .as_u64()
+                .map(BlockHeight::Height)
+                .ok_or_else(|| serde::de::Error::custom("expected a non-negative block height")),
+            _ => Err(serde::de::Error::custom(
+                "expected \"origin\" or a block height",
+            )),
+        }
+    }
+}
+
+define_ogmios_error! {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum BlockHeightError {
+        2001 => EraMismatch {
+            query_era: Era,
+            ledger_era: Era,
+        },
+        2002 => UnavailableInCurrentEra,
+        2003 => StateAcquiredExpired(String)
+        _ => Unknown { error: Value }
+    }
+}
+
+pub type BlockHeightResponse = RpcResponse<BlockHeight, BlockHeightError>;
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn deserialize_origin() {
+        let height: BlockHeight = serde_json::from_value(json!("origin")).unwrap();
+        assert_eq!(height, BlockHeight::Origin);
+    }
+
+    #[test]
+    fn deserialize_height() {
+        let json = r#"{"jsonrpc":"2.0","method":"queryNetwork/blockHeight","result":10325873}"#;
+        let response: BlockHeightResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(Result::from(response), Ok(BlockHeight::Height(10325873)));
+    }
+
+    #[test]
+    fn reject_other_strings() {
+        assert!(serde_json::from_value::<BlockHeight>(json!("genesis")).is_err());
+    }
+
+    #[test]
+    fn height_succession() {
+        assert_eq!(BlockHeight::Origin.next(), BlockHeight::Height(0));
+        assert_eq!(BlockHeight::Height(41).next(), BlockHeight::Height(42));
+        assert!(BlockHeight::Origin < BlockHeight::Height(0));
+    }
+}
@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+
+use crate::codec::{RpcResponse, Tx};
+use crate::define_ogmios_error;
+use crate::method::tip::Tip;
+
+/// A point on the chain, either a block (slot + header hash) or the origin
+pub type Point = Tip;
+
+// Find Intersection
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FindIntersectionParams {
+    pub points: Vec<Point>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FindIntersectionResult {
+    pub intersection: Point,
+    pub tip: Tip,
+}
+
+define_ogmios_error! {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum ChainSyncError {
+        1000 => IntersectionNotFound {
+            tip: Tip,
+        },
+        1001 => IntersectionInterleaved
+        _ => Unknown { error: Value }
+    }
+}
+
+pub type FindIntersectionResponse = RpcResponse<FindIntersectionResult, ChainSyncError>;
+
+// Next Block
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Block {
+    /// Block type: "praos", "bft" or "ebb"
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub era: String,
+    /// Header hash
+    pub id: String,
+    /// Header hash of the previous block, or "genesis"
+    pub ancestor: String,
+    pub height: u64,
+    /// Absent for epoch boundary blocks
+    #[serde(default)]
+    pub slot: Option<u64>,
+    #[serde(default)]
+    pub transactions: Vec<Tx>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "direction", rename_all = "camelCase")]
+pub enum NextBlockResult {
+    Forward { block: Box<Block>, tip: Tip },
+    Backward { point: Point, tip: Tip },
+}
+
+impl NextBlockResult {
+    pub fn tip(&self) -> &Tip {
+        match self {
+            NextBlockResult::Forward { tip, .. } | NextBlockResult::Backward { tip, .. } => tip,
+        }
+    }
+}
+
+pub type NextBlockResponse = RpcResponse<NextBlockResult, ChainSyncError>;
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn deserialize_roll_forward() {
+        let json = json!({
+            "direction": "forward",
+            "block": {
+                "type": "praos",
+                "era": "conway",
+                "id": "aa".repeat(32),
+                "ancestor": "bb".repeat(32),
+                "height": 42,
+                "slot": 1000,
+                "transactions": []
+            },
+            "tip": { "slot": 2000, "id": "cc".repeat(32) }
+        });
+        let result: NextBlockResult = serde_json::from_value(json).unwrap();
+        match result {
+            NextBlockResult::Forward { block, .. } => {
+                assert_eq!(block.height, 42);
+                assert_eq!(block.slot, Some(1000));
+            }
+            other => panic!("Expected a roll forward, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn deserialize_roll_backward() {
+        let json = json!({
+            "direction": "backward",
+            "point": "origin",
+            "tip": { "slot": 2000, "id": "cc".repeat(32) }
+        });
+        let result: NextBlockResult = serde_json::from_value(json).unwrap();
+        assert!(matches!(
+            result,
+            NextBlockResult::Backward {
+                point: Tip::Origin,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn deserialize_intersection_not_found() {
+        let json = json!({
+            "code": 1000,
+            "message": "No intersection found.",
+            "data": { "tip": "origin" }
+        });
+        let error: ChainSyncError = serde_json::from_value(json).unwrap();
+        assert!(matches!(
+            error,
+            ChainSyncError::IntersectionNotFound {
+                tip: Tip::Origin,
+                ..
+            }
+        ));
+    }
+}
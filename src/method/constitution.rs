@@ -0,0 +1,60 @@
+use serde::Deserialize;
+
+use crate::codec::*;
+use crate::define_ogmios_error;
+
+/// The constitution currently in force
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Constitution {
+    pub metadata: Anchor,
+    /// Script constraining protocol parameter updates and treasury withdrawals, if any
+    #[serde(default)]
+    pub guardrails: Option<Guardrails>,
+}
+
+define_ogmios_error! {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum ConstitutionError {
+        2001 => EraMismatch {
+            query_era: Era,
+            ledger_era: Era,
+        },
+        2002 => UnavailableInCurrentEra,
+        2003 => StateAcquiredExpired(String)
+        _ => Unknown { error: Value }
+    }
+}
+
+pub type ConstitutionResponse = RpcResponse<Constitution, ConstitutionError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_mainnet_constitution() {
+        let json = r#"{"jsonrpc":"2.0","method":"queryLedgerState/constitution","result":{"metadata":{"url":"ipfs://bafkreiazhhawe7sjwuthcfgl3mmv2swec7sukvclu3oli7qdyz4uhhuvmy","hash":"2a61e2f4b63442978140c77a70daab3961b22b12b63b13949a390c097214d1c5"},"guardrails":{"hash":"fa24fb305126805cf2164c161d852a0e7330cf988f1fe558cf7d4a64"}},"id":null}"#;
+        let response: ConstitutionResponse = serde_json::from_str(json).unwrap();
+        let constitution = Result::from(response).unwrap();
+
+        assert_eq!(
+            constitution.metadata.url,
+            "ipfs://bafkreiazhhawe7sjwuthcfgl3mmv2swec7sukvclu3oli7qdyz4uhhuvmy"
+        );
+        assert_eq!(
+            *constitution.metadata.hash,
+            "2a61e2f4b63442978140c77a70daab3961b22b12b63b13949a390c097214d1c5"
+        );
+        assert_eq!(
+            constitution.guardrails.unwrap().hash,
+            "fa24fb305126805cf2164c161d852a0e7330cf988f1fe558cf7d4a64"
+        );
+    }
+
+    #[test]
+    fn deserialize_without_guardrails() {
+        let json = r#"{"metadata":{"url":"https://example.com/constitution.txt","hash":"00000000000000000000000000000000000000000000000000000000000000ff"},"guardrails":null}"#;
+        let constitution: Constitution = serde_json::from_str(json).unwrap();
+        assert_eq!(constitution.guardrails, None);
+    }
+}
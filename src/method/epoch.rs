@@ -0,0 +1,46 @@
+use crate::codec::*;
+use crate::define_ogmios_error;
+
+define_ogmios_error! {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum EpochError {
+        2001 => EraMismatch {
+            query_era: Era,
+            ledger_era: Era,
+        },
+        2002 => UnavailableInCurrentEra,
+        2003 => StateAcquiredExpired(String)
+        _ => Unknown { error: Value }
+    }
+}
+
+pub type EpochResponse = RpcResponse<u64, EpochError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_epoch() {
+        let json = r#"{"jsonrpc":"2.0","method":"queryLedgerState/epoch","result":528}"#;
+        let response: EpochResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(Result::from(response), Ok(528));
+    }
+
+    #[test]
+    fn deserialize_unavailable_in_current_era() {
+        let json = r#"{
+            "jsonrpc": "2.0",
+            "method": "queryLedgerState/epoch",
+            "error": {
+                "code": 2002,
+                "message": "The requested query is not available in the current era."
+            }
+        }"#;
+        let response: EpochResponse = serde_json::from_str(json).unwrap();
+        assert!(matches!(
+            Result::from(response),
+            Err(EpochError::UnavailableInCurrentEra { .. })
+        ));
+    }
+}
@@ -0,0 +1,335 @@
+use std::fmt;
+
+use serde::Deserialize;
+
+use crate::codec::*;
+use crate::define_ogmios_error;
+use crate::method::network::NetworkQueryError;
+#[cfg(feature = "chrono")]
+use crate::method::network::StartTime;
+
+/// Bounds and slotting parameters of one era, in chronological order in the response
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct EraSummary {
+    pub start: EraBound,
+    /// Absent when the end of the era is not yet known
+    #[serde(default)]
+    pub end: Option<EraBound>,
+    pub parameters: EraParameters,
+}
+
+impl EraSummary {
+    /// Slots of the era, `None` while its end is not yet known
+    pub fn slots(&self) -> Option<SlotRange> {
+        Some(SlotRange::new(self.start.slot, self.end?.slot))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct EraBound {
+    /// Time elapsed since the system start
+    pub time: RelativeTime,
+    pub slot: SlotNo,
+    pub epoch: EpochNo,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+pub struct RelativeTime {
+    pub seconds: u64,
+}
+
+impl RelativeTime {
+    /// The time in milliseconds, `None` if it doesn't fit `u64`
+    pub fn checked_millis(self) -> Option<u64> {
+        self.seconds.checked_mul(1000)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EraParameters {
+    /// Number of slots in an epoch
+    pub epoch_length: u64,
+    pub slot_length: SlotLength,
+    /// Number of slots from the tip within which the era can't end, absent for eras that
+    /// won't end
+    #[serde(default)]
+    pub safe_zone: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct SlotLength {
+    pub milliseconds: u64,
+}
+
+define_ogmios_error! {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum EraSummariesError {
+        2001 => EraMismatch {
+            query_era: Era,
+            ledger_era: Era,
+        },
+        2002 => UnavailableInCurrentEra,
+        2003 => StateAcquiredExpired(String)
+        _ => Unknown { error: Value }
+    }
+}
+
+pub type EraSummariesResponse = RpcResponse<Vec<EraSummary>, EraSummariesError>;
+
+/// System start of the network, in milliseconds since the Unix epoch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SystemStart(pub i64);
+
+#[cfg(feature = "chrono")]
+impl From<chrono::DateTime<chrono::Utc>> for SystemStart {
+    fn from(time: chrono::DateTime<chrono::Utc>) -> Self {
+        SystemStart(time.timestamp_millis())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<StartTime> for SystemStart {
+    fn from(start: StartTime) -> Self {
+        start.0.into()
+    }
+}
+
+/// Error of [`OgmiosHttpClient::era_history`](crate::http::OgmiosHttpClient::era_history),
+/// from whichever of its two queries failed
+#[derive(Debug, Clone, PartialEq)]
+pub enum EraHistoryError {
+    StartTime(NetworkQueryError),
+    EraSummaries(EraSummariesError),
+}
+
+impl fmt::Display for EraHistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EraHistoryError::StartTime(error) => write!(f, "failed to query start time: {}", error),
+            EraHistoryError::EraSummaries(error) => {
+                write!(f, "failed to query era summaries: {}", error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EraHistoryError {}
+
+/// System start and era summaries, converting between slots and POSIX time in milliseconds as
+/// seen by Plutus scripts through `POSIXTime`
+#[derive(Debug, Clone, PartialEq)]
+pub struct EraHistory {
+    pub system_start: SystemStart,
+    pub eras: Vec<EraSummary>,
+}
+
+impl EraHistory {
+    pub fn new(system_start: SystemStart, eras: Vec<EraSummary>) -> Self {
+        Self { system_start, eras }
+    }
+
+    /// POSIX time in milliseconds at the start of `slot`, `None` past the end of the last
+    /// known era or when it doesn't fit `i64`
+    pub fn slot_to_posix_time(&self, slot: SlotNo) -> Option<i64> {
+        let era = self.eras.iter().find(|era| match era.slots() {
+            Some(slots) => slots.contains(slot),
+            // The last known era, whose end isn't known yet
+            None => era.start.slot <= slot,
+        })?;
+        let since_start = slot
+            .checked_since(era.start.slot)?
+            .checked_mul(era.parameters.slot_length.milliseconds)?;
+        let relative = era.start.time.checked_millis()?.checked_add(since_start)?;
+        self.system_start
+            .0
+            .checked_add(i64::try_from(relative).ok()?)
+    }
+
+    /// Slot containing the POSIX time `time` in milliseconds, `None` before the system start,
+    /// past the end of the last known era or when the era's bounds overflow
+    pub fn posix_time_to_slot(&self, time: i64) -> Option<SlotNo> {
+        let relative = u64::try_from(time.checked_sub(self.system_start.0)?).ok()?;
+        let era = self.eras.iter().find(|era| {
+            era.start
+                .time
+                .checked_millis()
+                .is_some_and(|start| start <= relative)
+                && era
+                    .end
+                    .is_none_or(|end| end.time.checked_millis().is_none_or(|end| relative < end))
+        })?;
+        let since_start = relative - era.start.time.checked_millis()?;
+        let slots = since_start.checked_div(era.parameters.slot_length.milliseconds)?;
+        Some(SlotNo(era.start.slot.0.checked_add(slots)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "chrono")]
+    use crate::method::network::StartTimeResponse;
+
+    const MAINNET_ERA_SUMMARIES: &str = r#"{
+        "jsonrpc": "2.0",
+        "method": "queryLedgerState/eraSummaries",
+        "result": [
+            {
+                "start": { "time": { "seconds": 0 }, "slot": 0, "epoch": 0 },
+                "end": { "time": { "seconds": 89856000 }, "slot": 4492800, "epoch": 208 },
+                "parameters": { "epochLength": 21600, "slotLength": { "milliseconds": 20000 }, "safeZone": 4320 }
+            },
+            {
+                "start": { "time": { "seconds": 89856000 }, "slot": 4492800, "epoch": 208 },
+                "end": { "time": { "seconds": 101952000 }, "slot": 16588800, "epoch": 236 },
+                "parameters": { "epochLength": 432000, "slotLength": { "milliseconds": 1000 }, "safeZone": 129600 }
+            },
+            {
+                "start": { "time": { "seconds": 101952000 }, "slot": 16588800, "epoch": 236 },
+                "end": { "time": { "seconds": 108432000 }, "slot": 23068800, "epoch": 251 },
+                "parameters": { "epochLength": 432000, "slotLength": { "milliseconds": 1000 }, "safeZone": 129600 }
+            },
+            {
+                "start": { "time": { "seconds": 108432000 }, "slot": 23068800, "epoch": 251 },
+                "end": { "time": { "seconds": 125280000 }, "slot": 39916800, "epoch": 290 },
+                "parameters": { "epochLength": 432000, "slotLength": { "milliseconds": 1000 }, "safeZone": 129600 }
+            },
+            {
+                "start": { "time": { "seconds": 125280000 }, "slot": 39916800, "epoch": 290 },
+                "end": { "time": { "seconds": 138240000 }, "slot": 52876800, "epoch": 320 },
+                "parameters": { "epochLength": 432000, "slotLength": { "milliseconds": 1000 }, "safeZone": 129600 }
+            },
+            {
+                "start": { "time": { "seconds": 138240000 }, "slot": 52876800, "epoch": 320 },
+                "end": { "time": { "seconds": 227232000 }, "slot": 141868800, "epoch": 526 },
+                "parameters": { "epochLength": 432000, "slotLength": { "milliseconds": 1000 }, "safeZone": 129600 }
+            },
+            {
+                "start": { "time": { "seconds": 227232000 }, "slot": 141868800, "epoch": 526 },
+                "end": null,
+                "parameters": { "epochLength": 432000, "slotLength": { "milliseconds": 1000 }, "safeZone": null }
+            }
+        ]
+    }"#;
+
+    /// 2017-09-23T21:44:51Z
+    const MAINNET_SYSTEM_START: SystemStart = SystemStart(1506203091000);
+
+    fn mainnet_era_summaries() -> Vec<EraSummary> {
+        let response: EraSummariesResponse = serde_json::from_str(MAINNET_ERA_SUMMARIES).unwrap();
+        Result::from(response).unwrap()
+    }
+
+    #[test]
+    fn deserialize_mainnet_era_summaries() {
+        let summaries = mainnet_era_summaries();
+
+        assert_eq!(summaries.len(), 7);
+        let byron = &summaries[0];
+        assert_eq!(byron.parameters.slot_length.milliseconds, 20000);
+        assert_eq!(byron.end.unwrap().slot, SlotNo(4492800));
+        assert_eq!(byron.slots().unwrap().len(), 4492800);
+        let conway = summaries.last().unwrap();
+        assert_eq!(conway.start.epoch, EpochNo(526));
+        assert_eq!(conway.slots(), None);
+        assert_eq!(conway.end, None);
+        assert_eq!(conway.parameters.safe_zone, None);
+        for pair in summaries.windows(2) {
+            assert_eq!(pair[0].end, Some(pair[1].start));
+        }
+    }
+
+    #[test]
+    fn slot_to_posix_time_on_mainnet() {
+        let history = EraHistory::new(MAINNET_SYSTEM_START, mainnet_era_summaries());
+        assert_eq!(history.slot_to_posix_time(SlotNo(0)), Some(1506203091000));
+        // Byron slots last 20 seconds
+        assert_eq!(history.slot_to_posix_time(SlotNo(100)), Some(1506205091000));
+        // First Shelley slot, 2020-07-29T21:44:51Z
+        assert_eq!(
+            history.slot_to_posix_time(SlotNo(4492800)),
+            Some(1596059091000)
+        );
+        // Past the start of the last era, which has no known end
+        assert_eq!(
+            history.slot_to_posix_time(SlotNo(141868800 + 1000)),
+            Some(1733436091000)
+        );
+    }
+
+    #[test]
+    fn posix_time_to_slot_on_mainnet() {
+        let history = EraHistory::new(MAINNET_SYSTEM_START, mainnet_era_summaries());
+        assert_eq!(history.posix_time_to_slot(1506203091000), Some(SlotNo(0)));
+        assert_eq!(history.posix_time_to_slot(1506203121000), Some(SlotNo(1)));
+        assert_eq!(
+            history.posix_time_to_slot(1596059091000),
+            Some(SlotNo(4492800))
+        );
+        // Times within a slot round down to its start
+        assert_eq!(
+            history.posix_time_to_slot(1733436091999),
+            Some(SlotNo(141869800))
+        );
+        assert_eq!(history.posix_time_to_slot(1506203090999), None);
+        for slot in [0, 4492799, 4492800, 39916800, 141868800, 150000000] {
+            let time = history.slot_to_posix_time(SlotNo(slot)).unwrap();
+            assert_eq!(history.posix_time_to_slot(time), Some(SlotNo(slot)));
+        }
+    }
+
+    #[test]
+    fn conversions_stop_at_the_last_known_era_end() {
+        let mut eras = mainnet_era_summaries();
+        eras.truncate(1);
+        let history = EraHistory::new(MAINNET_SYSTEM_START, eras);
+        assert_eq!(
+            history.slot_to_posix_time(SlotNo(4492799)),
+            Some(1596059071000)
+        );
+        assert_eq!(history.slot_to_posix_time(SlotNo(4492800)), None);
+        assert_eq!(history.posix_time_to_slot(1596059091000), None);
+    }
+
+    #[test]
+    fn conversions_overflow_to_none() {
+        let era: EraSummary = serde_json::from_value(serde_json::json!({
+            "start": { "time": { "seconds": 0 }, "slot": 0, "epoch": 0 },
+            "end": null,
+            "parameters": { "epochLength": 432000, "slotLength": { "milliseconds": 1000 }, "safeZone": null }
+        }))
+        .unwrap();
+        let history = EraHistory::new(MAINNET_SYSTEM_START, vec![era.clone()]);
+        assert_eq!(history.slot_to_posix_time(SlotNo(u64::MAX)), None);
+        assert_eq!(
+            history.slot_to_posix_time(SlotNo(i64::MAX as u64 / 1000)),
+            None
+        );
+        assert_eq!(history.posix_time_to_slot(i64::MIN), None);
+
+        let mut late = era.clone();
+        late.start.time.seconds = u64::MAX;
+        let history = EraHistory::new(SystemStart(0), vec![late]);
+        assert_eq!(history.slot_to_posix_time(SlotNo(0)), None);
+        assert_eq!(history.posix_time_to_slot(0), None);
+
+        let mut instant = era;
+        instant.parameters.slot_length.milliseconds = 0;
+        let history = EraHistory::new(SystemStart(0), vec![instant]);
+        assert_eq!(history.slot_to_posix_time(SlotNo(5)), Some(0));
+        assert_eq!(history.posix_time_to_slot(0), None);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn system_start_from_date_time() {
+        let start = chrono::DateTime::parse_from_rfc3339("2017-09-23T21:44:51Z").unwrap();
+        assert_eq!(SystemStart::from(start.to_utc()), MAINNET_SYSTEM_START);
+
+        let response = r#"{"jsonrpc":"2.0","method":"queryNetwork/startTime","result":"2017-09-23T21:44:51Z"}"#;
+        let response: StartTimeResponse = serde_json::from_str(response).unwrap();
+        let start = Result::from(response).unwrap();
+        assert_eq!(SystemStart::from(start), MAINNET_SYSTEM_START);
+    }
+}
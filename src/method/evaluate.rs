@@ -1,5 +1,10 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use num::{BigInt, BigRational};
 use serde::{Deserialize, Serialize};
 
+use super::utxo::{Utxo, UtxoError};
 use crate::codec::*;
 use crate::define_ogmios_error;
 
@@ -11,6 +16,9 @@ use crate::define_ogmios_error;
 #[serde(rename_all = "camelCase")]
 pub struct EvaluateRequestParams {
     pub transaction: TxCbor,
+    /// Outputs not yet on chain that the transaction spends or references
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub additional_utxo: Vec<Utxo>,
 }
 
 // -----------
@@ -24,7 +32,7 @@ pub struct Evaluation {
 }
 
 define_ogmios_error! {
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq)]
     pub enum EvaluationError {
         3000 => IncompatibleEra {
             incompatible_era: Era,
@@ -42,9 +50,7 @@ define_ogmios_error! {
         3004 => CannotCreateEvaluationContext {
             reason: String,
         },
-        3010 => ScriptExecution {
-            errors: Vec<ScriptError>,
-        },
+        3010 => ScriptExecution[errors: Vec<ScriptError>],
         -32602 => Deserialization {
             byron: String,
             shelley: String,
@@ -58,8 +64,84 @@ define_ogmios_error! {
     }
 }
 
+impl EvaluationError {
+    /// For -32602, the decoder failure of `era`, the transaction's declared era, if known.
+    /// Otherwise the most specific of the per-era failures.
+    pub fn deserialization_failure(&self, era: Option<&Era>) -> Option<DeserializationFailure<'_>> {
+        deserialization_failure!(self, era)
+    }
+}
+
 pub type EvaluateResponse = RpcResponse<Vec<Evaluation>, EvaluationError>;
 
+/// Error of [`OgmiosHttpClient::evaluate_auto_context`], from looking the inputs up on chain
+/// or from the evaluation itself
+///
+/// [`OgmiosHttpClient::evaluate_auto_context`]: crate::OgmiosHttpClient::evaluate_auto_context
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvaluateAutoContextError {
+    Lookup(UtxoError),
+    Evaluation(EvaluationError),
+}
+
+impl fmt::Display for EvaluateAutoContextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvaluateAutoContextError::Lookup(error) => {
+                write!(f, "failed to look up transaction inputs: {}", error)
+            }
+            EvaluateAutoContextError::Evaluation(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for EvaluateAutoContextError {}
+
+/// Execution budgets to set on a transaction's redeemers, keyed by redeemer
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EvaluationPlan(HashMap<RedeemerPointer, ExecutionUnits>);
+
+impl From<Vec<Evaluation>> for EvaluationPlan {
+    fn from(evaluations: Vec<Evaluation>) -> Self {
+        Self(
+            evaluations
+                .into_iter()
+                .map(|evaluation| (evaluation.validator, evaluation.budget))
+                .collect(),
+        )
+    }
+}
+
+impl EvaluationPlan {
+    pub fn for_redeemer(&self, purpose: RedeemerPurpose, index: u64) -> Option<&ExecutionUnits> {
+        self.0.get(&RedeemerPointer { purpose, index })
+    }
+
+    /// Pads every budget by `percent`, rounding up, to leave room for small differences
+    /// between the evaluated and the final transaction. Budgets are arbitrary-precision, so
+    /// no margin overflows.
+    pub fn apply_margin(self, percent: u64) -> Self {
+        let factor = BigRational::new(BigInt::from(percent) + 100, 100.into());
+        let pad = |units: Ratio| Ratio((units.0 * &factor).ceil());
+        Self(
+            self.0
+                .into_iter()
+                .map(|(pointer, budget)| {
+                    let budget = ExecutionUnits {
+                        memory: pad(budget.memory),
+                        cpu: pad(budget.cpu),
+                    };
+                    (pointer, budget)
+                })
+                .collect(),
+        )
+    }
+
+    pub fn into_map(self) -> HashMap<RedeemerPointer, ExecutionUnits> {
+        self.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr as _;
@@ -113,4 +195,93 @@ mod tests {
             },
         }]
     );
+
+    fn units(memory: i64, cpu: i64) -> ExecutionUnits {
+        ExecutionUnits {
+            memory: Ratio(BigRational::from_integer(memory.into())),
+            cpu: Ratio(BigRational::from_integer(cpu.into())),
+        }
+    }
+
+    fn plan() -> EvaluationPlan {
+        EvaluationPlan::from(vec![
+            Evaluation {
+                validator: RedeemerPointer {
+                    purpose: RedeemerPurpose::Spend,
+                    index: 0,
+                },
+                budget: units(6125, 1583505),
+            },
+            Evaluation {
+                validator: RedeemerPointer {
+                    purpose: RedeemerPurpose::Mint,
+                    index: 0,
+                },
+                budget: units(1000, 2000),
+            },
+        ])
+    }
+
+    #[test]
+    fn evaluation_plan_lookup() {
+        let plan = plan();
+        assert_eq!(
+            plan.for_redeemer(RedeemerPurpose::Spend, 0),
+            Some(&units(6125, 1583505))
+        );
+        assert_eq!(
+            plan.for_redeemer(RedeemerPurpose::Mint, 0),
+            Some(&units(1000, 2000))
+        );
+        assert_eq!(plan.for_redeemer(RedeemerPurpose::Spend, 1), None);
+    }
+
+    #[test]
+    fn evaluation_plan_margin_rounds_up() {
+        let plan = plan().apply_margin(10);
+        assert_eq!(
+            plan.for_redeemer(RedeemerPurpose::Spend, 0),
+            Some(&units(6738, 1741856))
+        );
+        assert_eq!(
+            plan.for_redeemer(RedeemerPurpose::Mint, 0),
+            Some(&units(1100, 2200))
+        );
+        assert_eq!(plan.into_map().len(), 2);
+    }
+
+    #[test]
+    fn evaluation_plan_margin_beyond_u64() {
+        let plan = plan().apply_margin(u64::MAX);
+        let budget = plan.for_redeemer(RedeemerPurpose::Mint, 0).unwrap();
+        // 1000 * (100 + u64::MAX) / 100
+        let expected = (BigInt::from(u64::MAX) + 100) * 10;
+        assert_eq!(budget.memory.0, BigRational::from_integer(expected));
+    }
+
+    #[test]
+    fn deserialization_failure_of_hinted_era() {
+        let mut data = serde_json::Map::new();
+        for era in ["byron", "shelley", "allegra", "mary", "alonzo", "babbage"] {
+            data.insert(era.to_string(), json!(format!("{} decoder failed", era)));
+        }
+        data.insert(
+            "conway".to_string(),
+            json!("DeserialiseFailure 7 \"expected map\""),
+        );
+        let error: EvaluationError = serde_json::from_value(json!({
+            "code": -32602,
+            "message": "Invalid transaction",
+            "data": data
+        }))
+        .unwrap();
+
+        let failure = error.deserialization_failure(Some(&Era::Babbage)).unwrap();
+        assert_eq!(
+            failure.to_string(),
+            "Babbage decoder: babbage decoder failed"
+        );
+        let failure = error.deserialization_failure(None).unwrap();
+        assert_eq!(failure.era, Era::Conway);
+    }
 }
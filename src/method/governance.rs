@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+use crate::codec::{
+    AdaBalance, Anchor, Era, GovernanceAction, GovernanceProposalReference, GovernanceVote,
+    RewardAccount, RpcResponse,
+};
+use crate::define_ogmios_error;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GovernanceProposalsParams {
+    /// Restricts the query to these proposals, all active proposals are returned if empty
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub proposals: Vec<GovernanceProposalReference>,
+}
+
+/// An active governance proposal
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GovernanceProposalState {
+    pub proposal: GovernanceProposalReference,
+    pub deposit: AdaBalance,
+    pub return_account: RewardAccount,
+    pub metadata: Anchor,
+    pub action: GovernanceAction,
+    /// Epoch the proposal was submitted in
+    pub since: EpochBound,
+    /// Last epoch the proposal can be ratified in
+    pub until: EpochBound,
+    #[serde(default)]
+    pub votes: Vec<GovernanceVote>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct EpochBound {
+    pub epoch: u64,
+}
+
+define_ogmios_error! {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum GovernanceProposalsError {
+        2001 => EraMismatch {
+            query_era: Era,
+            ledger_era: Era,
+        },
+        2002 => UnavailableInCurrentEra,
+        2003 => StateAcquiredExpired(String)
+        _ => Unknown { error: Value }
+    }
+}
+
+pub type GovernanceProposalsResponse =
+    RpcResponse<Vec<GovernanceProposalState>, GovernanceProposalsError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_governance_proposals() {
+        let json = r#"{
+            "jsonrpc": "2.0",
+            "method": "queryLedgerState/governanceProposals",
+            "result": [{
+                "proposal": {
+                    "transaction": { "id": "3f1c4b3e0a5c2f0d9d1c2b4e5f6a7b8c9d0e1f2a3b4c5d6e7f8091a2b3c4d5e6" },
+                    "index": 0
+                },
+                "deposit": { "ada": { "lovelace": 100000000000 } },
+                "returnAccount": "stake1u9x9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m",
+                "metadata": {
+                    "url": "ipfs://bafkreidemo",
+                    "hash": "ab12ab12ab12ab12ab12ab12ab12ab12ab12ab12ab12ab12ab12ab12ab12ab12"
+                },
+                "action": { "type": "information" },
+                "since": { "epoch": 507 },
+                "until": { "epoch": 513 },
+                "votes": []
+            }]
+        }"#;
+        let response: GovernanceProposalsResponse = serde_json::from_str(json).unwrap();
+        let proposals = Result::from(response).unwrap();
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(proposals[0].action, GovernanceAction::Info);
+        assert_eq!(proposals[0].until.epoch, 513);
+    }
+}
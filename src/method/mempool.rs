@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize, Serializer};
 
-use crate::codec::{RpcResponse, RpcSuccess, Tx, TxPointer};
+use crate::codec::{NumberOfBytes, RpcResponse, RpcSuccess, Tx, TxPointer};
 use crate::define_ogmios_error;
 
 // Acquire Mempool
@@ -33,10 +33,24 @@ impl Serialize for NextTransaction {
     }
 }
 
+/// Options for the `nextTransaction` request
+#[derive(Debug, Clone, Default)]
+pub struct NextTransactionOptions {
+    /// Request the full transaction rather than only its id, which includes the raw `cbor`
+    /// (as long as Ogmios runs with `--include-transaction-cbor`)
+    pub include_cbor: bool,
+}
+
+impl NextTransactionOptions {
+    pub fn params(&self) -> Option<NextTransaction> {
+        self.include_cbor.then_some(NextTransaction {})
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(untagged)]
 pub enum MempoolTransaction {
-    Tx(Tx),
+    Tx(Box<Tx>),
     TxPointer(TxPointer),
 }
 
@@ -46,7 +60,7 @@ pub struct NextTransactionResult {
 }
 
 define_ogmios_error! {
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq)]
     pub enum MempoolError {
         4000 => MustAcquireMempoolFirst,
         _ => Unknown { error: Value }
@@ -54,3 +68,63 @@ define_ogmios_error! {
 }
 
 pub type NextTransactionResponse = RpcResponse<NextTransactionResult, MempoolError>;
+
+// Has Transaction
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HasTransactionParams {
+    pub id: String,
+}
+
+/// Whether the transaction is in the acquired snapshot
+pub type HasTransactionResult = bool;
+
+pub type HasTransactionResponse = RpcResponse<HasTransactionResult, MempoolError>;
+
+// Size Of Mempool
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Count {
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolSize {
+    pub max_capacity: NumberOfBytes,
+    /// Size of the transactions in the acquired snapshot
+    pub current_size: NumberOfBytes,
+    /// Number of transactions in the acquired snapshot
+    pub transactions: Count,
+}
+
+pub type SizeOfMempoolResponse = RpcResponse<MempoolSize, MempoolError>;
+
+// Release Mempool
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseMempoolResult {
+    /// Always set to "mempool"
+    pub released: String,
+}
+
+pub type ReleaseMempoolResponse = RpcResponse<ReleaseMempoolResult, MempoolError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_size_of_mempool() {
+        let json = r#"{"jsonrpc":"2.0","method":"sizeOfMempool","result":{"maxCapacity":{"bytes":180224},"currentSize":{"bytes":3582},"transactions":{"count":2}},"id":null}"#;
+        let response: SizeOfMempoolResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            Result::from(response),
+            Ok(MempoolSize {
+                max_capacity: NumberOfBytes { bytes: 180224 },
+                current_size: NumberOfBytes { bytes: 3582 },
+                transactions: Count { count: 2 },
+            })
+        );
+    }
+}
@@ -1,7 +1,92 @@
+pub mod block_height;
+pub mod chain_sync;
+pub mod constitution;
+pub mod epoch;
+pub mod era_summaries;
 pub mod evaluate;
+pub mod governance;
 pub mod mempool;
+pub mod network;
 pub mod pparams;
 pub mod rewards;
+pub mod stake_distribution;
+pub mod stake_pools;
 pub mod submit;
 pub mod tip;
 pub mod utxo;
+
+/// Helpers shared by the error types of `queryLedgerState/*` methods
+pub trait StateQueryErrorExt {
+    /// Whether the acquired ledger state has expired (2003), in which case the state must be
+    /// re-acquired before retrying the query
+    fn needs_reacquire(&self) -> bool;
+}
+
+macro_rules! impl_state_query_error_ext {
+    ($($error:ty),+ $(,)?) => {
+        $(
+            impl StateQueryErrorExt for $error {
+                fn needs_reacquire(&self) -> bool {
+                    matches!(self, Self::StateAcquiredExpired { .. })
+                }
+            }
+        )+
+    };
+}
+
+impl<E: StateQueryErrorExt> StateQueryErrorExt for crate::OgmiosError<E> {
+    fn needs_reacquire(&self) -> bool {
+        self.rpc().is_some_and(E::needs_reacquire)
+    }
+}
+
+impl_state_query_error_ext!(
+    block_height::BlockHeightError,
+    constitution::ConstitutionError,
+    epoch::EpochError,
+    era_summaries::EraSummariesError,
+    governance::GovernanceProposalsError,
+    network::NetworkQueryError,
+    pparams::ProtocolParamsError,
+    rewards::DelegationsAndRewardsError,
+    rewards::RewardAccountSummariesError,
+    rewards::RewardsProvenanceError,
+    stake_distribution::StakeDistributionError,
+    stake_pools::StakePoolsError,
+    tip::TipError,
+    utxo::ProtocolParamsError,
+    utxo::UtxoError,
+);
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn state_acquired_expired_needs_reacquire() {
+        let json = json!({
+            "code": 2003,
+            "message": "The acquired state is no longer available",
+            "data": "expired"
+        });
+        let tip: tip::TipError = serde_json::from_value(json.clone()).unwrap();
+        assert!(tip.needs_reacquire());
+        let pparams: pparams::ProtocolParamsError = serde_json::from_value(json).unwrap();
+        assert!(pparams.needs_reacquire());
+    }
+
+    #[test]
+    fn era_mismatch_does_not_need_reacquire() {
+        let json = json!({
+            "code": 2001,
+            "message": "Era mismatch",
+            "data": { "queryEra": "babbage", "ledgerEra": "conway" }
+        });
+        let tip: tip::TipError = serde_json::from_value(json.clone()).unwrap();
+        assert!(!tip.needs_reacquire());
+        let utxo: utxo::UtxoError = serde_json::from_value(json).unwrap();
+        assert!(!utxo.needs_reacquire());
+    }
+}
@@ -0,0 +1,246 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::codec::*;
+use crate::define_ogmios_error;
+use crate::method::constitution::Constitution;
+use crate::method::era_summaries::SlotLength;
+use crate::method::pparams::{CostModels, MinFeeReferenceScripts};
+
+define_ogmios_error! {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum NetworkQueryError {
+        2001 => EraMismatch {
+            query_era: Era,
+            ledger_era: Era,
+        },
+        2002 => UnavailableInCurrentEra,
+        2003 => StateAcquiredExpired(String)
+        _ => Unknown { error: Value }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GenesisConfigurationParams {
+    pub era: Era,
+}
+
+/// Genesis configuration of an era. Ogmios only serves the eras introducing new genesis
+/// parameters, the shape of which differs between eras.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "era", rename_all = "camelCase")]
+pub enum GenesisConfig {
+    Shelley(ShelleyGenesis),
+    Alonzo(AlonzoGenesis),
+    Conway(ConwayGenesis),
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShelleyGenesis {
+    /// RFC 3339 timestamp of the network's system start
+    pub start_time: String,
+    pub network_magic: u32,
+    /// `mainnet` or `testnet`
+    pub network: String,
+    /// Fraction of slots expected to hold a block
+    pub active_slots_coefficient: Ratio,
+    /// Maximum number of blocks a rollback can span, known as `k`
+    pub security_parameter: u64,
+    pub epoch_length: u64,
+    pub slot_length: SlotLength,
+    pub slots_per_kes_period: u64,
+    pub max_kes_evolutions: u64,
+    pub update_quorum: u64,
+    pub max_lovelace_supply: u64,
+    /// Protocol parameters at the start of Shelley, in Ogmios' shape
+    pub initial_parameters: Value,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlonzoGenesis {
+    pub updatable_parameters: AlonzoGenesisParameters,
+}
+
+/// Protocol parameters introduced by Alonzo, as set at the start of the era
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlonzoGenesisParameters {
+    pub min_utxo_deposit_coefficient: u64,
+    pub collateral_percentage: u32,
+    pub max_collateral_inputs: u32,
+    pub max_value_size: NumberOfBytes,
+    pub plutus_cost_models: CostModels,
+    pub script_execution_prices: ExecutionUnits,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConwayGenesis {
+    pub constitution: Constitution,
+    /// Initial committee members and quorum, in Ogmios' shape
+    pub constitutional_committee: Value,
+    pub updatable_parameters: ConwayGenesisParameters,
+}
+
+/// Governance parameters introduced by Conway, as set at the start of the era
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConwayGenesisParameters {
+    pub stake_pool_voting_thresholds: Value,
+    pub delegate_representative_voting_thresholds: Value,
+    pub constitutional_committee_min_size: u64,
+    /// In epochs
+    pub constitutional_committee_max_term_length: u64,
+    /// In epochs
+    pub governance_action_lifetime: u64,
+    pub governance_action_deposit: AdaBalance,
+    pub delegate_representative_deposit: AdaBalance,
+    /// In epochs
+    pub delegate_representative_max_idle_time: u64,
+    #[serde(default)]
+    pub min_fee_reference_scripts: Option<MinFeeReferenceScripts>,
+}
+
+pub type GenesisConfigResponse = RpcResponse<GenesisConfig, NetworkQueryError>;
+
+/// System start of the network, decoded from an RFC 3339 timestamp
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StartTime(pub chrono::DateTime<chrono::Utc>);
+
+#[cfg(feature = "chrono")]
+impl<'de> serde::Deserialize<'de> for StartTime {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        chrono::DateTime::parse_from_rfc3339(&s)
+            .map(|time| StartTime(time.to_utc()))
+            .map_err(|e| serde::de::Error::custom(format!("invalid start time '{}': {}", s, e)))
+    }
+}
+
+#[cfg(feature = "chrono")]
+pub type StartTimeResponse = RpcResponse<StartTime, NetworkQueryError>;
+
+#[cfg(all(test, feature = "chrono"))]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn deserialize_start_time() {
+        let json = r#"{"jsonrpc":"2.0","method":"queryNetwork/startTime","result":"2017-09-23T21:44:51Z"}"#;
+        let response: StartTimeResponse = serde_json::from_str(json).unwrap();
+        let expected = chrono::Utc
+            .with_ymd_and_hms(2017, 9, 23, 21, 44, 51)
+            .unwrap();
+        assert_eq!(Result::from(response), Ok(StartTime(expected)));
+    }
+
+    #[test]
+    fn reject_malformed_start_time() {
+        assert!(serde_json::from_str::<StartTime>(r#""2017-09-23 21:44""#).is_err());
+    }
+}
+
+#[cfg(test)]
+mod genesis_tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_shelley_genesis() {
+        let json = r#"{"jsonrpc":"2.0","method":"queryNetwork/genesisConfiguration","result":{
+            "era": "shelley",
+            "startTime": "2017-09-23T21:44:51Z",
+            "networkMagic": 764824073,
+            "network": "mainnet",
+            "activeSlotsCoefficient": "1/20",
+            "securityParameter": 2160,
+            "epochLength": 432000,
+            "slotsPerKesPeriod": 129600,
+            "maxKesEvolutions": 62,
+            "slotLength": { "milliseconds": 1000 },
+            "updateQuorum": 5,
+            "maxLovelaceSupply": 45000000000000000,
+            "initialParameters": { "minFeeCoefficient": 44, "minFeeConstant": { "ada": { "lovelace": 155381 } } },
+            "initialDelegates": [],
+            "initialFunds": {},
+            "initialStakePools": { "stakePools": {}, "delegators": {} }
+        },"id":null}"#;
+        let response: GenesisConfigResponse = serde_json::from_str(json).unwrap();
+        let GenesisConfig::Shelley(genesis) = Result::from(response).unwrap() else {
+            panic!("Expected the Shelley genesis");
+        };
+        assert_eq!(genesis.network_magic, 764824073);
+        assert_eq!(genesis.network, "mainnet");
+        assert_eq!(
+            genesis.active_slots_coefficient.0,
+            num_rational::BigRational::new(1.into(), 20.into())
+        );
+        assert_eq!(genesis.security_parameter, 2160);
+        assert_eq!(genesis.epoch_length, 432000);
+        assert_eq!(genesis.slot_length.milliseconds, 1000);
+        assert_eq!(genesis.max_lovelace_supply, 45000000000000000);
+        assert_eq!(genesis.initial_parameters["minFeeCoefficient"], 44);
+    }
+
+    #[test]
+    fn deserialize_conway_genesis() {
+        let json = r#"{"jsonrpc":"2.0","method":"queryNetwork/genesisConfiguration","result":{
+            "era": "conway",
+            "constitution": {
+                "metadata": {
+                    "url": "ipfs://bafkreifnwj6zpu3ixa4siz2lndqybyc5wnnt3jkwyutci4e2tmbnj3xrdm",
+                    "hash": "ca41a91f399259bcefe57f9858e91f6d00e1a38d6d9c63d4052914ea7bd70cb2"
+                },
+                "guardrails": { "hash": "fa24fb305126805cf2164c161d852a0e7330cf988f1fe558cf7d4a64" }
+            },
+            "constitutionalCommittee": { "members": [], "quorum": "2/3" },
+            "updatableParameters": {
+                "stakePoolVotingThresholds": { "noConfidence": "51/100", "hardForkInitiation": "51/100" },
+                "delegateRepresentativeVotingThresholds": { "noConfidence": "67/100", "constitution": "3/4" },
+                "constitutionalCommitteeMinSize": 7,
+                "constitutionalCommitteeMaxTermLength": 146,
+                "governanceActionLifetime": 6,
+                "governanceActionDeposit": { "ada": { "lovelace": 100000000000 } },
+                "delegateRepresentativeDeposit": { "ada": { "lovelace": 500000000 } },
+                "delegateRepresentativeMaxIdleTime": 20,
+                "minFeeReferenceScripts": { "range": 25600, "base": 15, "multiplier": 1.2 }
+            }
+        },"id":null}"#;
+        let response: GenesisConfigResponse = serde_json::from_str(json).unwrap();
+        let GenesisConfig::Conway(genesis) = Result::from(response).unwrap() else {
+            panic!("Expected the Conway genesis");
+        };
+        assert_eq!(
+            genesis.constitution.guardrails.unwrap().hash,
+            "fa24fb305126805cf2164c161d852a0e7330cf988f1fe558cf7d4a64"
+        );
+        let parameters = genesis.updatable_parameters;
+        assert_eq!(parameters.constitutional_committee_min_size, 7);
+        assert_eq!(parameters.governance_action_lifetime, 6);
+        assert_eq!(parameters.governance_action_deposit.lovelace, 100000000000);
+        assert_eq!(
+            parameters.delegate_representative_deposit.lovelace,
+            500000000
+        );
+        assert_eq!(
+            parameters.min_fee_reference_scripts.unwrap().multiplier,
+            1.2
+        );
+    }
+
+    #[test]
+    fn serialize_params() {
+        let params = GenesisConfigurationParams { era: Era::Shelley };
+        assert_eq!(
+            serde_json::to_value(&params).unwrap(),
+            serde_json::json!({ "era": "shelley" })
+        );
+    }
+}
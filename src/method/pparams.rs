@@ -1,6 +1,9 @@
+use std::fmt;
+
+use num::{BigInt, BigRational, ToPrimitive as _, Zero as _};
 use serde::{Deserialize, Serialize};
 
-use crate::codec::{AdaBalance, Era, ExecutionUnits};
+use crate::codec::{AdaBalance, Era, ExecutionUnits, Language, NumberOfBytes, Ratio};
 use crate::define_ogmios_error;
 
 #[derive(Debug, Clone, Deserialize)]
@@ -12,6 +15,17 @@ pub struct ProtocolParams {
     pub min_fee_constant: AdaBalance,
     /// Deposit required for stake credential registration
     pub stake_credential_deposit: AdaBalance,
+    /// Deposit required for stake pool registration
+    pub stake_pool_deposit: AdaBalance,
+    /// Target number of stake pools, beyond which pool rewards saturate
+    pub desired_number_of_stake_pools: u32,
+    /// Influence of a pool's pledge on its rewards
+    pub stake_pool_pledge_influence: Ratio,
+    pub max_transaction_size: NumberOfBytes,
+    pub max_block_body_size: NumberOfBytes,
+    pub max_block_header_size: NumberOfBytes,
+    /// Maximum size of the serialized value of a transaction output
+    pub max_value_size: NumberOfBytes,
     pub plutus_cost_models: CostModels,
     /// Multiplied by the size of the reference script
     /// This number gets multiplied every `range` bytes by the `multiplier`
@@ -25,7 +39,10 @@ pub struct ProtocolParams {
     /// 2KB: 10 * 1024 + (10 * 1.2) * 1024 = 22528
     /// 2.5KB: 10 * 1024 + (10 * 1.2) * 1024 + (10 * 1.2^2) * 512 = 29900.8
     /// ...
-    pub min_fee_reference_scripts: MinFeeReferenceScripts,
+    ///
+    /// Introduced in Conway
+    #[serde(default)]
+    pub min_fee_reference_scripts: Option<MinFeeReferenceScripts>,
     /// Base UTxO deposit (lovelace amount), added to the min_utxo_deposit_coefficient * UTxO size
     pub min_utxo_deposit_constant: AdaBalance,
     /// Multiplied by the size of the UTxO (not the whole transaction) to get the minimum UTxO
@@ -33,12 +50,113 @@ pub struct ProtocolParams {
     pub min_utxo_deposit_coefficient: u64,
     /// Price per unit of CPU and memory
     pub script_execution_prices: ExecutionUnits,
+    /// Maximum budget of all scripts executed by one transaction, see
+    /// [`ExecutionUnits::overage`]
+    pub max_execution_units_per_transaction: ExecutionUnits,
+    /// Maximum budget of all scripts executed in one block
+    pub max_execution_units_per_block: ExecutionUnits,
 
     /// Percentage of the transaction fee that must be provided as collateral
     pub collateral_percentage: f64,
 
     /// Maximum number of collateral inputs
     pub max_collateral_inputs: u32,
+
+    /// Deposit required to submit a governance action, introduced in Conway
+    #[serde(default)]
+    pub governance_action_deposit: Option<AdaBalance>,
+    /// Deposit required to register as a DRep, introduced in Conway
+    #[serde(default)]
+    pub delegate_representative_deposit: Option<AdaBalance>,
+}
+
+impl ProtocolParams {
+    /// Bytes added to an output's serialized size when computing its minimum deposit, standing
+    /// for the ledger's bookkeeping of the UTxO entry (Babbage onwards)
+    pub const UTXO_ENTRY_OVERHEAD_BYTES: u64 = 160;
+
+    /// Minimum lovelace an output serializing to `output_size_bytes` must hold:
+    /// `min_utxo_deposit_constant + min_utxo_deposit_coefficient * (output_size_bytes +
+    /// UTXO_ENTRY_OVERHEAD_BYTES)`. `None` when it exceeds `u64`.
+    pub fn min_utxo_value(&self, output_size_bytes: u64) -> Option<u64> {
+        output_size_bytes
+            .checked_add(Self::UTXO_ENTRY_OVERHEAD_BYTES)?
+            .checked_mul(self.min_utxo_deposit_coefficient)?
+            .checked_add(self.min_utxo_deposit_constant.lovelace)
+    }
+
+    /// Reference script fee tiers, or an error when the node predates Conway
+    pub fn min_fee_reference_scripts_required(
+        &self,
+    ) -> Result<&MinFeeReferenceScripts, MissingProtocolParam> {
+        require(&self.min_fee_reference_scripts, "minFeeReferenceScripts")
+    }
+
+    /// Governance action deposit, or an error when the node predates Conway
+    pub fn governance_action_deposit_required(&self) -> Result<&AdaBalance, MissingProtocolParam> {
+        require(&self.governance_action_deposit, "governanceActionDeposit")
+    }
+
+    /// DRep registration deposit, or an error when the node predates Conway
+    pub fn delegate_representative_deposit_required(
+        &self,
+    ) -> Result<&AdaBalance, MissingProtocolParam> {
+        require(
+            &self.delegate_representative_deposit,
+            "delegateRepresentativeDeposit",
+        )
+    }
+
+    /// Lovelace fee due for `total_ref_script_bytes` of reference scripts spent or referenced
+    /// by a transaction, see [`MinFeeReferenceScripts::fee_for`]. Zero before Conway.
+    pub fn reference_script_fee(&self, total_ref_script_bytes: u64) -> Option<u64> {
+        self.min_fee_reference_scripts
+            .as_ref()
+            .map_or(Some(0), |tiers| tiers.fee_for(total_ref_script_bytes))
+    }
+
+    /// Cost models of `langs`, in order, e.g. to resolve a `MissingCostModels` (3115) error.
+    /// `None` for languages without a cost model in this era.
+    pub fn cost_models_for(&self, langs: &[Language]) -> Vec<(Language, Option<&CostModel>)> {
+        langs
+            .iter()
+            .map(|lang| (lang.clone(), self.plutus_cost_models.get(lang)))
+            .collect()
+    }
+
+    /// Lovelace cost of executing scripts with the given budget: memory price × memory units +
+    /// cpu price × cpu units, rounded up. `None` when it exceeds `u64`.
+    pub fn script_execution_price_for(&self, units: &ExecutionUnits) -> Option<u64> {
+        let prices = &self.script_execution_prices;
+        let price = &prices.memory.0 * &units.memory.0 + &prices.cpu.0 * &units.cpu.0;
+        price.ceil().to_integer().to_u64()
+    }
+}
+
+/// A protocol parameter required by the caller is absent, typically because the node is in an
+/// era that doesn't define it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingProtocolParam {
+    pub field: &'static str,
+}
+
+impl fmt::Display for MissingProtocolParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "protocol parameter '{}' is not available from this node",
+            self.field
+        )
+    }
+}
+
+impl std::error::Error for MissingProtocolParam {}
+
+fn require<'a, T>(
+    value: &'a Option<T>,
+    field: &'static str,
+) -> Result<&'a T, MissingProtocolParam> {
+    value.as_ref().ok_or(MissingProtocolParam { field })
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,13 +170,23 @@ pub struct CostModels {
     pub plutus_v3: Option<CostModel>,
 }
 
+impl CostModels {
+    pub fn get(&self, lang: &Language) -> Option<&CostModel> {
+        match lang {
+            Language::PlutusV1 => self.plutus_v1.as_ref(),
+            Language::PlutusV2 => self.plutus_v2.as_ref(),
+            Language::PlutusV3 => self.plutus_v3.as_ref(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct CostModel(pub Vec<i64>);
 
-impl Into<Vec<i64>> for CostModel {
-    fn into(self) -> Vec<i64> {
-        self.0
+impl From<CostModel> for Vec<i64> {
+    fn from(cost_model: CostModel) -> Self {
+        cost_model.0
     }
 }
 
@@ -84,8 +212,52 @@ pub struct MinFeeReferenceScripts {
     pub multiplier: f64,
 }
 
+impl MinFeeReferenceScripts {
+    /// Most reference script bytes a transaction may spend or reference (200 KiB), a ledger
+    /// constant rather than a protocol parameter
+    pub const MAX_BYTES_PER_TX: u64 = 200 * 1024;
+
+    /// Lovelace cost of `bytes` of reference scripts, rounded down as the ledger does.
+    /// `base` and `multiplier` are taken at their decimal value (1.2 as 6/5), so that whole
+    /// amounts aren't rounded down by floating point error.
+    ///
+    /// `None` when `bytes` exceeds [`Self::MAX_BYTES_PER_TX`], when `base` or `multiplier`
+    /// isn't finite, or when the fee doesn't fit `u64`.
+    pub fn fee_for(&self, bytes: u64) -> Option<u64> {
+        if bytes > Self::MAX_BYTES_PER_TX {
+            return None;
+        }
+        let range = BigRational::from_integer(self.range.max(1).into());
+        let multiplier = decimal_ratio(self.multiplier)?;
+        let mut price = decimal_ratio(self.base)?;
+        let mut remaining = BigRational::from_integer(bytes.into());
+        let mut fee = BigRational::zero();
+        while remaining >= range {
+            fee += &range * &price;
+            price *= &multiplier;
+            remaining -= &range;
+        }
+        fee += remaining * price;
+        fee.floor().to_integer().to_u64()
+    }
+}
+
+/// The exact value of `value`'s shortest decimal representation, `None` for infinities and NaN
+fn decimal_ratio(value: f64) -> Option<BigRational> {
+    if !value.is_finite() {
+        return None;
+    }
+    let decimal = value.to_string();
+    let (int, frac) = decimal.split_once('.').unwrap_or((&decimal, ""));
+    let numerator: BigInt = format!("{}{}", int, frac).parse().ok()?;
+    Some(BigRational::new(
+        numerator,
+        BigInt::from(10).pow(frac.len() as u32),
+    ))
+}
+
 define_ogmios_error! {
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq)]
     pub enum ProtocolParamsError {
         2001 => EraMismatch {
             query_era: Era,
@@ -100,6 +272,7 @@ define_ogmios_error! {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::codec::RpcResponse;
 
     #[test]
     fn test_cost_model_serialization() {
@@ -112,13 +285,248 @@ mod tests {
         assert!(cost_models.plutus_v3.is_some());
     }
 
+    const MAINNET_PARAMS: &str = "tests/fixtures/queryLedgerState/protocolParameters.json";
+
+    /// The recorded mainnet parameters, decoded as a whole response since buffering by
+    /// `RpcResponse` goes through a different path than decoding the parameters directly
+    fn mainnet_params() -> ProtocolParams {
+        let response = std::fs::read_to_string(MAINNET_PARAMS).expect("ogmios response");
+        let response: RpcResponse<ProtocolParams, ProtocolParamsError> =
+            serde_json::from_str(&response).expect("protocol params response");
+        Result::from(response).expect("protocol params")
+    }
+
+    /// The `result` of the recorded mainnet response, to edit before decoding it
+    fn mainnet_params_json() -> serde_json::Value {
+        let response = std::fs::read_to_string(MAINNET_PARAMS).expect("ogmios response");
+        let json: serde_json::Value = serde_json::from_str(&response).expect("ogmios response");
+        json.get("result").cloned().expect("missing result")
+    }
+
+    #[test]
+    fn test_mainnet_script_execution_prices() {
+        let params = mainnet_params();
+
+        let prices = &params.script_execution_prices;
+        assert_eq!(prices.memory.0, BigRational::new(577.into(), 10000.into()));
+        assert_eq!(prices.cpu.0, BigRational::new(721.into(), 10000000.into()));
+
+        let prices: ExecutionUnits = serde_json::from_value(
+            serde_json::json!({ "memory": "577/10000", "cpu": "721/10000000" }),
+        )
+        .unwrap();
+        assert_eq!(prices, params.script_execution_prices);
+    }
+
+    #[test]
+    fn test_mainnet_max_execution_units() {
+        let params = mainnet_params();
+
+        let per_tx = &params.max_execution_units_per_transaction;
+        assert_eq!(
+            per_tx.memory.0,
+            BigRational::from_integer(10_000_000.into())
+        );
+        assert_eq!(
+            per_tx.cpu.0,
+            BigRational::from_integer(10_000_000_000u64.into())
+        );
+        let per_block = &params.max_execution_units_per_block;
+        assert_eq!(
+            per_block.memory.0,
+            BigRational::from_integer(50_000_000.into())
+        );
+        assert_eq!(
+            per_block.cpu.0,
+            BigRational::from_integer(40_000_000_000u64.into())
+        );
+
+        let budget: ExecutionUnits = serde_json::from_value(
+            serde_json::json!({ "memory": 12_000_000, "cpu": 9_000_000_000u64 }),
+        )
+        .unwrap();
+        assert_eq!(budget.overage(per_tx), Some((2_000_000, -1_000_000_000)));
+    }
+
+    #[test]
+    fn test_script_execution_price_for() {
+        let mut params = mainnet_params();
+
+        let units: ExecutionUnits =
+            serde_json::from_value(serde_json::json!({ "memory": 1_000_000, "cpu": 500_000_000 }))
+                .unwrap();
+        // 0.0577 * 1_000_000 + 0.0000721 * 500_000_000 = 57_700 + 36_050
+        assert_eq!(params.script_execution_price_for(&units), Some(93_750));
+
+        let units: ExecutionUnits =
+            serde_json::from_value(serde_json::json!({ "memory": 1, "cpu": 1 })).unwrap();
+        // 0.0577 + 0.0000721 rounds up to 1
+        assert_eq!(params.script_execution_price_for(&units), Some(1));
+
+        params.script_execution_prices =
+            serde_json::from_value(serde_json::json!({ "memory": "2/1", "cpu": "1/1" })).unwrap();
+        let units: ExecutionUnits =
+            serde_json::from_value(serde_json::json!({ "memory": u64::MAX, "cpu": 0 })).unwrap();
+        assert_eq!(params.script_execution_price_for(&units), None);
+    }
+
+    fn params_with_reference_script_tiers(tiers: serde_json::Value) -> ProtocolParams {
+        ProtocolParams {
+            min_fee_reference_scripts: serde_json::from_value(tiers).expect("tiers"),
+            ..mainnet_params()
+        }
+    }
+
+    #[test]
+    fn test_reference_script_fee() {
+        let params = params_with_reference_script_tiers(
+            serde_json::json!({ "range": 1024, "base": 10, "multiplier": 1.2 }),
+        );
+        assert_eq!(params.reference_script_fee(0), Some(0));
+        assert_eq!(params.reference_script_fee(1024), Some(10240));
+        assert_eq!(params.reference_script_fee(2048), Some(22528));
+        // 29900.8, rounded down
+        assert_eq!(params.reference_script_fee(2560), Some(29900));
+    }
+
+    #[test]
+    fn test_mainnet_reference_script_fee() {
+        let mut params = mainnet_params();
+        // 15 * 10_000
+        assert_eq!(params.reference_script_fee(10_000), Some(150_000));
+        // 15 * 25_600 + 18 * 4_400
+        assert_eq!(params.reference_script_fee(30_000), Some(463_200));
+
+        // Eight full ranges: 15 * 25_600 * (1 + 1.2 + ... + 1.2^7) = 6_335_648.56
+        assert_eq!(
+            params.reference_script_fee(MinFeeReferenceScripts::MAX_BYTES_PER_TX),
+            Some(6_335_648)
+        );
+        assert_eq!(
+            params.reference_script_fee(MinFeeReferenceScripts::MAX_BYTES_PER_TX + 1),
+            None
+        );
+
+        params.min_fee_reference_scripts = None;
+        assert_eq!(params.reference_script_fee(30_000), Some(0));
+    }
+
+    #[test]
+    fn reference_script_fee_out_of_range() {
+        let tiers = |base: f64, multiplier: f64| MinFeeReferenceScripts {
+            range: 25600,
+            base,
+            multiplier,
+        };
+        assert_eq!(tiers(1e300, 1.2).fee_for(1), None);
+        assert_eq!(tiers(f64::INFINITY, 1.2).fee_for(1), None);
+        assert_eq!(tiers(15.0, f64::NAN).fee_for(1), None);
+        assert_eq!(tiers(15.0, 1.2).fee_for(u64::MAX), None);
+    }
+
+    #[test]
+    fn test_min_utxo_value() {
+        let mut params = mainnet_params();
+
+        assert_eq!(params.min_utxo_deposit_coefficient, 4310);
+        assert_eq!(params.min_utxo_deposit_constant.lovelace, 0);
+        // 4310 * (30 + 160)
+        assert_eq!(params.min_utxo_value(30), Some(818_900));
+        // 4310 * (80 + 160)
+        assert_eq!(params.min_utxo_value(80), Some(1_034_400));
+        assert_eq!(params.min_utxo_value(u64::MAX / 4310), None);
+        assert_eq!(params.min_utxo_value(u64::MAX), None);
+
+        params.min_utxo_deposit_constant = AdaBalance {
+            lovelace: 1_000_000,
+        };
+        // 1_000_000 + 4310 * (30 + 160)
+        assert_eq!(params.min_utxo_value(30), Some(1_818_900));
+        params.min_utxo_deposit_constant = AdaBalance { lovelace: u64::MAX };
+        assert_eq!(params.min_utxo_value(30), None);
+    }
+
+    #[test]
+    fn test_cost_models_for() {
+        let mut params = mainnet_params();
+        params.plutus_cost_models.plutus_v3 = None;
+
+        let cost_models = params.cost_models_for(&[Language::PlutusV2, Language::PlutusV3]);
+        assert_eq!(cost_models.len(), 2);
+        assert_eq!(cost_models[0].0, Language::PlutusV2);
+        assert_eq!(
+            cost_models[0].1.map(|model| &model.0),
+            params
+                .plutus_cost_models
+                .plutus_v2
+                .as_ref()
+                .map(|model| &model.0)
+        );
+        assert!(cost_models[0].1.is_some());
+        assert_eq!(cost_models[1].0, Language::PlutusV3);
+        assert!(cost_models[1].1.is_none());
+    }
+
     #[test]
     fn test_protocol_params_deserialization() {
-        let response = std::fs::read_to_string("tests/data/ogmios_protocol_params.json")
-            .expect("ogmios response");
-        let json: serde_json::Value = serde_json::from_str(&response).expect("ogmios response");
-        let result = json.get("result").cloned().expect("missing result");
+        let params = mainnet_params();
+        assert_eq!(params.stake_credential_deposit.lovelace, 2_000_000);
+        assert_eq!(params.stake_pool_deposit.lovelace, 500_000_000);
+        assert_eq!(params.desired_number_of_stake_pools, 150);
+        assert_eq!(
+            params.stake_pool_pledge_influence.0,
+            BigRational::new(3.into(), 10.into())
+        );
+        assert_eq!(params.max_transaction_size.bytes, 16384);
+        assert_eq!(params.max_block_body_size.bytes, 65536);
+        assert_eq!(params.max_block_header_size.bytes, 1100);
+        assert_eq!(params.max_value_size.bytes, 5000);
+        assert_eq!(params.max_collateral_inputs, 3);
+        assert_eq!(params.collateral_percentage, 150.0);
+        assert_eq!(
+            params.governance_action_deposit.unwrap().lovelace,
+            100_000_000_000
+        );
+        assert_eq!(
+            params.delegate_representative_deposit.unwrap().lovelace,
+            500_000_000
+        );
+    }
+
+    #[test]
+    fn test_protocol_params_without_conway_fields() {
+        let mut result = mainnet_params_json();
+        let object = result.as_object_mut().unwrap();
+        object.remove("minFeeReferenceScripts");
+        object.remove("governanceActionDeposit");
+        object.remove("delegateRepresentativeDeposit");
+
         let params: ProtocolParams = serde_json::from_value(result).expect("protocol params");
-        assert!(params.stake_credential_deposit.lovelace > 0);
+        assert!(params.min_fee_reference_scripts.is_none());
+        assert!(params.governance_action_deposit.is_none());
+        assert_eq!(
+            params.delegate_representative_deposit_required(),
+            Err(MissingProtocolParam {
+                field: "delegateRepresentativeDeposit"
+            })
+        );
+        let err = params.min_fee_reference_scripts_required().unwrap_err();
+        assert_eq!(err.field, "minFeeReferenceScripts");
+        assert_eq!(
+            err.to_string(),
+            "protocol parameter 'minFeeReferenceScripts' is not available from this node"
+        );
+    }
+
+    #[test]
+    fn test_require_present_field() {
+        let params = mainnet_params();
+
+        let min_fee = params.min_fee_reference_scripts_required().unwrap();
+        assert_eq!(min_fee.range, 25600);
+        let deposit = params.governance_action_deposit_required().unwrap();
+        assert_eq!(deposit.lovelace, 100_000_000_000u64);
+        let deposit = params.delegate_representative_deposit_required().unwrap();
+        assert_eq!(deposit.lovelace, 500_000_000);
     }
 }
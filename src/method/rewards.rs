@@ -2,8 +2,11 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::codec::{AdaBalance, Era, RpcRequest, RpcResponse};
+use crate::codec::{
+    AdaBalance, Credential, Era, Hash32, PoolId, Ratio, RewardAccount, RpcRequest, RpcResponse,
+};
 use crate::define_ogmios_error;
+use crate::error::InputError;
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -14,6 +17,30 @@ pub struct RewardAccountSummariesParams {
     pub scripts: Option<Vec<String>>,
 }
 
+impl From<Vec<Credential>> for RewardAccountSummariesParams {
+    fn from(credentials: Vec<Credential>) -> Self {
+        let DelegationsAndRewardsParams { keys, scripts } = credentials.into();
+        Self {
+            keys: (!keys.is_empty()).then_some(keys),
+            scripts: (!scripts.is_empty()).then_some(scripts),
+        }
+    }
+}
+
+impl RewardAccountSummariesParams {
+    /// Rewrites every credential as a hex-encoded hash, the form all Ogmios versions accept.
+    /// Bech32 stake addresses are moved to `keys` or `scripts` according to their credential.
+    pub fn normalize(&self) -> Result<Self, InputError> {
+        let keys = self.keys.iter().flatten().map(|key| (key, false));
+        let scripts = self.scripts.iter().flatten().map(|script| (script, true));
+        let credentials = keys
+            .chain(scripts)
+            .map(|(credential, script)| Credential::parse(credential, script))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(credentials.into())
+    }
+}
+
 pub type RewardAccountSummariesRequest = RpcRequest<RewardAccountSummariesParams>;
 
 #[derive(Debug, Clone, Deserialize)]
@@ -27,12 +54,14 @@ pub struct RewardAccountSummary {
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Delegate {
-    pub id: String,
-    pub vrf: Option<String>,
+    pub id: PoolId,
+    /// Hash of the pool's VRF verification key
+    #[serde(default)]
+    pub vrf: Option<Hash32>,
 }
 
 define_ogmios_error! {
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq)]
     pub enum RewardAccountSummariesError {
         2001 => EraMismatch {
             query_era: Era,
@@ -47,6 +76,99 @@ define_ogmios_error! {
 pub type RewardAccountSummariesResponse =
     RpcResponse<HashMap<String, RewardAccountSummary>, RewardAccountSummariesError>;
 
+/// Same as [`RewardAccountSummariesResponse`] with keys decoded as reward accounts
+pub type TypedRewardAccountSummariesResponse =
+    RpcResponse<HashMap<RewardAccount, RewardAccountSummary>, RewardAccountSummariesError>;
+
+// Delegations and rewards
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegationsAndRewardsParams {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub keys: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub scripts: Vec<String>,
+}
+
+impl From<Vec<Credential>> for DelegationsAndRewardsParams {
+    fn from(credentials: Vec<Credential>) -> Self {
+        let mut keys = vec![];
+        let mut scripts = vec![];
+        for credential in credentials {
+            match credential {
+                Credential::Key(hash) => keys.push(hash),
+                Credential::Script(hash) => scripts.push(hash),
+            }
+        }
+        Self { keys, scripts }
+    }
+}
+
+pub type DelegationsAndRewardsRequest = RpcRequest<DelegationsAndRewardsParams>;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegationAndReward {
+    /// Stake pool the credential is delegated to, if any
+    #[serde(default)]
+    pub delegate: Option<PoolId>,
+    pub rewards: AdaBalance,
+}
+
+define_ogmios_error! {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum DelegationsAndRewardsError {
+        2001 => EraMismatch {
+            query_era: Era,
+            ledger_era: Era,
+        },
+        2002 => UnavailableInCurrentEra,
+        2003 => StateAcquiredExpired(String)
+        _ => Unknown { error: Value }
+    }
+}
+
+pub type DelegationsAndRewardsResponse =
+    RpcResponse<HashMap<String, DelegationAndReward>, DelegationsAndRewardsError>;
+
+// Rewards provenance
+
+/// Inputs to the reward calculation of the ongoing epoch
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardsProvenance {
+    pub desired_number_of_stake_pools: u64,
+    pub stake_pool_pledge_influence: Ratio,
+    pub total_rewards_in_epoch: AdaBalance,
+    pub active_stake_in_epoch: AdaBalance,
+    pub total_stake_in_epoch: AdaBalance,
+    pub stake_pools: HashMap<PoolId, StakePoolProvenance>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakePoolProvenance {
+    pub stake: AdaBalance,
+    pub owner_stake: AdaBalance,
+    pub approximate_performance: f64,
+}
+
+define_ogmios_error! {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum RewardsProvenanceError {
+        2001 => EraMismatch {
+            query_era: Era,
+            ledger_era: Era,
+        },
+        2002 => UnavailableInCurrentEra,
+        2003 => StateAcquiredExpired(String)
+        _ => Unknown { error: Value }
+    }
+}
+
+pub type RewardsProvenanceResponse = RpcResponse<RewardsProvenance, RewardsProvenanceError>;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,6 +244,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn typed_reward_account_summaries_keys() {
+        let json = r#"{"jsonrpc":"2.0","method":"queryLedgerState/rewardAccountSummaries","result":{"af71729c838c1f33529fbd5d72564468fb530febd289976b3733f448":{"delegate":{"id":"pool1prc9hna2mgamtspchrygc66s9n4tlkvh39e3t9zccef4kzc3ns2"},"rewards":{"ada":{"lovelace":7737851}},"deposit":{"ada":{"lovelace":2000000}}}},"id":null}"#;
+        let response: TypedRewardAccountSummariesResponse = serde_json::from_str(json).unwrap();
+        let summaries = Result::from(response).unwrap();
+
+        let account = RewardAccount::try_from(
+            "af71729c838c1f33529fbd5d72564468fb530febd289976b3733f448".to_string(),
+        )
+        .unwrap();
+        let summary = &summaries[&account];
+        assert_eq!(summary.rewards.lovelace, 7737851);
+        assert_eq!(
+            summary.delegate.as_ref().unwrap().id,
+            PoolId("pool1prc9hna2mgamtspchrygc66s9n4tlkvh39e3t9zccef4kzc3ns2".to_string())
+        );
+
+        let invalid = json.replace("af71729c", "not-hex!");
+        assert!(serde_json::from_str::<TypedRewardAccountSummariesResponse>(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_delegate_with_vrf() {
+        let json = r#"{
+            "id": "pool1prc9hna2mgamtspchrygc66s9n4tlkvh39e3t9zccef4kzc3ns2",
+            "vrf": "c2b62ffa92ad18ffc117ea3abeb161a68885000a466f9c71db5e4731d6630061"
+        }"#;
+        let delegate: Delegate = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            delegate.id,
+            "pool1prc9hna2mgamtspchrygc66s9n4tlkvh39e3t9zccef4kzc3ns2"
+        );
+        assert_eq!(
+            delegate.vrf.unwrap().as_str(),
+            "c2b62ffa92ad18ffc117ea3abeb161a68885000a466f9c71db5e4731d6630061"
+        );
+    }
+
+    #[test]
+    fn test_delegate_without_vrf() {
+        let json = r#"{ "id": "pool1prc9hna2mgamtspchrygc66s9n4tlkvh39e3t9zccef4kzc3ns2" }"#;
+        let delegate: Delegate = serde_json::from_str(json).unwrap();
+        assert!(delegate.vrf.is_none());
+    }
+
+    #[test]
+    fn test_delegate_rejects_invalid_ids() {
+        let json = r#"{ "id": "not a pool" }"#;
+        assert!(serde_json::from_str::<Delegate>(json).is_err());
+
+        let json = r#"{
+            "id": "pool1prc9hna2mgamtspchrygc66s9n4tlkvh39e3t9zccef4kzc3ns2",
+            "vrf": "c2b6"
+        }"#;
+        assert!(serde_json::from_str::<Delegate>(json).is_err());
+    }
+
     #[test]
     fn test_params_serialization() {
         let params = RewardAccountSummariesParams {
@@ -139,6 +318,56 @@ mod tests {
         assert_eq!(json, r#"{"scripts":["script1"]}"#);
     }
 
+    #[test]
+    fn serialize_normalized_params() {
+        let params = RewardAccountSummariesParams {
+            keys: Some(vec![
+                "stake1uyehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gh6ffgw".to_string(),
+                "F4F2B9B6F3E9D3F5A1C2B3D4E5F60718293A4B5C6D7E8F90A1B2C3D4".to_string(),
+            ]),
+            scripts: None,
+        };
+        let json = serde_json::to_value(params.normalize().unwrap()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "keys": [
+                    "337b62cfff6403a06a3acbc34f8c46003c69fe79a3628cefa9c47251",
+                    "f4f2b9b6f3e9d3f5a1c2b3d4e5f60718293a4b5c6d7e8f90a1b2c3d4"
+                ]
+            })
+        );
+
+        // A script stake address given as a key is sent as a script
+        let params = RewardAccountSummariesParams {
+            keys: Some(vec![
+                "stake178phkx6acpnf78fuvxn0mkew3l0fd058hzquvz7w36x4gtcccycj5".to_string(),
+            ]),
+            scripts: Some(vec![
+                "c37b1b5dc0669f1d3c61a6fddb2e8fde96be87b881c60bce8e8d5400".to_string(),
+            ]),
+        };
+        let json = serde_json::to_value(params.normalize().unwrap()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "scripts": [
+                    "c37b1b5dc0669f1d3c61a6fddb2e8fde96be87b881c60bce8e8d542f",
+                    "c37b1b5dc0669f1d3c61a6fddb2e8fde96be87b881c60bce8e8d5400"
+                ]
+            })
+        );
+
+        let params = RewardAccountSummariesParams {
+            keys: Some(vec!["key1".to_string()]),
+            scripts: None,
+        };
+        assert!(matches!(
+            params.normalize(),
+            Err(InputError::InvalidCredential(_))
+        ));
+    }
+
     #[test]
     fn test_reward_account_summary_invalid_key_error() {
         let json = r#"{
@@ -165,4 +394,71 @@ mod tests {
             _ => panic!("Expected Unknown error variant for code -32600"),
         }
     }
+
+    #[test]
+    fn test_delegations_and_rewards_deserialization() {
+        let json = r#"{"jsonrpc":"2.0","method":"queryLedgerState/delegationsAndRewards","result":{"af71729c838c1f33529fbd5d72564468fb530febd289976b3733f448":{"delegate":"pool1prc9hna2mgamtspchrygc66s9n4tlkvh39e3t9zccef4kzc3ns2","rewards":{"ada":{"lovelace":7737851}}},"9f84cbd5b7b1a0d4fbc1c1a6a2c5b9a8b1b07e1d4ac3e0b69b1bba2d":{"rewards":{"ada":{"lovelace":0}}}},"id":null}"#;
+        let response: DelegationsAndRewardsResponse =
+            serde_json::from_str(json).expect("failed to deserialize");
+
+        let result: Result<HashMap<String, DelegationAndReward>, DelegationsAndRewardsError> =
+            response.into();
+        let entries = result.unwrap();
+        assert_eq!(entries.len(), 2);
+
+        let delegated = entries
+            .get("af71729c838c1f33529fbd5d72564468fb530febd289976b3733f448")
+            .unwrap();
+        assert_eq!(
+            delegated.delegate,
+            Some(PoolId(
+                "pool1prc9hna2mgamtspchrygc66s9n4tlkvh39e3t9zccef4kzc3ns2".to_string()
+            ))
+        );
+        assert_eq!(delegated.rewards.lovelace, 7737851);
+
+        let undelegated = entries
+            .get("9f84cbd5b7b1a0d4fbc1c1a6a2c5b9a8b1b07e1d4ac3e0b69b1bba2d")
+            .unwrap();
+        assert!(undelegated.delegate.is_none());
+        assert_eq!(undelegated.rewards.lovelace, 0);
+    }
+
+    #[test]
+    fn test_delegations_and_rewards_params_serialization() {
+        let params = DelegationsAndRewardsParams::from(vec![
+            Credential::Key("key1".to_string()),
+            Credential::Script("script1".to_string()),
+            Credential::Key("key2".to_string()),
+        ]);
+        let json = serde_json::to_string(&params).unwrap();
+        assert_eq!(json, r#"{"keys":["key1","key2"],"scripts":["script1"]}"#);
+    }
+
+    #[test]
+    fn deserialize_rewards_provenance() {
+        let json = r#"{
+            "desiredNumberOfStakePools": 500,
+            "stakePoolPledgeInfluence": "3/10",
+            "totalRewardsInEpoch": { "ada": { "lovelace": 9000000000 } },
+            "activeStakeInEpoch": { "ada": { "lovelace": 21000000000000 } },
+            "totalStakeInEpoch": { "ada": { "lovelace": 33000000000000 } },
+            "stakePools": {
+                "pool1prc9hna2mgamtspchrygc66s9n4tlkvh39e3t9zccef4kzc3ns2": {
+                    "stake": { "ada": { "lovelace": 1000000000 } },
+                    "ownerStake": { "ada": { "lovelace": 500000000 } },
+                    "approximatePerformance": 0.98,
+                    "parameters": { "cost": { "ada": { "lovelace": 340000000 } } }
+                }
+            }
+        }"#;
+        let provenance: RewardsProvenance = serde_json::from_str(json).unwrap();
+        assert_eq!(provenance.desired_number_of_stake_pools, 500);
+        assert_eq!(provenance.total_rewards_in_epoch.lovelace, 9000000000);
+        let pool = PoolId("pool1prc9hna2mgamtspchrygc66s9n4tlkvh39e3t9zccef4kzc3ns2".to_string());
+        assert_eq!(
+            provenance.stake_pools[&pool].owner_stake.lovelace,
+            500000000
+        );
+    }
 }
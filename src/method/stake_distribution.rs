@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+use std::ops::Deref;
+
+use num::{BigRational, Zero as _};
+use serde::Deserialize;
+
+use crate::codec::{Era, PoolId, Ratio, RpcResponse};
+use crate::define_ogmios_error;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakeDistributionEntry {
+    /// Stake of the pool relative to the total active stake
+    pub stake: Ratio,
+    /// Hex-encoded 32-byte blake2b hash digest of the pool's VRF verification key
+    pub vrf: String,
+}
+
+/// Live stake distribution, keyed by pool id
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(transparent)]
+pub struct StakeDistribution(pub HashMap<PoolId, StakeDistributionEntry>);
+
+impl Deref for StakeDistribution {
+    type Target = HashMap<PoolId, StakeDistributionEntry>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl StakeDistribution {
+    /// Sum of every pool's relative stake, which should be (close to) 1
+    pub fn total_active_stake_fraction(&self) -> BigRational {
+        self.0
+            .values()
+            .fold(BigRational::zero(), |total, entry| total + &entry.stake.0)
+    }
+
+    /// The `n` pools with the highest stake, in descending order of stake
+    pub fn top_n_pools(&self, n: usize) -> Vec<(&PoolId, &StakeDistributionEntry)> {
+        let mut pools: Vec<_> = self.0.iter().collect();
+        pools.sort_by(|(_, a), (_, b)| b.stake.0.cmp(&a.stake.0));
+        pools.truncate(n);
+        pools
+    }
+}
+
+define_ogmios_error! {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum StakeDistributionError {
+        2001 => EraMismatch {
+            query_era: Era,
+            ledger_era: Era,
+        },
+        2002 => UnavailableInCurrentEra,
+        2003 => StateAcquiredExpired(String)
+        _ => Unknown { error: Value }
+    }
+}
+
+pub type StakeDistributionResponse = RpcResponse<StakeDistribution, StakeDistributionError>;
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    const RESPONSE: &str = r#"{"jsonrpc":"2.0","method":"queryLedgerState/liveStakeDistribution","result":{"pool1qqqqqdk4zhsjuxxd8jyvwncf5eucfskz0xjjj64fdmlgj735lr9":{"stake":"1/4","vrf":"d9f8ba8a1b8e3e3b5c9c7e0b6c2a1f9d8e7c6b5a49382716f5e4d3c2b1a09f8e"},"pool1z5uqdk7dzdxaae5633fqfcu2eqzy3a3rgtuvy087fdld7yws0xt":{"stake":"1/2","vrf":"a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90"},"pool1pu5jlj4q9w9jlxeu370a3c9myx47md5j5m2str0naunn2q3lkdy":{"stake":"1/4","vrf":"0f1e2d3c4b5a69788796a5b4c3d2e1f00f1e2d3c4b5a69788796a5b4c3d2e1f0"}},"id":null}"#;
+
+    #[test]
+    fn deserialize_live_stake_distribution() {
+        let response: StakeDistributionResponse = serde_json::from_str(RESPONSE).unwrap();
+        let result: Result<StakeDistribution, StakeDistributionError> = response.into();
+        let distribution = result.unwrap();
+
+        assert_eq!(distribution.len(), 3);
+        assert_eq!(
+            distribution.total_active_stake_fraction(),
+            BigRational::from_integer(1.into())
+        );
+    }
+
+    #[test]
+    fn top_n_pools_in_order() {
+        let response: StakeDistributionResponse = serde_json::from_str(RESPONSE).unwrap();
+        let result: Result<StakeDistribution, StakeDistributionError> = response.into();
+        let distribution = result.unwrap();
+
+        let top = distribution.top_n_pools(2);
+        assert_eq!(top.len(), 2);
+        assert_eq!(
+            top[0].0.as_str(),
+            "pool1z5uqdk7dzdxaae5633fqfcu2eqzy3a3rgtuvy087fdld7yws0xt"
+        );
+        assert_eq!(top[0].1.stake.0, BigRational::from_str("1/2").unwrap());
+        assert_eq!(top[1].1.stake.0, BigRational::from_str("1/4").unwrap());
+    }
+}
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::codec::*;
+use crate::define_ogmios_error;
+
+// Moved to the codec module, shared with pool registration certificates
+pub use crate::codec::{Relay, StakePoolParameters};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakePoolsParams {
+    pub stake_pools: Vec<StakePoolId>,
+}
+
+impl From<Vec<String>> for StakePoolsParams {
+    fn from(ids: Vec<String>) -> Self {
+        Self {
+            stake_pools: ids.into_iter().map(|id| StakePoolId { id }).collect(),
+        }
+    }
+}
+
+define_ogmios_error! {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum StakePoolsError {
+        2001 => EraMismatch {
+            query_era: Era,
+            ledger_era: Era,
+        },
+        2002 => UnavailableInCurrentEra,
+        2003 => StateAcquiredExpired(String)
+        _ => Unknown { error: Value }
+    }
+}
+
+pub type StakePoolsResponse = RpcResponse<HashMap<String, StakePoolParameters>, StakePoolsError>;
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn serialize_filter() {
+        let params = StakePoolsParams::from(vec![
+            "pool1prc9hna2mgamtspchrygc66s9n4tlkvh39e3t9zccef4kzc3ns2".to_string(),
+        ]);
+        assert_eq!(
+            serde_json::to_value(params).unwrap(),
+            json!({ "stakePools": [{ "id": "pool1prc9hna2mgamtspchrygc66s9n4tlkvh39e3t9zccef4kzc3ns2" }] })
+        );
+    }
+
+    #[test]
+    fn deserialize_stake_pools() {
+        let json = r#"{
+            "jsonrpc": "2.0",
+            "method": "queryLedgerState/stakePools",
+            "result": {
+                "pool1prc9hna2mgamtspchrygc66s9n4tlkvh39e3t9zccef4kzc3ns2": {
+                    "id": "pool1prc9hna2mgamtspchrygc66s9n4tlkvh39e3t9zccef4kzc3ns2",
+                    "vrfVerificationKeyHash": "c2b62ffa92ad18ffc117ea3abeb161a68885000a466f9c71db5e4731d6630061",
+                    "owners": ["af71729c838c1f33529fbd5d72564468fb530febd289976b3733f448"],
+                    "cost": { "ada": { "lovelace": 340000000 } },
+                    "margin": "1/50",
+                    "pledge": { "ada": { "lovelace": 100000000000 } },
+                    "rewardAccount": "stake1u9x9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m9m",
+                    "metadata": {
+                        "url": "https://example.com/pool.json",
+                        "hash": "ab12ab12ab12ab12ab12ab12ab12ab12ab12ab12ab12ab12ab12ab12ab12ab12"
+                    },
+                    "relays": [
+                        { "type": "ipAddress", "ipv4": "192.0.2.1", "port": 3001 },
+                        { "type": "hostname", "hostname": "relay.example.com", "port": 3001 },
+                        { "type": "hostname", "hostname": "_pool._tcp.example.com" }
+                    ]
+                }
+            }
+        }"#;
+        let response: StakePoolsResponse = serde_json::from_str(json).unwrap();
+        let pools = Result::from(response).unwrap();
+        let pool = &pools["pool1prc9hna2mgamtspchrygc66s9n4tlkvh39e3t9zccef4kzc3ns2"];
+        assert_eq!(pool.cost.lovelace, 340000000);
+        assert_eq!(pool.relays.len(), 3);
+        assert_eq!(
+            pool.relays[0],
+            Relay::IpAddress {
+                ipv4: Some("192.0.2.1".to_string()),
+                ipv6: None,
+                port: Some(3001),
+            }
+        );
+        assert_eq!(
+            pool.relays[2],
+            Relay::Hostname {
+                hostname: "_pool._tcp.example.com".to_string(),
+                port: None,
+            }
+        );
+    }
+}
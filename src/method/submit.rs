@@ -3,11 +3,15 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use super::utxo::Utxo;
+use super::evaluate::EvaluationError;
+use super::mempool::MempoolError;
+use super::utxo::{Utxo, UtxoError};
 use crate::codec::{
-    AdaBalance, AdaBalanceDelta, Balance, CredentialOrigin, Era, ExecutionUnits, InputSource,
-    Language, NumberOfBytes, ProtocolVersion, RedeemerPointer, ScriptPurpose, StakePoolId, TxCbor,
-    TxId, TxOutput, TxOutputPointer, ValidityInterval,
+    AdaBalance, AdaBalanceDelta, Balance, CredentialOrigin, DelegateRepresentative,
+    DeserializationFailure, Era, ExecutionUnits, GovernanceProposal, GovernanceProposalReference,
+    InputSource, Language, NumberOfBytes, ProtocolVersion, RedeemerPointer, RejectedVote,
+    RewardAccount, ScriptError, ScriptExecutionError, ScriptPurpose, StakePoolId, TxCbor, TxId,
+    TxOutput, TxOutputPointer, ValidityInterval, Voter, deserialization_failure,
 };
 use crate::define_ogmios_error;
 
@@ -25,7 +29,7 @@ pub struct SubmitRequestParams {
 // Response
 // -----------
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, PartialEq)]
 #[doc = "hi"]
 pub struct MetadataHash {
     /// Hex-encoded 32-byte blake2b hash digest
@@ -33,7 +37,7 @@ pub struct MetadataHash {
 }
 
 define_ogmios_error! {
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq)]
     pub enum SubmitError {
         3005 => EraMismatch {
             query_era: Era,
@@ -148,7 +152,7 @@ define_ogmios_error! {
         },
         3132 => MissingCollateralInputs,
         3133 => NonAdaCollateral {
-            unsuitable_collateral_inputs: Balance
+            unsuitable_collateral_value: Balance
         },
         3134 => ExecutionUnitsTooLarge {
             provided_execution_units: ExecutionUnits,
@@ -163,10 +167,10 @@ define_ogmios_error! {
             mismatch_reason: String
         },
         3137 => UnauthorizedVotes {
-            unauthorized_votes: Vec<Value>, // TODO:
+            unauthorized_votes: Vec<RejectedVote>,
         },
         3138 => UnknownGovernanceProposals {
-            unknown_proposals: Vec<Value>, // TODO:
+            unknown_proposals: Vec<GovernanceProposalReference>,
         },
         3139 => InvalidProtocolParametersUpdate,
         3140 => UnknownStakePool {
@@ -213,10 +217,10 @@ define_ogmios_error! {
             expected_deposit: AdaBalance,
         },
         3152 => DRepAlreadyRegistered {
-            known_delegate_representative: Value // TODO:
+            known_delegate_representative: DelegateRepresentative
         },
         3153 => DRepNotRegistered {
-            unknown_delegate_representative: Value // TODO:
+            unknown_delegate_representative: DelegateRepresentative
         },
         3154 => UnknownConsitutionalCommitteeMember {
             unknown_consistency_committee_member: CommitteeMember
@@ -236,10 +240,10 @@ define_ogmios_error! {
             computed_withdrawal: AdaBalance,
         },
         3159 => InvalidOrMissingPreviousProposals {
-            invalid_or_missing_previous_proposals: Vec<Value>, // TODO:
+            invalid_or_missing_previous_proposals: Vec<GovernanceProposal>,
         },
         3160 => VotingOnExpiredActions {
-            inivalid_votes: Vec<Value>, // TODO:
+            invalid_votes: Vec<RejectedVote>,
         },
         3161 => ExecutionBudgetOutOfBounds {
             budget_used: ExecutionUnits,
@@ -261,7 +265,7 @@ define_ogmios_error! {
             maximum_reference_scripts: NumberOfBytes,
         },
         3167 => UnknownVoters {
-            unknown_voters: Vec<Value>, // TODO:
+            unknown_voters: Vec<Voter>,
         },
         3168 => EmptyTreasuryWithdrawal,
         3997 => UnexpectedMempoolError(Value),
@@ -279,30 +283,410 @@ define_ogmios_error! {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+impl SubmitError {
+    /// For -32602, the decoder failure of `era`, the transaction's declared era, if known.
+    /// Otherwise the most specific of the per-era failures.
+    pub fn deserialization_failure(&self, era: Option<&Era>) -> Option<DeserializationFailure<'_>> {
+        deserialization_failure!(self, era)
+    }
+
+    /// For 3129 (collateral locked by script), the collateral inputs to replace with inputs
+    /// locked by a key
+    pub fn unsuitable_collateral(&self) -> Option<&[TxOutputPointer]> {
+        match self {
+            SubmitError::CollateralLockedByScript {
+                unsuitable_collateral_inputs,
+                ..
+            } => Some(unsuitable_collateral_inputs),
+            _ => None,
+        }
+    }
+
+    /// For 3133 (non-ADA collateral), the offending collateral value, holding tokens other
+    /// than ADA
+    pub fn non_ada_collateral_value(&self) -> Option<&Balance> {
+        match self {
+            SubmitError::NonAdaCollateral {
+                unsuitable_collateral_value,
+                ..
+            } => Some(unsuitable_collateral_value),
+            _ => None,
+        }
+    }
+
+    /// For 3141 (incomplete withdrawals), the withdrawals to fix, sorted by reward account.
+    /// Each amount is the full reward balance of the account: withdrawals must drain the
+    /// account entirely, so the transaction should withdraw exactly that amount.
+    pub fn incomplete_withdrawals(&self) -> Option<Vec<(RewardAccount, AdaBalance)>> {
+        match self {
+            SubmitError::IncompleteWithdrawals {
+                incomplete_withdrawals,
+                ..
+            } => {
+                let mut withdrawals: Vec<_> = incomplete_withdrawals
+                    .iter()
+                    .map(|(account, balance)| (RewardAccount(account.clone()), balance.clone()))
+                    .collect();
+                withdrawals.sort_by(|(a, _), (b, _)| a.cmp(b));
+                Some(withdrawals)
+            }
+            _ => None,
+        }
+    }
+
+    /// For 3997 (unexpected mempool error), a readable description of the node's error. The
+    /// payload isn't typed by Ogmios: a plain string, or an object describing the error under
+    /// `error`, `reason` or `message`, possibly nested. `None` for other shapes, in which case
+    /// the raw payload is still available in `data`.
+    pub fn mempool_error_detail(&self) -> Option<String> {
+        fn describe(value: &Value) -> Option<String> {
+            match value {
+                Value::String(detail) => Some(detail.clone()),
+                Value::Object(fields) => ["error", "reason", "message"]
+                    .iter()
+                    .find_map(|key| fields.get(*key).and_then(describe)),
+                _ => None,
+            }
+        }
+
+        match self {
+            SubmitError::UnexpectedMempoolError { data, .. } => describe(data),
+            _ => None,
+        }
+    }
+
+    /// A short, actionable hint on how to fix the transaction, suitable for surfacing to
+    /// developers next to the error message
+    pub fn remediation(&self) -> &'static str {
+        match self {
+            SubmitError::EraMismatch { .. } => "build the transaction for the ledger's current era",
+            SubmitError::InvalidSignatories { .. } => {
+                "remove or fix the invalid signatures, signing the current transaction body"
+            }
+            SubmitError::MissingSignatories { .. } => {
+                "sign the transaction with every required key"
+            }
+            SubmitError::FailingNativeScripts { .. } => {
+                "satisfy the native scripts' conditions, e.g. signers and validity interval"
+            }
+            SubmitError::ExtraneousScripts { .. } => {
+                "remove the scripts no input, mint, certificate or withdrawal requires"
+            }
+            SubmitError::MissingMetadataHash { .. } => {
+                "set the auxiliary data hash in the transaction body"
+            }
+            SubmitError::MissingMetadata { .. } => {
+                "include the auxiliary data the body's hash refers to"
+            }
+            SubmitError::MetadataHashMismatch { .. } => {
+                "recompute the auxiliary data hash from the included metadata"
+            }
+            SubmitError::InvalidMetadata { .. } => {
+                "keep metadata values within the allowed sizes and shapes"
+            }
+            SubmitError::MissingRedeemers { .. } => "provide a redeemer for every script execution",
+            SubmitError::ExtraneousRedeemers { .. } => {
+                "remove redeemers that don't point to a script execution"
+            }
+            SubmitError::MissingDatums { .. } => {
+                "include the datums of the script-locked inputs being spent"
+            }
+            SubmitError::ExtraneousDatums { .. } => "remove datums no input or output needs",
+            SubmitError::ScriptIntegrityHashMismatch { .. } => {
+                "recompute the script integrity hash from the redeemers, datums and cost models"
+            }
+            SubmitError::OrphanScriptInputs { .. } => {
+                "spend script inputs with a datum hash or inline datum"
+            }
+            SubmitError::MissingCostModels { .. } => {
+                "use a language whose cost model is set in the protocol parameters"
+            }
+            SubmitError::MalformedScripts { .. } => "fix the serialization of the included scripts",
+            SubmitError::UnknownOutputReferences { .. } => {
+                "spend or reference only outputs that exist and are unspent"
+            }
+            SubmitError::OutsideOfValidityInterval { .. } => {
+                "adjust the validity interval to include the current slot"
+            }
+            SubmitError::TransactionTooLarge { .. } => {
+                "reduce the transaction size below the maximum, e.g. by using reference scripts"
+            }
+            SubmitError::ValueToolarge { .. } => "split the output value across several outputs",
+            SubmitError::EmptyInputSet { .. } => "spend at least one input",
+            SubmitError::TransactionFeeTooSmall { .. } => {
+                "increase the fee to at least the minimum required"
+            }
+            SubmitError::ValueNotConserved { .. } => {
+                "balance the transaction so inputs equal outputs plus fee and deposits"
+            }
+            SubmitError::NetworkMismatch { .. } => {
+                "use addresses and a network id of the node's network"
+            }
+            SubmitError::InsufficientlyFundedOutputs { .. } => {
+                "raise each output's lovelace to at least its minimum UTxO value"
+            }
+            SubmitError::BootstrapAttributesTooLarge { .. } => {
+                "use Byron addresses with smaller attributes"
+            }
+            SubmitError::MintingOrBurningAda { .. } => "remove ADA from the minted value",
+            SubmitError::InsufficientCollateral { .. } => {
+                "add collateral covering the required percentage of the fee"
+            }
+            SubmitError::CollateralLockedByScript { .. } => {
+                "replace script-locked collateral with key-locked inputs"
+            }
+            SubmitError::UnforeseeableSlot { .. } => {
+                "set a validity upper bound within the current era's safe zone"
+            }
+            SubmitError::TooManyCollateralInputs { .. } => "use fewer, larger collateral inputs",
+            SubmitError::MissingCollateralInputs { .. } => {
+                "add collateral inputs to transactions running Plutus scripts"
+            }
+            SubmitError::NonAdaCollateral { .. } => {
+                "use ADA-only collateral or add a collateral return output"
+            }
+            SubmitError::ExecutionUnitsTooLarge { .. } => {
+                "lower the redeemers' execution units below the per-transaction maximum"
+            }
+            SubmitError::TotalCollateralMismatch { .. } => {
+                "set total collateral to the collateral inputs minus the collateral return"
+            }
+            SubmitError::SpendsMismatch { .. } => {
+                "recompute the transaction against the current ledger state"
+            }
+            SubmitError::UnauthorizedVotes { .. } => {
+                "only vote on proposals the voter is allowed to vote on"
+            }
+            SubmitError::UnknownGovernanceProposals { .. } => {
+                "reference existing, active governance proposals"
+            }
+            SubmitError::InvalidProtocolParametersUpdate { .. } => {
+                "propose only well-formed protocol parameter values"
+            }
+            SubmitError::UnknownStakePool { .. } => "delegate to a registered stake pool",
+            SubmitError::IncompleteWithdrawals { .. } => {
+                "withdraw the full reward balance of each account"
+            }
+            SubmitError::RetirementTooLate { .. } => {
+                "retire the pool within the maximum retirement epoch bound"
+            }
+            SubmitError::StakePoolCostTooLow { .. } => {
+                "set the pool cost to at least the minimum pool cost"
+            }
+            SubmitError::MetadataHashTooLarge { .. } => "use a 32-byte metadata hash",
+            SubmitError::CredentialAlreadyRegistered { .. } => {
+                "skip registering credentials that are already registered"
+            }
+            SubmitError::UnknownCredential { .. } => "register the credential before using it",
+            SubmitError::NonEmptyRewardAccount { .. } => {
+                "withdraw the remaining rewards before deregistering"
+            }
+            SubmitError::InvalidGenesisDelegation { .. } => {
+                "use a valid genesis delegation certificate"
+            }
+            SubmitError::InvalidMIRTransfer { .. } => {
+                "use a valid move-instantaneous-rewards transfer"
+            }
+            SubmitError::ForbiddenWithdrawal { .. } => {
+                "delegate the credentials to a DRep before withdrawing rewards"
+            }
+            SubmitError::CredentialDepositMismatch { .. } => {
+                "set the deposit to the one required by the protocol parameters"
+            }
+            SubmitError::DRepAlreadyRegistered { .. } => {
+                "skip registering a DRep that is already registered"
+            }
+            SubmitError::DRepNotRegistered { .. } => "register the DRep before using it",
+            SubmitError::UnknownConsitutionalCommitteeMember { .. } => {
+                "use the credential of a current committee member"
+            }
+            SubmitError::GovernanceProposalDepositMismatch { .. } => {
+                "set the proposal deposit to the one required by the protocol parameters"
+            }
+            SubmitError::ConflictingCommitteeUpdate { .. } => {
+                "don't add and remove the same committee member in one update"
+            }
+            SubmitError::InvalidCommitteeUpdate { .. } => {
+                "set committee members' expiry epochs in the future"
+            }
+            SubmitError::TreasureWithdrawalMismatch { .. } => {
+                "set the treasury withdrawals' network to the node's network"
+            }
+            SubmitError::InvalidOrMissingPreviousProposals { .. } => {
+                "reference the last enacted proposal of the same purpose as ancestor"
+            }
+            SubmitError::VotingOnExpiredActions { .. } => {
+                "only vote on proposals that haven't expired"
+            }
+            SubmitError::ExecutionBudgetOutOfBounds { .. } => {
+                "lower the total execution budget below the maximum"
+            }
+            SubmitError::InvalidHardForkVersionBump { .. } => {
+                "propose the next major or minor protocol version"
+            }
+            SubmitError::ConstitutionGuardrailsHashMismatch { .. } => {
+                "use the guardrails script hash set by the constitution"
+            }
+            SubmitError::ConflictingInputsAndReferences { .. } => {
+                "don't reference inputs that are also spent"
+            }
+            SubmitError::UnauthorizedGovernanceAction { .. } => {
+                "only submit actions the proposer is allowed to propose"
+            }
+            SubmitError::ReferenceScriptsTooLarge { .. } => "reference fewer or smaller scripts",
+            SubmitError::UnknownVoters { .. } => "vote with registered voters only",
+            SubmitError::EmptyTreasuryWithdrawal { .. } => {
+                "withdraw a positive amount from the treasury"
+            }
+            SubmitError::UnexpectedMempoolError { .. } => {
+                "retry later, see the error detail for the node's reason"
+            }
+            SubmitError::UnrecognizedCertificateType { .. } => {
+                "use certificate types supported in the current era"
+            }
+            SubmitError::Deserialization { .. } => {
+                "fix the transaction's CBOR encoding for the current era"
+            }
+            SubmitError::Unknown { .. } => "see the error message and data",
+        }
+    }
+
+    /// For 3161 (execution budget out of bounds), how far the used budget exceeds `max` as
+    /// `(memory, cpu)`, see [`ExecutionUnits::overage`]
+    pub fn budget_overage(&self, max: &ExecutionUnits) -> Option<(i128, i128)> {
+        match self {
+            SubmitError::ExecutionBudgetOutOfBounds { budget_used, .. } => budget_used.overage(max),
+            _ => None,
+        }
+    }
+}
+
+/// Script execution errors sharing a code with a submit error convert into it:
+/// 3110 (extraneous redeemers), 3111 (missing datums), 3115 (missing cost models),
+/// 3117 (unknown output references) and 3161 (execution budget out of bounds).
+/// Other errors are returned unchanged.
+impl TryFrom<ScriptExecutionError> for SubmitError {
+    type Error = ScriptExecutionError;
+
+    fn try_from(error: ScriptExecutionError) -> Result<Self, Self::Error> {
+        match error {
+            ScriptExecutionError::ExtraneousRedeemers {
+                message,
+                extraneous_redeemers,
+            } => Ok(SubmitError::ExtraneousRedeemers {
+                message,
+                extraneous_redeemers,
+            }),
+            ScriptExecutionError::MissingDatums {
+                message,
+                missing_datums,
+            } => Ok(SubmitError::MissingDatums {
+                message,
+                missing_datums,
+            }),
+            ScriptExecutionError::MissingCostModels {
+                message,
+                missing_cost_models,
+            } => Ok(SubmitError::MissingCostModels {
+                message,
+                missing_cost_models,
+            }),
+            ScriptExecutionError::UnknownOutputReferences {
+                message,
+                unknown_output_references,
+            } => Ok(SubmitError::UnknownOutputReferences {
+                message,
+                unknown_output_references,
+            }),
+            ScriptExecutionError::ExecutionBudgetOutOfBounds {
+                message,
+                budget_used,
+            } => Ok(SubmitError::ExecutionBudgetOutOfBounds {
+                message,
+                budget_used,
+            }),
+            error => Err(error),
+        }
+    }
+}
+
+/// Evaluation errors convert into the equivalent submit error where the codes overlap:
+/// - `Deserialization` (-32602) maps one-to-one
+/// - `ScriptExecution` (3010) maps when it holds a single script error which itself converts
+///   (see the `ScriptExecutionError` conversion)
+///
+/// The evaluation-only errors (3000-3004) and unknown errors are returned unchanged.
+impl TryFrom<EvaluationError> for SubmitError {
+    type Error = EvaluationError;
+
+    fn try_from(error: EvaluationError) -> Result<Self, Self::Error> {
+        match error {
+            EvaluationError::Deserialization {
+                message,
+                byron,
+                shelley,
+                allegra,
+                mary,
+                alonzo,
+                babbage,
+                conway,
+            } => Ok(SubmitError::Deserialization {
+                message,
+                byron,
+                shelley,
+                allegra,
+                mary,
+                alonzo,
+                babbage,
+                conway,
+            }),
+            EvaluationError::ScriptExecution {
+                message,
+                mut errors,
+            } if errors.len() == 1 => {
+                let script_error = errors.remove(0);
+                SubmitError::try_from(script_error.error).map_err(|error| {
+                    EvaluationError::ScriptExecution {
+                        message,
+                        errors: vec![ScriptError {
+                            validator: script_error.validator,
+                            error,
+                        }],
+                    }
+                })
+            }
+            error => Err(error),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
 pub struct CommitteeMember {
     /// Hex-encoded 28-byte blake2b hash digest
     pub id: String,
     pub from: CredentialOrigin,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub enum Network {
     Mainnet,
     Testnet,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub enum NetworkMismatchDiscriminatedType {
     Address,
     RewardAccount,
     StakePoolCertificate,
     Transaction,
+    /// Any type introduced by a newer Ogmios version
+    #[serde(untagged)]
+    Other(String),
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct InsufficientlyFundedOutput {
     pub output: Utxo,
@@ -315,3 +699,477 @@ pub struct SubmitResult {
 }
 
 // pub type SubmitResponse = RpcResponse<SubmitResult, EvaluationError>;
+
+/// Where a submitted transaction currently is, see
+/// [`OgmiosHttpClient::transaction_status`](crate::OgmiosHttpClient::transaction_status)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxStatus {
+    /// At least one output of the transaction is in the UTxO set. The slot it was included in
+    /// isn't known: Ogmios doesn't report it alongside UTxOs.
+    OnChain,
+    /// The transaction is waiting in the node's mempool
+    InMempool,
+    /// Neither on chain nor in the mempool: not yet propagated, dropped, or on chain with
+    /// every output already spent
+    Unknown,
+}
+
+/// Error of [`OgmiosHttpClient::transaction_status`](crate::OgmiosHttpClient::transaction_status),
+/// from the mempool or the on-chain lookup
+#[derive(Debug, Clone, PartialEq)]
+pub enum TxStatusError {
+    Mempool(MempoolError),
+    Lookup(UtxoError),
+}
+
+impl std::fmt::Display for TxStatusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TxStatusError::Mempool(error) => write!(f, "failed to look up the mempool: {}", error),
+            TxStatusError::Lookup(error) => {
+                write!(f, "failed to look up transaction outputs: {}", error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TxStatusError {}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::codec::{RedeemerPurpose, TxPointer, VoterRole};
+
+    #[test]
+    fn extraneous_redeemers_evaluation_error_into_submit_error() {
+        let json = json!({
+            "code": 3010,
+            "message": "Some scripts of the transactions terminated with error(s).",
+            "data": [{
+                "validator": { "index": 0, "purpose": "spend" },
+                "error": {
+                    "code": 3110,
+                    "message": "Extraneous (non-required) redeemers found in the transaction.",
+                    "data": { "extraneousRedeemers": [{ "index": 1, "purpose": "mint" }] }
+                }
+            }]
+        });
+        let error: EvaluationError = serde_json::from_value(json).unwrap();
+
+        match SubmitError::try_from(error).unwrap() {
+            SubmitError::ExtraneousRedeemers {
+                extraneous_redeemers,
+                ..
+            } => {
+                assert_eq!(
+                    extraneous_redeemers,
+                    vec![RedeemerPointer {
+                        purpose: RedeemerPurpose::Mint,
+                        index: 1
+                    }]
+                );
+            }
+            error => panic!("Expected ExtraneousRedeemers, got {:?}", error),
+        }
+    }
+
+    #[test]
+    fn evaluation_only_error_does_not_convert() {
+        let json = json!({
+            "code": 3004,
+            "message": "Unable to create the evaluation context from the given transaction.",
+            "data": { "reason": "unknown inputs" }
+        });
+        let error: EvaluationError = serde_json::from_value(json).unwrap();
+
+        assert!(matches!(
+            SubmitError::try_from(error),
+            Err(EvaluationError::CannotCreateEvaluationContext { .. })
+        ));
+    }
+
+    #[test]
+    fn incomplete_withdrawals_sorted_by_account() {
+        let json = json!({
+            "code": 3141,
+            "message": "Withdrawals must drain reward accounts entirely.",
+            "data": {
+                "incompleteWithdrawals": {
+                    "stake_test1uzpq2pktpnj54e64kfgjkm8nrptdwfj7s7fvhp40e98qsusd9z7ek": { "ada": { "lovelace": 1_500_000 } },
+                    "stake_test1uqfu74w3wh4gfzu8m6e7j987h4lq9r3t7ef5gaw497uu85qsqfy27": { "ada": { "lovelace": 42 } }
+                }
+            }
+        });
+        let error: SubmitError = serde_json::from_value(json).unwrap();
+
+        let withdrawals = error.incomplete_withdrawals().unwrap();
+        assert_eq!(withdrawals.len(), 2);
+        assert_eq!(
+            withdrawals[0].0,
+            "stake_test1uqfu74w3wh4gfzu8m6e7j987h4lq9r3t7ef5gaw497uu85qsqfy27"
+        );
+        assert_eq!(withdrawals[0].1.lovelace, 42);
+        assert_eq!(withdrawals[1].1.lovelace, 1_500_000);
+    }
+
+    #[test]
+    fn network_mismatch_unknown_discriminated_type() {
+        let json = json!({
+            "code": 3124,
+            "message": "Some discriminated entities in the transaction are configured for another network.",
+            "data": {
+                "expectedNetwork": "mainnet",
+                "discriminatedType": "governanceProposal",
+                "invalidEntities": ["stake_test1uqfu74w3wh4gfzu8m6e7j987h4lq9r3t7ef5gaw497uu85qsqfy27"]
+            }
+        });
+        let error: SubmitError = serde_json::from_value(json).unwrap();
+
+        match error {
+            SubmitError::NetworkMismatch {
+                discriminated_type: NetworkMismatchDiscriminatedType::Other(other),
+                ..
+            } => assert_eq!(other, "governanceProposal"),
+            other => panic!("Expected a network mismatch, got {:?}", other),
+        }
+
+        let known: NetworkMismatchDiscriminatedType =
+            serde_json::from_value(json!("rewardAccount")).unwrap();
+        assert!(matches!(
+            known,
+            NetworkMismatchDiscriminatedType::RewardAccount
+        ));
+    }
+
+    #[test]
+    fn submit_errors_compare_equal() {
+        let json = json!({ "code": 3121, "message": "Empty input set" });
+        let error: SubmitError = serde_json::from_value(json).unwrap();
+
+        assert_eq!(
+            error,
+            SubmitError::EmptyInputSet {
+                message: "Empty input set".to_string(),
+            }
+        );
+        assert_ne!(
+            error,
+            SubmitError::EmptyInputSet {
+                message: "Another message".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn collateral_locked_by_script() {
+        let json = json!({
+            "code": 3129,
+            "message": "Collateral inputs are locked by scripts.",
+            "data": {
+                "unsuitableCollateralInputs": [
+                    { "transaction": { "id": "ab".repeat(32) }, "index": 1 }
+                ]
+            }
+        });
+        let error: SubmitError = serde_json::from_value(json).unwrap();
+
+        let inputs = error.unsuitable_collateral().unwrap();
+        assert_eq!(inputs.len(), 1);
+        assert_eq!(inputs[0].transaction.id, "ab".repeat(32));
+        assert_eq!(inputs[0].index, 1);
+        assert_eq!(error.non_ada_collateral_value(), None);
+    }
+
+    #[test]
+    fn non_ada_collateral() {
+        let json = json!({
+            "code": 3133,
+            "message": "Collateral inputs contain non-ADA tokens.",
+            "data": {
+                "unsuitableCollateralValue": {
+                    "ada": { "lovelace": 5_000_000 },
+                    "b0d07d45fe9514f80213f4020e5a61241458be626841cde717cb38a7": { "6e7574636f696e": 1 }
+                }
+            }
+        });
+        let error: SubmitError = serde_json::from_value(json).unwrap();
+
+        let value = error.non_ada_collateral_value().unwrap();
+        assert_eq!(value.lovelace, 5_000_000);
+        assert_eq!(value.assets.len(), 1);
+        assert_eq!(error.unsuitable_collateral(), None);
+    }
+
+    #[test]
+    fn deserialize_voting_on_expired_actions() {
+        let json = json!({
+            "code": 3160,
+            "message": "Voting on expired actions.",
+            "data": {
+                "invalidVotes": [{
+                    "voter": { "role": "stakePoolOperator", "id": "cc".repeat(28) },
+                    "proposal": { "transaction": { "id": "ee".repeat(32) }, "index": 2 }
+                }]
+            }
+        });
+        let error: SubmitError = serde_json::from_value(json).unwrap();
+        let SubmitError::VotingOnExpiredActions { invalid_votes, .. } = error else {
+            panic!("Expected VotingOnExpiredActions, got {:?}", error);
+        };
+        assert_eq!(
+            invalid_votes[0].voter.role,
+            crate::codec::VoterRole::StakePoolOperator
+        );
+        assert_eq!(invalid_votes[0].proposal.index, 2);
+    }
+
+    #[test]
+    fn deserialize_unknown_governance_proposals() {
+        let json = json!({
+            "code": 3138,
+            "message": "Unknown governance proposals.",
+            "data": {
+                "unknownProposals": [
+                    {
+                        "transaction": { "id": "5a2d4f1b6a4c8e3f0b9d7c6e5f4a3b2c1d0e9f8a7b6c5d4e3f2a1b0c9d8e7f6a" },
+                        "index": 0
+                    },
+                    {
+                        "transaction": { "id": "ee".repeat(32) },
+                        "index": 3
+                    }
+                ]
+            }
+        });
+        let error: SubmitError = serde_json::from_value(json).unwrap();
+        let SubmitError::UnknownGovernanceProposals {
+            unknown_proposals, ..
+        } = error
+        else {
+            panic!("Expected UnknownGovernanceProposals, got {:?}", error);
+        };
+        assert_eq!(
+            unknown_proposals,
+            [
+                GovernanceProposalReference {
+                    transaction: TxPointer {
+                        id: "5a2d4f1b6a4c8e3f0b9d7c6e5f4a3b2c1d0e9f8a7b6c5d4e3f2a1b0c9d8e7f6a"
+                            .to_string()
+                    },
+                    index: 0,
+                },
+                GovernanceProposalReference {
+                    transaction: TxPointer {
+                        id: "ee".repeat(32)
+                    },
+                    index: 3,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn deserialize_unknown_voters() {
+        let json = json!({
+            "code": 3167,
+            "message": "The transaction contains votes from unknown voters.",
+            "data": {
+                "unknownVoters": [
+                    { "role": "delegateRepresentative", "id": "bb".repeat(28), "from": "verificationKey" },
+                    { "role": "constitutionalCommittee", "id": "aa".repeat(28), "from": "script" },
+                    { "role": "stakePoolOperator", "id": "cc".repeat(28) }
+                ]
+            }
+        });
+        let error: SubmitError = serde_json::from_value(json).unwrap();
+        let SubmitError::UnknownVoters { unknown_voters, .. } = error else {
+            panic!("Expected UnknownVoters, got {:?}", error);
+        };
+        assert_eq!(
+            unknown_voters[0],
+            Voter {
+                role: VoterRole::DelegateRepresentative,
+                id: "bb".repeat(28),
+                from: Some(CredentialOrigin::VerificationKey),
+            }
+        );
+        assert_eq!(unknown_voters[1].from, Some(CredentialOrigin::Script));
+        assert_eq!(unknown_voters[2].role, VoterRole::StakePoolOperator);
+        assert_eq!(unknown_voters[2].from, None);
+    }
+
+    #[test]
+    fn deserialize_drep_already_registered() {
+        let json = json!({
+            "code": 3152,
+            "message": "Trying to re-register some already known delegate representative.",
+            "data": {
+                "knownDelegateRepresentative": {
+                    "type": "registered",
+                    "id": "bb".repeat(28),
+                    "from": "verificationKey"
+                }
+            }
+        });
+        let error: SubmitError = serde_json::from_value(json).unwrap();
+        let SubmitError::DRepAlreadyRegistered {
+            known_delegate_representative,
+            ..
+        } = error
+        else {
+            panic!("Expected DRepAlreadyRegistered, got {:?}", error);
+        };
+        assert_eq!(
+            known_delegate_representative,
+            DelegateRepresentative::Registered {
+                id: "bb".repeat(28),
+                from: CredentialOrigin::VerificationKey,
+            }
+        );
+    }
+
+    #[test]
+    fn remediation_hints() {
+        let error: SubmitError = serde_json::from_value(json!({
+            "code": 3122,
+            "message": "Insufficient fee!",
+            "data": {
+                "minimumRequiredFee": { "ada": { "lovelace": 171000 } },
+                "providedFee": { "ada": { "lovelace": 170000 } }
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            error.remediation(),
+            "increase the fee to at least the minimum required"
+        );
+
+        let error: SubmitError = serde_json::from_value(json!({
+            "code": 3121,
+            "message": "The transaction has no inputs."
+        }))
+        .unwrap();
+        assert_eq!(error.remediation(), "spend at least one input");
+
+        let error: SubmitError = serde_json::from_value(json!({
+            "code": 3129,
+            "message": "Collateral inputs are locked by scripts.",
+            "data": { "unsuitableCollateralInputs": [] }
+        }))
+        .unwrap();
+        assert_eq!(
+            error.remediation(),
+            "replace script-locked collateral with key-locked inputs"
+        );
+
+        let error: SubmitError = serde_json::from_value(json!({
+            "code": 3999,
+            "message": "Something new."
+        }))
+        .unwrap();
+        assert_eq!(error.remediation(), "see the error message and data");
+    }
+
+    #[test]
+    fn unexpected_mempool_error_detail() {
+        let json = json!({
+            "code": 3997,
+            "message": "Unexpected mempool error; the node returned an error we don't know how to handle.",
+            "data": {
+                "error": "MempoolTxTooSlow: the transaction took too long to validate"
+            }
+        });
+        let error: SubmitError = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            error.mempool_error_detail().as_deref(),
+            Some("MempoolTxTooSlow: the transaction took too long to validate")
+        );
+        let SubmitError::UnexpectedMempoolError { data, .. } = &error else {
+            panic!("Expected UnexpectedMempoolError, got {:?}", error);
+        };
+        assert!(data["error"].is_string());
+
+        let nested = SubmitError::UnexpectedMempoolError {
+            message: String::new(),
+            data: json!({ "error": { "reason": "mempool is full" } }),
+        };
+        assert_eq!(
+            nested.mempool_error_detail().as_deref(),
+            Some("mempool is full")
+        );
+        let opaque = SubmitError::UnexpectedMempoolError {
+            message: String::new(),
+            data: json!([1, 2]),
+        };
+        assert_eq!(opaque.mempool_error_detail(), None);
+        assert_eq!(
+            SubmitError::EmptyTreasuryWithdrawal {
+                message: String::new()
+            }
+            .mempool_error_detail(),
+            None
+        );
+    }
+
+    fn deserialization_error() -> Value {
+        json!({
+            "code": -32602,
+            "message": "Invalid transaction; It looks like the given transaction wasn't well-formed.",
+            "data": {
+                "byron": "invalid or incomplete value of type 'Transaction': DeserialiseFailure 0 \"expected list len\"",
+                "shelley": "invalid or incomplete value of type 'Transaction': DeserialiseFailure 5 \"Size mismatch\"",
+                "allegra": "invalid or incomplete value of type 'Transaction': DeserialiseFailure 5 \"Size mismatch\"",
+                "mary": "invalid or incomplete value of type 'Transaction': DeserialiseFailure 5 \"Size mismatch\"",
+                "alonzo": "invalid or incomplete value of type 'Transaction': DeserialiseFailure 41 \"unknown field 18\"",
+                "babbage": "invalid or incomplete value of type 'Transaction': DeserialiseFailure 41 \"unknown field 18\"",
+                "conway": "invalid or incomplete value of type 'Transaction': DeserialiseFailure 212 \"expected word\""
+            }
+        })
+    }
+
+    #[test]
+    fn deserialization_failure_of_hinted_era() {
+        let error: SubmitError = serde_json::from_value(deserialization_error()).unwrap();
+
+        let failure = error.deserialization_failure(Some(&Era::Conway)).unwrap();
+        assert_eq!(failure.era, Era::Conway);
+        assert_eq!(
+            failure.to_string(),
+            "Conway decoder: invalid or incomplete value of type 'Transaction': DeserialiseFailure 212 \"expected word\""
+        );
+        let failure = error.deserialization_failure(Some(&Era::Mary)).unwrap();
+        assert!(failure.message.contains("Size mismatch"));
+    }
+
+    #[test]
+    fn deserialization_failure_furthest_decoder() {
+        let mut json = deserialization_error();
+        let error: SubmitError = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(
+            error.deserialization_failure(None).unwrap().era,
+            Era::Conway
+        );
+
+        // Without offsets the latest era wins
+        for era in [
+            "byron", "shelley", "allegra", "mary", "alonzo", "babbage", "conway",
+        ] {
+            json["data"][era] = json!("invalid or incomplete value of type 'Transaction'");
+        }
+        json["data"]["alonzo"] = json!("DeserialiseFailure 3 \"expected bytes\"");
+        let error: SubmitError = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            error.deserialization_failure(None).unwrap().era,
+            Era::Alonzo
+        );
+
+        let error: SubmitError = serde_json::from_value(json!({
+            "code": 3168,
+            "message": "Empty treasury withdrawal"
+        }))
+        .unwrap();
+        assert_eq!(error.deserialization_failure(None), None);
+    }
+}
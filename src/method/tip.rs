@@ -1,3 +1,6 @@
+use std::fmt;
+use std::str::FromStr;
+
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::codec::*;
@@ -58,6 +61,16 @@ impl<'de> Deserialize<'de> for Tip {
     }
 }
 
+impl Tip {
+    /// Slot of the point, `None` for the origin
+    pub fn slot(&self) -> Option<SlotNo> {
+        match self {
+            Tip::Point { slot, .. } => Some(SlotNo(*slot)),
+            Tip::Origin => None,
+        }
+    }
+}
+
 impl PartialOrd for Tip {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         match (self, other) {
@@ -68,8 +81,56 @@ impl PartialOrd for Tip {
     }
 }
 
+/// Formats as `origin` or `<slot>.<hash>`, the shorthand accepted by [`Tip::from_str`]
+impl fmt::Display for Tip {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Tip::Point { slot, id } => write!(f, "{}.{}", slot, id),
+            Tip::Origin => f.write_str("origin"),
+        }
+    }
+}
+
+/// A point given as something other than `origin` or `<slot>.<hash>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePointError(pub String);
+
+impl fmt::Display for ParsePointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid point '{}', expected 'origin' or '<slot>.<hash>'",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParsePointError {}
+
+/// Parses `origin` or `<slot>.<hash>`, with the header hash hex-encoded, e.g. for chain-sync
+/// start points given on the command line
+impl FromStr for Tip {
+    type Err = ParsePointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "origin" {
+            return Ok(Tip::Origin);
+        }
+        let error = || ParsePointError(s.to_string());
+        let (slot, id) = s.split_once('.').ok_or_else(error)?;
+        let slot = slot.parse().map_err(|_| error())?;
+        if id.len() != 64 || !id.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(error());
+        }
+        Ok(Tip::Point {
+            slot,
+            id: id.to_string(),
+        })
+    }
+}
+
 define_ogmios_error! {
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq)]
     pub enum TipError {
         2001 => EraMismatch {
             query_era: Era,
@@ -134,4 +195,44 @@ mod tests {
         let json = serde_json::to_value(tip).unwrap();
         assert_eq!(json, json!("origin"));
     }
+
+    #[test]
+    fn point_shorthand_round_trip() {
+        let id = "1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
+        let shorthand = format!("4492800.{}", id);
+        let point: Tip = shorthand.parse().unwrap();
+        assert_eq!(
+            point,
+            Tip::Point {
+                slot: 4492800,
+                id: id.to_string(),
+            }
+        );
+        assert_eq!(point.to_string(), shorthand);
+        assert_eq!(point.slot(), Some(SlotNo(4492800)));
+
+        let origin: Tip = "origin".parse().unwrap();
+        assert_eq!(origin, Tip::Origin);
+        assert_eq!(origin.to_string(), "origin");
+        assert_eq!(origin.slot(), None);
+    }
+
+    #[test]
+    fn point_shorthand_rejects_malformed_input() {
+        for input in [
+            "",
+            "Origin",
+            "4492800",
+            "slot.1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef",
+            "4492800.1234",
+            "4492800.zz34567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef",
+        ] {
+            assert_eq!(
+                input.parse::<Tip>(),
+                Err(ParsePointError(input.to_string())),
+                "{}",
+                input
+            );
+        }
+    }
 }
@@ -1,6 +1,13 @@
+use std::collections::HashMap;
+
+use num::BigInt;
 use serde::{Deserialize, Serialize};
 
-use crate::codec::{Balance, Era, RpcRequest, RpcResponse, Script, TxOutputPointer, TxPointer};
+use crate::codec::cbor::Encoder;
+use crate::codec::{
+    Address, AssetName, Balance, Era, PolicyId, RpcRequest, RpcResponse, Script, TxOutputPointer,
+    TxPointer,
+};
 use crate::define_ogmios_error;
 
 #[derive(Debug, Clone, Serialize)]
@@ -18,7 +25,7 @@ pub enum UtxoRequestParams {
 }
 pub type UtxoRequest = RpcRequest<UtxoRequestParams>;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Utxo {
     pub transaction: TxPointer,
@@ -26,17 +33,160 @@ pub struct Utxo {
     /// A Cardano address (either legacy format or new format)
     /// New: `addr1q9d34spgg2kdy47n82e7x9pdd6vql6d2engxmpj20jmhuc2047yqd4xnh7u6u5jp4t0q3fkxzckph4tgnzvamlu7k5psuahzcp`
     /// Legacy: `DdzFFzCqrht8mbSTZHqpM2u4HeND2mdspsaBhdQ1BowPJBMzbDeBMeKgqdoKqo1D4sdPusEdZJVrFJRBBxX1jUEofNDYCJSZLg8MkyCE`
-    pub address: String,
+    pub address: Address,
     pub value: Balance,
     /// A Blake2b 32-byte hash digest, hex-encoded
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub datum_hash: Option<String>,
     /// A hex-encoded CBOR value
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub datum: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub script: Option<Script>,
 }
 
+impl Utxo {
+    /// Decodes the inline datum's CBOR, if any
+    pub fn datum_bytes(&self) -> Option<Result<Vec<u8>, hex::FromHexError>> {
+        self.datum.as_ref().map(hex::decode)
+    }
+
+    /// Decodes the reference script's CBOR, if any. Native scripts carrying no CBOR yield `None`.
+    pub fn script_bytes(&self) -> Option<Result<Vec<u8>, hex::FromHexError>> {
+        self.script.as_ref().and_then(Script::cbor).map(hex::decode)
+    }
+
+    /// Encodes the UTxO as the `[input, output]` pair of a `TransactionUnspentOutput`, as
+    /// consumed by cardano-serialization-lib and cardano-multiplatform-lib. The output uses the
+    /// post-Alonzo map format.
+    pub fn to_input_cbor(&self) -> Result<Vec<u8>, UtxoEncodingError> {
+        let tx_id = decode_hex("transaction id", &self.transaction.id)?;
+        let address = self
+            .address
+            .to_bytes()
+            .map_err(|e| UtxoEncodingError(format!("invalid address: {}", e)))?;
+
+        let mut encoder = Encoder::new();
+        encoder.array(2);
+        encoder.array(2).bytes(&tx_id).unsigned(self.index as u64);
+
+        let fields = 2
+            + (self.datum_hash.is_some() || self.datum.is_some()) as u64
+            + self.script.is_some() as u64;
+        encoder.map(fields);
+        encoder.unsigned(0).bytes(&address);
+        encoder.unsigned(1);
+        encode_value(&mut encoder, &self.value)?;
+        if let Some(datum) = &self.datum {
+            let datum = decode_hex("datum", datum)?;
+            encoder
+                .unsigned(2)
+                .array(2)
+                .unsigned(1)
+                .tag(24)
+                .bytes(&datum);
+        } else if let Some(hash) = &self.datum_hash {
+            let hash = decode_hex("datum hash", hash)?;
+            encoder.unsigned(2).array(2).unsigned(0).bytes(&hash);
+        }
+        if let Some(script) = &self.script {
+            let (language, cbor) = match script {
+                Script::Native { cbor, .. } => (
+                    0,
+                    cbor.as_ref().ok_or_else(|| {
+                        UtxoEncodingError("native script without CBOR".to_string())
+                    })?,
+                ),
+                Script::PlutusV1 { cbor } => (1, cbor),
+                Script::PlutusV2 { cbor } => (2, cbor),
+                Script::PlutusV3 { cbor } => (3, cbor),
+            };
+            let cbor = decode_hex("script", cbor)?;
+            // Native scripts are embedded as is, Plutus scripts as byte strings
+            let mut script_ref = Encoder::new();
+            script_ref.array(2).unsigned(language);
+            if language == 0 {
+                script_ref.raw(&cbor);
+            } else {
+                script_ref.bytes(&cbor);
+            }
+            encoder.unsigned(3).tag(24).bytes(&script_ref.into_bytes());
+        }
+        Ok(encoder.into_bytes())
+    }
+}
+
+/// Returned when a [`Utxo`] can't be encoded to CBOR, because one of its fields is malformed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UtxoEncodingError(pub String);
+
+impl std::fmt::Display for UtxoEncodingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cannot encode UTxO: {}", self.0)
+    }
+}
+
+impl std::error::Error for UtxoEncodingError {}
+
+fn decode_hex(field: &str, value: &str) -> Result<Vec<u8>, UtxoEncodingError> {
+    hex::decode(value).map_err(|e| UtxoEncodingError(format!("invalid {}: {}", field, e)))
+}
+
+/// Lovelace alone for ADA-only values, `[lovelace, multiasset]` otherwise. Keys are sorted
+/// canonically, shorter first, so the encoding doesn't depend on map iteration order.
+fn encode_value(encoder: &mut Encoder, value: &Balance) -> Result<(), UtxoEncodingError> {
+    let canonical = |a: &&String, b: &&String| (a.len(), a).cmp(&(b.len(), b));
+    let mut policies: Vec<_> = value.assets.policies().collect();
+    policies.sort_by(canonical);
+    if policies.is_empty() {
+        encoder.unsigned(value.lovelace);
+        return Ok(());
+    }
+    encoder.array(2).unsigned(value.lovelace);
+    encoder.map(policies.len() as u64);
+    for policy_id in policies {
+        let tokens = &value.assets[policy_id];
+        let mut asset_names: Vec<_> = tokens.keys().collect();
+        asset_names.sort_by(canonical);
+        encoder.bytes(&decode_hex("policy id", policy_id)?);
+        encoder.map(tokens.len() as u64);
+        for asset_name in asset_names {
+            let quantity = &tokens[asset_name];
+            let quantity = u64::try_from(quantity).map_err(|_| {
+                UtxoEncodingError(format!(
+                    "quantity of {}.{} is not a u64",
+                    policy_id, asset_name
+                ))
+            })?;
+            encoder.bytes(&decode_hex("asset name", asset_name)?);
+            encoder.unsigned(quantity);
+        }
+    }
+    Ok(())
+}
+
+/// Sums the quantity of each asset across a set of UTxOs, keyed by `(policy id, asset name)`.
+/// When `include_ada` is set, lovelace is included under `("ada", "lovelace")`.
+pub fn asset_totals(utxos: &[Utxo], include_ada: bool) -> HashMap<(PolicyId, AssetName), BigInt> {
+    let mut totals: HashMap<(PolicyId, AssetName), BigInt> = HashMap::new();
+    let mut add = |policy_id: &str, asset_name: &str, quantity: BigInt| {
+        *totals
+            .entry((policy_id.to_string(), asset_name.to_string()))
+            .or_default() += quantity;
+    };
+    for utxo in utxos {
+        if include_ada {
+            add("ada", "lovelace", utxo.value.lovelace.into());
+        }
+        for (policy_id, asset_name, quantity) in utxo.value.assets.flat_iter() {
+            add(policy_id, asset_name, quantity.clone());
+        }
+    }
+    totals
+}
+
 define_ogmios_error! {
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq)]
     pub enum UtxoError {
         2001 => EraMismatch {
             query_era: Era,
@@ -51,7 +201,7 @@ define_ogmios_error! {
 pub type UtxoResponse = RpcResponse<Vec<Utxo>, UtxoError>;
 
 define_ogmios_error! {
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq)]
     pub enum ProtocolParamsError {
         2001 => EraMismatch {
             query_era: Era,
@@ -62,3 +212,223 @@ define_ogmios_error! {
         _ => Unknown { error: Value }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::codec::cbor::Decoder;
+
+    const POLICY: &str = "b0d07d45fe9514f80213f4020e5a61241458be626841cde717cb38a7";
+    const TOKEN: &str = "6e7574636f696e";
+
+    fn utxo(index: u32, value: serde_json::Value) -> Utxo {
+        serde_json::from_value(json!({
+            "transaction": { "id": "3a2d4d3b0c1f6f2e4b9a8c7d6e5f4a3b2c1d0e9f8a7b6c5d4e3f2a1b0c9d8e7f" },
+            "index": index,
+            "address": "addr_test1vz09v9yfxguvlp0zsnrpa3tdtm7el8xufp3m5lsm7qxzclgmzkket",
+            "value": value
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn asset_totals_across_utxos() {
+        let utxos = vec![
+            utxo(
+                0,
+                json!({ "ada": { "lovelace": 1_500_000 }, POLICY: { TOKEN: 10 } }),
+            ),
+            utxo(
+                1,
+                json!({ "ada": { "lovelace": 2_000_000 }, POLICY: { TOKEN: 25 } }),
+            ),
+            utxo(
+                2,
+                json!({ "ada": { "lovelace": 1_000_000 }, POLICY: { TOKEN: 7, "74657374": 1 } }),
+            ),
+        ];
+
+        let totals = asset_totals(&utxos, false);
+        assert_eq!(totals.len(), 2);
+        assert_eq!(
+            totals[&(POLICY.to_string(), TOKEN.to_string())],
+            BigInt::from(42)
+        );
+        assert_eq!(
+            totals[&(POLICY.to_string(), "74657374".to_string())],
+            BigInt::from(1)
+        );
+
+        let totals = asset_totals(&utxos, true);
+        assert_eq!(totals.len(), 3);
+        assert_eq!(
+            totals[&("ada".to_string(), "lovelace".to_string())],
+            BigInt::from(4_500_000)
+        );
+    }
+
+    #[test]
+    fn inline_datum_bytes() {
+        let mut utxo = utxo(0, json!({ "ada": { "lovelace": 2_000_000 } }));
+        assert!(utxo.datum_bytes().is_none());
+        assert!(utxo.script_bytes().is_none());
+
+        utxo.datum = Some("d87980".to_string());
+        assert_eq!(utxo.datum_bytes().unwrap().unwrap(), vec![0xd8, 0x79, 0x80]);
+
+        utxo.datum = Some("d8798".to_string());
+        assert!(utxo.datum_bytes().unwrap().is_err());
+    }
+
+    #[test]
+    fn reference_script_bytes() {
+        let mut utxo = utxo(0, json!({ "ada": { "lovelace": 2_000_000 } }));
+        utxo.script = Some(Script::PlutusV2 {
+            cbor: "4e4d01000033222220051200120011".to_string(),
+        });
+        assert_eq!(
+            utxo.script_bytes().unwrap().unwrap(),
+            hex::decode("4e4d01000033222220051200120011").unwrap()
+        );
+
+        utxo.script = serde_json::from_value(json!({
+            "language": "native",
+            "json": { "clause": "before", "slot": 42 },
+            "cbor": null
+        }))
+        .unwrap();
+        assert!(utxo.script_bytes().is_none());
+    }
+
+    #[test]
+    fn datum_and_script_layouts() {
+        let response: UtxoResponse = serde_json::from_str(include_str!(
+            "../../tests/fixtures/queryLedgerState/utxo.json"
+        ))
+        .unwrap();
+        let utxos = Result::from(response).unwrap();
+        assert_eq!(utxos.len(), 4);
+
+        let plain = &utxos[0];
+        assert_eq!(plain.datum_hash, None);
+        assert_eq!(plain.datum, None);
+        assert_eq!(plain.script, None);
+
+        let hash_only = &utxos[1];
+        assert_eq!(
+            hash_only.datum_hash.as_deref(),
+            Some("923918e403bf43c34b4ef6b48eb2ee04babed17320d8d1b9ff9ad086e86f44ec")
+        );
+        assert_eq!(hash_only.datum, None);
+        assert_eq!(hash_only.script, None);
+
+        let inline = &utxos[2];
+        assert_eq!(inline.datum_hash, None);
+        assert_eq!(inline.datum.as_deref(), Some("d8799f4568656c6c6fff"));
+        assert_eq!(inline.script, None);
+
+        let reference = &utxos[3];
+        assert_eq!(reference.datum_hash, None);
+        assert_eq!(reference.datum, None);
+        assert_eq!(
+            reference.script,
+            Some(Script::PlutusV2 {
+                cbor: "4e4d01000033222220051200120011".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn decoded_utxos_compare_equal() {
+        let decode = || {
+            let response: UtxoResponse = serde_json::from_str(include_str!(
+                "../../tests/fixtures/queryLedgerState/utxo.json"
+            ))
+            .unwrap();
+            Result::from(response).unwrap()
+        };
+        let (utxos, again) = (decode(), decode());
+        assert_eq!(utxos, again);
+        assert_ne!(utxos[2], utxos[3]);
+
+        let mut spent = again[3].clone();
+        spent.value.lovelace += 1;
+        assert_ne!(utxos[3], spent);
+    }
+
+    #[test]
+    fn asset_totals_beyond_u64() {
+        let utxos = vec![
+            utxo(
+                0,
+                json!({ "ada": { "lovelace": u64::MAX }, POLICY: { TOKEN: u64::MAX } }),
+            ),
+            utxo(1, json!({ "ada": { "lovelace": 1 }, POLICY: { TOKEN: 1 } })),
+        ];
+
+        let totals = asset_totals(&utxos, true);
+        let expected = BigInt::from(u64::MAX) + 1;
+        assert_eq!(totals[&(POLICY.to_string(), TOKEN.to_string())], expected);
+        assert_eq!(
+            totals[&("ada".to_string(), "lovelace".to_string())],
+            expected
+        );
+    }
+
+    #[test]
+    fn ada_only_input_cbor() {
+        let utxo = utxo(1, json!({ "ada": { "lovelace": 1_500_000 } }));
+        let cbor = utxo.to_input_cbor().unwrap();
+
+        let address = utxo.address.to_bytes().unwrap();
+        let expected = format!(
+            "82 825820{}01 a2 00581d{} 01 1a0016e360",
+            utxo.transaction.id,
+            hex::encode(&address)
+        );
+        assert_eq!(hex::encode(&cbor), expected.replace(' ', ""));
+    }
+
+    #[test]
+    fn multi_asset_input_cbor_with_inline_datum() {
+        let mut utxo = utxo(
+            0,
+            json!({ "ada": { "lovelace": 2_000_000 }, POLICY: { TOKEN: 10, "74": 1 } }),
+        );
+        utxo.datum = Some("d87980".to_string());
+        let cbor = utxo.to_input_cbor().unwrap();
+
+        let mut decoder = Decoder::new(&cbor);
+        assert_eq!(decoder.array().unwrap(), 2);
+        decoder.skip().unwrap();
+        assert_eq!(decoder.map().unwrap(), 3);
+        assert_eq!(decoder.unsigned().unwrap(), 0);
+        decoder.skip().unwrap();
+        assert_eq!(decoder.unsigned().unwrap(), 1);
+        assert_eq!(decoder.array().unwrap(), 2);
+        assert_eq!(decoder.unsigned().unwrap(), 2_000_000);
+        assert_eq!(decoder.map().unwrap(), 1);
+        assert_eq!(hex::encode(decoder.bytes().unwrap()), POLICY);
+        assert_eq!(decoder.map().unwrap(), 2);
+        // Shorter asset names first
+        assert_eq!(decoder.bytes().unwrap(), b"t");
+        assert_eq!(decoder.unsigned().unwrap(), 1);
+        assert_eq!(hex::encode(decoder.bytes().unwrap()), TOKEN);
+        assert_eq!(decoder.unsigned().unwrap(), 10);
+        assert_eq!(decoder.unsigned().unwrap(), 2);
+        assert_eq!(decoder.array().unwrap(), 2);
+        assert_eq!(decoder.unsigned().unwrap(), 1);
+        assert_eq!(decoder.tag().unwrap(), 24);
+        assert_eq!(hex::encode(decoder.bytes().unwrap()), "d87980");
+        assert!(decoder.is_empty());
+    }
+
+    #[test]
+    fn input_cbor_rejects_malformed_fields() {
+        let mut utxo = utxo(0, json!({ "ada": { "lovelace": 1_000_000 } }));
+        utxo.datum_hash = Some("not hex".to_string());
+        assert!(utxo.to_input_cbor().is_err());
+    }
+}
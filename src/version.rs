@@ -0,0 +1,72 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Version of the Ogmios server, as reported by its `/health` endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ServerVersion {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl ServerVersion {
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+impl fmt::Display for ServerVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// A version string not of the form `v<major>.<minor>.<patch>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseVersionError(pub String);
+
+impl fmt::Display for ParseVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid server version '{}'", self.0)
+    }
+}
+
+impl std::error::Error for ParseVersionError {}
+
+/// Parses versions as reported by `/health`, e.g. `v6.11.2 (c6a4b2d9)`. The leading `v`,
+/// the patch number and anything after the version number are optional.
+impl FromStr for ServerVersion {
+    type Err = ParseVersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseVersionError(s.to_string());
+        let version = s.split_whitespace().next().ok_or_else(error)?;
+        let version = version.strip_prefix('v').unwrap_or(version);
+        let version = version.split(['-', '+']).next().unwrap_or(version);
+        let mut parts = version.split('.').map(|part| part.parse::<u64>());
+        let major = parts.next().ok_or_else(error)?.map_err(|_| error())?;
+        let minor = parts.next().unwrap_or(Ok(0)).map_err(|_| error())?;
+        let patch = parts.next().unwrap_or(Ok(0)).map_err(|_| error())?;
+        Ok(Self::new(major, minor, patch))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_health_versions() {
+        assert_eq!(
+            "v6.11.2 (c6a4b2d9)".parse(),
+            Ok(ServerVersion::new(6, 11, 2))
+        );
+        assert_eq!("5.6".parse(), Ok(ServerVersion::new(5, 6, 0)));
+        assert_eq!("v6.0.0-rc1".parse(), Ok(ServerVersion::new(6, 0, 0)));
+        assert!("nightly".parse::<ServerVersion>().is_err());
+    }
+}
@@ -1,43 +1,357 @@
+use std::collections::HashSet;
 use std::fmt;
+use std::time::Duration;
 
 use anyhow::{Context, bail};
 use futures_util::stream::{SplitSink, SplitStream};
-use futures_util::{SinkExt, StreamExt};
+use futures_util::{SinkExt, Stream, StreamExt, TryStreamExt};
 pub use reqwest::Url;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
-use tokio::net::TcpStream;
+use tokio::net::{TcpStream, lookup_host};
+use tokio_tungstenite::tungstenite;
+use tokio_tungstenite::tungstenite::error::UrlError;
 use tokio_tungstenite::tungstenite::protocol::Message;
-use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async};
+pub use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, client_async_with_config};
 
-use crate::codec::{Id, RpcRequest, RpcResponseIdentifier};
-use crate::method::mempool::{AcquireMempoolResult, NextTransactionResponse};
+use crate::codec::{Id, IdGenerator, RpcError, RpcRequest, RpcResponse, RpcResponseIdentifier};
+use crate::error::DeserializeErrorHook;
+use crate::method::chain_sync::{
+    FindIntersectionParams, FindIntersectionResponse, NextBlockResponse, Point,
+};
+use crate::method::epoch::EpochResponse;
+use crate::method::mempool::{
+    AcquireMempoolResponse, AcquireMempoolResult, HasTransactionParams, HasTransactionResponse,
+    HasTransactionResult, MempoolError, MempoolSize, MempoolTransaction, NextTransactionOptions,
+    NextTransactionResponse, NextTransactionResult, ReleaseMempoolResponse, ReleaseMempoolResult,
+    SizeOfMempoolResponse,
+};
 use crate::method::rewards::{RewardAccountSummariesParams, RewardAccountSummariesResponse};
 
+/// Returned when Ogmios closes the connection while a response is awaited
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionClosed {
+    /// Method and id of the response that was awaited
+    pub awaiting: RpcResponseIdentifier,
+}
+
+impl fmt::Display for ConnectionClosed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Connection closed while awaiting {}", self.awaiting)
+    }
+}
+
+impl std::error::Error for ConnectionClosed {}
+
+/// Returned when a response isn't received within the client's request timeout, see
+/// [`OgmiosWsClient::request_timeout`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestTimeout {
+    /// Method and id of the response that was awaited
+    pub awaiting: RpcResponseIdentifier,
+    pub timeout: Duration,
+}
+
+impl fmt::Display for RequestTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "No response within {:?} while awaiting {}",
+            self.timeout, self.awaiting
+        )
+    }
+}
+
+impl std::error::Error for RequestTimeout {}
+
+/// Methods [`OgmiosWsClient::auto_reconnect`] doesn't resend: session-bound chain-sync and
+/// mempool monitoring, and non-idempotent submission
+const NOT_RESENT_METHODS: &[&str] = &[
+    "findIntersection",
+    "nextBlock",
+    "acquireMempool",
+    "nextTransaction",
+    "hasTransaction",
+    "sizeOfMempool",
+    "releaseMempool",
+    "submitTransaction",
+];
+
+/// Whether `error` comes from the connection being closed or failing, as opposed to an error
+/// answered by Ogmios or a protocol violation such as an oversized message. A TCP reset and
+/// writing to a connection the server closed surface as protocol errors, and are counted.
+pub(crate) fn is_connection_lost(error: &anyhow::Error) -> bool {
+    error.downcast_ref::<ConnectionClosed>().is_some()
+        || matches!(
+            error.downcast_ref::<tungstenite::Error>(),
+            Some(
+                tungstenite::Error::ConnectionClosed
+                    | tungstenite::Error::AlreadyClosed
+                    | tungstenite::Error::Io(_)
+                    | tungstenite::Error::Protocol(
+                        tungstenite::error::ProtocolError::ResetWithoutClosingHandshake
+                            | tungstenite::error::ProtocolError::SendAfterClosing
+                    )
+            )
+        )
+}
+
+/// Returned when a connection to Ogmios can't be established, by the step that failed
+#[derive(Debug)]
+pub enum ConnectError {
+    /// The URL isn't a `ws://` or `wss://` URL with a host
+    InvalidUrl(String),
+    /// The host name couldn't be resolved
+    Dns(std::io::Error),
+    /// No TCP connection could be opened to the resolved addresses
+    Tcp(std::io::Error),
+    /// The TLS session couldn't be established. `wss://` URLs currently always fail with
+    /// this, TLS support isn't compiled in.
+    Tls(tungstenite::Error),
+    /// The server rejected or failed the WebSocket upgrade
+    Handshake(tungstenite::Error),
+}
+
+impl fmt::Display for ConnectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConnectError::InvalidUrl(reason) => write!(f, "Invalid WebSocket URL: {}", reason),
+            ConnectError::Dns(e) => write!(f, "Failed to resolve host: {}", e),
+            ConnectError::Tcp(e) => write!(f, "Failed to open TCP connection: {}", e),
+            ConnectError::Tls(e) => write!(f, "Failed to establish TLS: {}", e),
+            ConnectError::Handshake(e) => write!(f, "WebSocket handshake failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ConnectError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConnectError::InvalidUrl(_) => None,
+            ConnectError::Dns(e) | ConnectError::Tcp(e) => Some(e),
+            ConnectError::Tls(e) | ConnectError::Handshake(e) => Some(e),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct OgmiosWsClient {
+    /// Where to reconnect to, see [`Self::reconnect`]
+    url: Url,
+    config: WebSocketConfig,
     write: SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>,
     read: SplitStream<WebSocketStream<MaybeTlsStream<TcpStream>>>,
     messages: Vec<(RpcResponseIdentifier, String)>,
+    on_deserialize_error: Option<DeserializeErrorHook>,
+    /// Set when a [`MempoolSnapshot`] is dropped without being released, the release is sent
+    /// ahead of the next request
+    pending_mempool_release: bool,
+    /// Set once the mempool is released, until it is acquired again. Mempool queries fail
+    /// locally with [`MempoolError::MustAcquireMempoolFirst`] meanwhile.
+    mempool_released: bool,
+    /// Ids of requests whose responses are discarded instead of buffered, e.g. timed out ones
+    ignored_responses: HashSet<Id>,
+    ids: IdGenerator,
+    require_id_match: bool,
+    request_timeout: Option<Duration>,
+    auto_reconnect: bool,
 }
 
 impl OgmiosWsClient {
-    pub async fn connect(url: Url) -> anyhow::Result<Self> {
-        let (ws_stream, _) = connect_async(url.to_string()).await?;
+    /// Largest message (and frame) accepted by default: big enough for large blocks and UTxO
+    /// dumps, which can exceed tungstenite's own 16 MiB frame limit
+    pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 256 << 20;
+
+    pub fn default_config() -> WebSocketConfig {
+        WebSocketConfig::default()
+            .max_message_size(Some(Self::DEFAULT_MAX_MESSAGE_SIZE))
+            .max_frame_size(Some(Self::DEFAULT_MAX_MESSAGE_SIZE))
+    }
+
+    pub async fn connect(url: Url) -> Result<Self, ConnectError> {
+        Self::connect_with(url, Self::default_config(), IdGenerator::default()).await
+    }
+
+    /// Connects using `ids` to generate request ids, e.g. [`IdGenerator::sequential`] for
+    /// readable logs when pipelining
+    pub async fn connect_with_id_generator(
+        url: Url,
+        ids: IdGenerator,
+    ) -> Result<Self, ConnectError> {
+        Self::connect_with(url, Self::default_config(), ids).await
+    }
+
+    /// Connects with a custom WebSocket configuration, e.g. to change the maximum message size
+    pub async fn connect_with_config(
+        url: Url,
+        config: WebSocketConfig,
+    ) -> Result<Self, ConnectError> {
+        Self::connect_with(url, config, IdGenerator::default()).await
+    }
+
+    async fn connect_with(
+        url: Url,
+        config: WebSocketConfig,
+        ids: IdGenerator,
+    ) -> Result<Self, ConnectError> {
+        let ws_stream = Self::open(&url, config).await?;
         let (write, read) = ws_stream.split();
         Ok(Self {
+            url,
+            config,
             write,
             read,
             messages: vec![],
+            on_deserialize_error: None,
+            pending_mempool_release: false,
+            mempool_released: false,
+            ignored_responses: HashSet::new(),
+            ids,
+            require_id_match: true,
+            request_timeout: None,
+            auto_reconnect: false,
         })
     }
 
+    async fn open(
+        url: &Url,
+        config: WebSocketConfig,
+    ) -> Result<WebSocketStream<MaybeTlsStream<TcpStream>>, ConnectError> {
+        if !matches!(url.scheme(), "ws" | "wss") {
+            return Err(ConnectError::InvalidUrl(format!(
+                "unsupported scheme '{}'",
+                url.scheme()
+            )));
+        }
+        // The WebSocket stream is built without TLS support
+        if url.scheme() == "wss" {
+            return Err(ConnectError::Tls(tungstenite::Error::Url(
+                UrlError::TlsFeatureNotEnabled,
+            )));
+        }
+        let host = url
+            .host_str()
+            .ok_or_else(|| ConnectError::InvalidUrl("no host".to_string()))?;
+        let port = url
+            .port_or_known_default()
+            .ok_or_else(|| ConnectError::InvalidUrl("no port".to_string()))?;
+        // Bracketed IPv6 hosts don't resolve as is
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+
+        let addrs: Vec<_> = lookup_host((host, port))
+            .await
+            .map_err(ConnectError::Dns)?
+            .collect();
+        let stream = TcpStream::connect(&addrs[..])
+            .await
+            .map_err(ConnectError::Tcp)?;
+        let (ws_stream, _) =
+            client_async_with_config(url.as_str(), MaybeTlsStream::Plain(stream), Some(config))
+                .await
+                .map_err(ConnectError::Handshake)?;
+        Ok(ws_stream)
+    }
+
+    /// Replaces the connection with a new one to the same URL, e.g. after Ogmios restarted.
+    /// Responses already buffered are kept, but the session state held by Ogmios is lost:
+    /// chain-sync must find an intersection again and the mempool must be re-acquired.
+    pub async fn reconnect(&mut self) -> Result<(), ConnectError> {
+        let ws_stream = Self::open(&self.url, self.config).await?;
+        (self.write, self.read) = ws_stream.split();
+        self.pending_mempool_release = false;
+        self.mempool_released = false;
+        self.ignored_responses.clear();
+        Ok(())
+    }
+
+    /// Called with `(method, raw_body, error)` whenever a response fails to deserialize
+    pub fn on_deserialize_error(
+        &mut self,
+        hook: impl Fn(&str, &str, &serde_json::Error) + Send + Sync + 'static,
+    ) {
+        self.on_deserialize_error = Some(DeserializeErrorHook::new(hook));
+    }
+
+    /// Whether responses must carry the id of their request. Enabled by default, which keeps
+    /// concurrent and pipelined requests apart. When disabled, e.g. behind a proxy stripping
+    /// ids, a response without id is taken as the answer to the oldest pending request for the
+    /// same method, which is only reliable with one request per method in flight.
+    pub fn require_id_match(&mut self, require: bool) {
+        self.require_id_match = require;
+    }
+
+    /// Fail reads not answered within `timeout` with [`RequestTimeout`]. A late response is
+    /// discarded when it arrives rather than buffered. No timeout is applied by default.
+    pub fn request_timeout(&mut self, timeout: Option<Duration>) {
+        self.request_timeout = timeout;
+    }
+
+    /// Reconnect once and resend when [`Self::request`] finds the connection lost, see
+    /// [`Self::reconnect`]. Chain-sync and mempool monitoring requests depend on session state
+    /// lost along with the connection, and a submission may have gone through before it was
+    /// lost, so those fail with the connection error instead of being resent. When reconnecting
+    /// fails, its [`ConnectError`] is returned with the connection error as context. Disabled
+    /// by default.
+    pub fn auto_reconnect(&mut self, enabled: bool) {
+        self.auto_reconnect = enabled;
+    }
+
+    /// Whether `received` answers the request identified by `expected`
+    fn is_response_to(
+        &self,
+        received: &RpcResponseIdentifier,
+        expected: &RpcResponseIdentifier,
+    ) -> bool {
+        received == expected
+            || (!self.require_id_match
+                && received.id.is_none()
+                && received.method == expected.method)
+    }
+
+    fn deserialize<T: DeserializeOwned>(
+        &self,
+        awaiting: &RpcResponseIdentifier,
+        text: &str,
+    ) -> anyhow::Result<T> {
+        let res = serde_json::from_str(text);
+        if let (Err(e), Some(hook)) = (&res, &self.on_deserialize_error) {
+            hook.call(&awaiting.method, text, e);
+        }
+        res.with_context(|| format!("failed to deserialize response to {}", awaiting))
+    }
+
     pub async fn request<T: Serialize + fmt::Debug, U: DeserializeOwned>(
         &mut self,
         method: &str,
         params: Option<T>,
     ) -> anyhow::Result<U> {
-        let id = self.send_request(method, params).await?;
+        let result = self.send_and_read(method, &params).await;
+        match result {
+            Err(e)
+                if self.auto_reconnect
+                    && !NOT_RESENT_METHODS.contains(&method)
+                    && is_connection_lost(&e) =>
+            {
+                tracing::warn!("Connection lost during {}, reconnecting: {:#}", method, e);
+                if let Err(reconnect) = self.reconnect().await {
+                    return Err(anyhow::Error::new(reconnect).context(format!(
+                        "Failed to reconnect after the connection was lost: {:#}",
+                        e
+                    )));
+                }
+                self.send_and_read(method, &params).await
+            }
+            result => result,
+        }
+    }
+
+    async fn send_and_read<T: Serialize + fmt::Debug, U: DeserializeOwned>(
+        &mut self,
+        method: &str,
+        params: &Option<T>,
+    ) -> anyhow::Result<U> {
+        let id = self.send_request(method, params.as_ref()).await?;
         self.read_response(method, id).await
     }
 
@@ -45,12 +359,26 @@ impl OgmiosWsClient {
         &mut self,
         method: &str,
         params: Option<T>,
+    ) -> anyhow::Result<Id> {
+        if self.pending_mempool_release {
+            self.pending_mempool_release = false;
+            self.mempool_released = true;
+            let id = self.send("releaseMempool", None::<()>).await?;
+            self.ignored_responses.insert(id);
+        }
+        self.send(method, params).await
+    }
+
+    async fn send<T: Serialize + fmt::Debug>(
+        &mut self,
+        method: &str,
+        params: Option<T>,
     ) -> anyhow::Result<Id> {
         let params = match params {
             Some(p) => serde_json::to_value(p)?,
             None => serde_json::Value::Object(serde_json::Map::new()),
         };
-        let id = Id::default();
+        let id = self.ids.next_id();
         let req = RpcRequest {
             jsonrpc: "2.0".to_string(),
             method: method.to_string(),
@@ -74,42 +402,198 @@ impl OgmiosWsClient {
             method: method.to_string(),
             id: Some(id),
         };
-        if let Some(msg) = self
+        let buffered = self
             .messages
-            .extract_if(.., |msg| msg.0 == identifier)
-            .next()
-        {
-            let res = serde_json::from_str(&msg.1).context("failed to deserialize")?;
-            return Ok(res);
+            .iter()
+            .position(|msg| self.is_response_to(&msg.0, &identifier));
+        if let Some(position) = buffered {
+            let msg = self.messages.remove(position);
+            return self.deserialize(&identifier, &msg.1);
         }
 
-        // Wait for new messages
-        while let Some(msg) = self.read.next().await.transpose()? {
+        match self.request_timeout {
+            Some(timeout) => {
+                match tokio::time::timeout(timeout, self.await_response(&identifier)).await {
+                    Ok(result) => result,
+                    Err(_) => {
+                        self.ignored_responses.extend(identifier.id.clone());
+                        Err(RequestTimeout {
+                            awaiting: identifier,
+                            timeout,
+                        }
+                        .into())
+                    }
+                }
+            }
+            None => self.await_response(&identifier).await,
+        }
+    }
+
+    /// Reads new messages until the response to `identifier` arrives, buffering the others
+    async fn await_response<T: DeserializeOwned>(
+        &mut self,
+        identifier: &RpcResponseIdentifier,
+    ) -> anyhow::Result<T> {
+        while let Some(msg) = self
+            .read
+            .next()
+            .await
+            .transpose()
+            .with_context(|| format!("failed to read response to {}", identifier))?
+        {
             match msg {
                 Message::Text(text) => {
                     let new_identifier: RpcResponseIdentifier =
-                        serde_json::from_str(&text).context("failed to deserialize")?;
-                    if new_identifier == identifier {
-                        let res: T =
-                            serde_json::from_str(&text).context("failed to deserialize")?;
-                        return Ok(res);
+                        self.deserialize(identifier, &text)?;
+                    if self.is_response_to(&new_identifier, identifier) {
+                        return self.deserialize(identifier, &text);
+                    } else if let Some(id) = &new_identifier.id
+                        && self.ignored_responses.remove(id)
+                    {
+                        continue;
                     } else {
+                        tracing::debug!(
+                            "Buffering response to {} while awaiting {}",
+                            new_identifier,
+                            identifier
+                        );
                         self.messages.push((new_identifier, text.to_string()));
                     }
                 }
-                _ => bail!("Unexpected message type received from ogmios: {:?}", msg),
+                // Fragmented messages are reassembled by tungstenite, which also answers pings
+                Message::Ping(_) | Message::Pong(_) => continue,
+                Message::Close(_) => break,
+                _ => bail!(
+                    "Unexpected message type received from ogmios while awaiting {}: {:?}",
+                    identifier,
+                    msg
+                ),
             }
         }
 
-        bail!("Connection closed")
+        Err(ConnectionClosed {
+            awaiting: identifier.clone(),
+        }
+        .into())
+    }
+
+    /// Removes and returns the responses received but never read, in arrival order, e.g.
+    /// responses to requests whose read was abandoned
+    pub fn drain_buffered(&mut self) -> Vec<(RpcResponseIdentifier, String)> {
+        std::mem::take(&mut self.messages)
+    }
+
+    pub async fn find_intersection(
+        &mut self,
+        points: Vec<Point>,
+    ) -> anyhow::Result<FindIntersectionResponse> {
+        let params = FindIntersectionParams { points };
+        self.request("findIntersection", Some(params)).await
+    }
+
+    pub async fn next_block(&mut self) -> anyhow::Result<NextBlockResponse> {
+        self.request("nextBlock", None::<()>).await
+    }
+
+    /// Pipelines `n` `nextBlock` requests: all requests are sent before any response is read,
+    /// so the node can stream blocks without waiting for a round-trip each time.
+    /// Responses are returned in request order.
+    pub async fn next_blocks(&mut self, n: usize) -> anyhow::Result<Vec<NextBlockResponse>> {
+        let mut ids = Vec::with_capacity(n);
+        for _ in 0..n {
+            ids.push(self.send_request("nextBlock", None::<()>).await?);
+        }
+        let mut responses = Vec::with_capacity(n);
+        for id in ids {
+            responses.push(self.read_response("nextBlock", id).await?);
+        }
+        Ok(responses)
     }
 
     pub async fn acquire_mempool(&mut self) -> anyhow::Result<AcquireMempoolResult> {
-        self.request("acquireMempool", None::<()>).await
+        let response: AcquireMempoolResponse = self.request("acquireMempool", None::<()>).await?;
+        self.mempool_released = false;
+        Ok(response.result)
     }
 
     pub async fn next_mempool_tx(&mut self) -> anyhow::Result<NextTransactionResponse> {
-        self.request("nextTransaction", None::<()>).await
+        self.next_mempool_tx_with_options(NextTransactionOptions::default())
+            .await
+    }
+
+    pub async fn next_mempool_tx_with_options(
+        &mut self,
+        options: NextTransactionOptions,
+    ) -> anyhow::Result<NextTransactionResponse> {
+        if let Some(error) = self.released_mempool_error("nextTransaction") {
+            return Ok(error);
+        }
+        self.request("nextTransaction", options.params()).await
+    }
+
+    /// Whether transaction `tx_id` is in the acquired mempool snapshot. The mempool must be
+    /// acquired first, otherwise this fails with [`MempoolError::MustAcquireMempoolFirst`],
+    /// which can be recovered with `downcast_ref`.
+    pub async fn has_transaction(&mut self, tx_id: &str) -> anyhow::Result<HasTransactionResult> {
+        let response: HasTransactionResponse = match self.released_mempool_error("hasTransaction") {
+            Some(error) => error,
+            None => {
+                let params = HasTransactionParams {
+                    id: tx_id.to_string(),
+                };
+                self.request("hasTransaction", Some(params)).await?
+            }
+        };
+        Ok(Result::from(response)?)
+    }
+
+    /// Capacity and usage of the acquired mempool snapshot. Fails with
+    /// [`MempoolError::MustAcquireMempoolFirst`] unless the mempool is acquired.
+    pub async fn size_of_mempool(&mut self) -> anyhow::Result<MempoolSize> {
+        let response: SizeOfMempoolResponse = match self.released_mempool_error("sizeOfMempool") {
+            Some(error) => error,
+            None => self.request("sizeOfMempool", None::<()>).await?,
+        };
+        Ok(Result::from(response)?)
+    }
+
+    /// Releases the acquired mempool snapshot. Mempool queries fail with
+    /// [`MempoolError::MustAcquireMempoolFirst`] until the mempool is acquired again.
+    pub async fn release_mempool(&mut self) -> anyhow::Result<ReleaseMempoolResult> {
+        let response: ReleaseMempoolResponse = self.request("releaseMempool", None::<()>).await?;
+        self.mempool_released = true;
+        Ok(Result::from(response)?)
+    }
+
+    /// The error Ogmios answers mempool queries with when no snapshot is acquired, returned
+    /// without a round-trip after a release
+    fn released_mempool_error<T>(&self, method: &str) -> Option<RpcResponse<T, MempoolError>> {
+        if !self.mempool_released {
+            return None;
+        }
+        Some(RpcResponse::Error(RpcError {
+            jsonrpc: "2.0".to_string(),
+            method: Some(method.to_string()),
+            error: MempoolError::MustAcquireMempoolFirst {
+                message: "The mempool must be acquired first.".to_string(),
+            },
+            id: None,
+        }))
+    }
+
+    /// Acquires a mempool snapshot, collects all its transactions and releases it. The
+    /// release is attempted even if reading the transactions failed.
+    pub async fn drain_mempool(&mut self) -> anyhow::Result<Vec<MempoolTransaction>> {
+        let mut snapshot = MempoolSnapshot::acquire(self).await?;
+        let transactions = snapshot.transactions().try_collect().await;
+        let released = snapshot.release().await;
+        let transactions = transactions?;
+        released?;
+        Ok(transactions)
+    }
+
+    pub async fn query_epoch(&mut self) -> anyhow::Result<EpochResponse> {
+        self.request("queryLedgerState/epoch", None::<()>).await
     }
 
     pub async fn reward_account_summaries(
@@ -117,8 +601,906 @@ impl OgmiosWsClient {
         keys: Option<Vec<String>>,
         scripts: Option<Vec<String>>,
     ) -> anyhow::Result<RewardAccountSummariesResponse> {
-        let params = RewardAccountSummariesParams { keys, scripts };
+        let params = RewardAccountSummariesParams { keys, scripts }.normalize()?;
         self.request("queryLedgerState/rewardAccountSummaries", Some(params))
             .await
     }
 }
+
+/// A mempool snapshot acquired on an [`OgmiosWsClient`], released when dropped
+///
+/// Dropping can't await, so a dropped snapshot is released right before the client's next
+/// request. Use [`MempoolSnapshot::release`] to release it immediately.
+#[derive(Debug)]
+pub struct MempoolSnapshot<'a> {
+    client: &'a mut OgmiosWsClient,
+    slot: u64,
+    released: bool,
+}
+
+impl<'a> MempoolSnapshot<'a> {
+    pub async fn acquire(client: &'a mut OgmiosWsClient) -> anyhow::Result<Self> {
+        let acquired = client.acquire_mempool().await?;
+        Ok(Self {
+            client,
+            slot: acquired.slot,
+            released: false,
+        })
+    }
+
+    /// Slot at which the snapshot was taken
+    pub fn slot(&self) -> u64 {
+        self.slot
+    }
+
+    /// Streams the transactions of the snapshot, ending after the last one
+    pub fn transactions(&mut self) -> impl Stream<Item = anyhow::Result<MempoolTransaction>> + '_ {
+        self.transactions_with_options(NextTransactionOptions::default())
+    }
+
+    pub fn transactions_with_options(
+        &mut self,
+        options: NextTransactionOptions,
+    ) -> impl Stream<Item = anyhow::Result<MempoolTransaction>> + '_ {
+        futures_util::stream::unfold(Some(&mut *self.client), move |client| {
+            let options = options.clone();
+            async move {
+                let client = client?;
+                let response = match client.next_mempool_tx_with_options(options).await {
+                    Ok(response) => response,
+                    Err(e) => return Some((Err(e), None)),
+                };
+                match Result::from(response) {
+                    Ok(NextTransactionResult {
+                        transaction: Some(transaction),
+                    }) => Some((Ok(transaction), Some(client))),
+                    Ok(NextTransactionResult { transaction: None }) => None,
+                    Err(e) => Some((Err(e.into()), None)),
+                }
+            }
+        })
+    }
+
+    pub async fn size(&mut self) -> anyhow::Result<MempoolSize> {
+        self.client.size_of_mempool().await
+    }
+
+    pub async fn has(&mut self, id: &str) -> anyhow::Result<bool> {
+        self.client.has_transaction(id).await
+    }
+
+    pub async fn release(mut self) -> anyhow::Result<()> {
+        self.released = true;
+        self.client.release_mempool().await?;
+        Ok(())
+    }
+}
+
+impl Drop for MempoolSnapshot<'_> {
+    fn drop(&mut self) {
+        if !self.released {
+            self.client.pending_mempool_release = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::error::InputError;
+    use crate::method::chain_sync::NextBlockResult;
+    use crate::method::mempool::{MempoolTransaction, NextTransactionResult};
+    use crate::method::tip::{Tip, TipResponse};
+    use crate::test_utils::MockWsServer;
+
+    #[test]
+    fn only_closed_and_io_errors_are_connection_loss() {
+        let lost = |e: tungstenite::Error| is_connection_lost(&anyhow::Error::new(e));
+        assert!(lost(tungstenite::Error::ConnectionClosed));
+        assert!(lost(tungstenite::Error::AlreadyClosed));
+        assert!(lost(tungstenite::Error::Io(
+            std::io::ErrorKind::BrokenPipe.into()
+        )));
+        assert!(!lost(tungstenite::Error::Capacity(
+            tungstenite::error::CapacityError::MessageTooLong {
+                size: 2,
+                max_size: 1
+            }
+        )));
+        assert!(lost(tungstenite::Error::Protocol(
+            tungstenite::error::ProtocolError::ResetWithoutClosingHandshake
+        )));
+        assert!(lost(tungstenite::Error::Protocol(
+            tungstenite::error::ProtocolError::SendAfterClosing
+        )));
+        assert!(!lost(tungstenite::Error::Protocol(
+            tungstenite::error::ProtocolError::UnmaskedFrameFromClient
+        )));
+        assert!(!lost(tungstenite::Error::Utf8(String::new())));
+        assert!(is_connection_lost(
+            &anyhow::Error::new(tungstenite::Error::ConnectionClosed).context("reading")
+        ));
+    }
+
+    #[tokio::test]
+    async fn deserialize_error_hook_receives_raw_body() {
+        let server =
+            MockWsServer::rpc(|_, _| json!({ "result": { "slot": "not a number" } })).await;
+        let mut client = OgmiosWsClient::connect(server.url()).await.unwrap();
+        let captured = Arc::new(Mutex::new(vec![]));
+        {
+            let captured = captured.clone();
+            client.on_deserialize_error(move |method, raw_body, _| {
+                captured
+                    .lock()
+                    .unwrap()
+                    .push((method.to_string(), raw_body.to_string()));
+            });
+        }
+
+        let result = client
+            .request::<(), TipResponse>("queryLedgerState/tip", None)
+            .await;
+
+        assert!(result.is_err());
+        let captured = captured.lock().unwrap();
+        assert_eq!(captured.len(), 1);
+        assert_eq!(captured[0].0, "queryLedgerState/tip");
+        assert!(captured[0].1.contains("not a number"));
+    }
+
+    #[tokio::test]
+    async fn reward_account_summaries_sends_hex_credentials() {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let server = {
+            let requests = requests.clone();
+            MockWsServer::rpc(move |_, params| {
+                requests.lock().unwrap().push(params.clone());
+                json!({ "result": {} })
+            })
+            .await
+        };
+        let mut client = OgmiosWsClient::connect(server.url()).await.unwrap();
+
+        client
+            .reward_account_summaries(
+                Some(vec![
+                    "stake178phkx6acpnf78fuvxn0mkew3l0fd058hzquvz7w36x4gtcccycj5".to_string(),
+                ]),
+                None,
+            )
+            .await
+            .unwrap();
+        let error = client
+            .reward_account_summaries(Some(vec!["key1".to_string()]), None)
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            *requests.lock().unwrap(),
+            [json!({ "scripts": ["c37b1b5dc0669f1d3c61a6fddb2e8fde96be87b881c60bce8e8d542f"] })]
+        );
+        assert!(matches!(
+            error.downcast_ref::<InputError>(),
+            Some(InputError::InvalidCredential(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn next_mempool_tx_includes_cbor() {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let server = {
+            let requests = requests.clone();
+            MockWsServer::rpc(move |method, params| {
+                requests
+                    .lock()
+                    .unwrap()
+                    .push((method.to_string(), params.clone()));
+                json!({
+                    "result": {
+                        "transaction": {
+                            "id": "e8a3d2e4b7f1c6a5d9b0e3f2a1c4b7d6e5f8a9b0c1d2e3f4a5b6c7d8e9f0a1b2",
+                            "spends": "inputs",
+                            "inputs": [{
+                                "transaction": { "id": "3a2d4d3b0c1f6f2e4b9a8c7d6e5f4a3b2c1d0e9f8a7b6c5d4e3f2a1b0c9d8e7f" },
+                                "index": 0
+                            }],
+                            "outputs": [{
+                                "address": "addr_test1vz09v9yfxguvlp0zsnrpa3tdtm7el8xufp3m5lsm7qxzclgmzkket",
+                                "value": { "ada": { "lovelace": 1_000_000 } }
+                            }],
+                            "fee": { "ada": { "lovelace": 168_801 } },
+                            "signatories": [],
+                            "cbor": "84a300"
+                        }
+                    }
+                })
+            })
+            .await
+        };
+        let mut client = OgmiosWsClient::connect(server.url()).await.unwrap();
+
+        let response = client
+            .next_mempool_tx_with_options(NextTransactionOptions { include_cbor: true })
+            .await
+            .unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].0, "nextTransaction");
+        assert_eq!(requests[0].1, json!({ "fields": "all" }));
+        let result: Result<NextTransactionResult, _> = response.into();
+        match result.unwrap().transaction {
+            Some(MempoolTransaction::Tx(tx)) => assert_eq!(tx.cbor.as_deref(), Some("84a300")),
+            other => panic!("Expected a full transaction, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn next_blocks_pipelines_requests() {
+        const DEPTH: usize = 5;
+        let server = MockWsServer::start(|mut ws| async move {
+            // Only answer once every request has arrived, in reverse order, so the test fails
+            // unless the client sends all of them up front and correlates responses by id
+            let mut requests = vec![];
+            while requests.len() < DEPTH {
+                let Some(Ok(Message::Text(text))) = ws.next().await else {
+                    return;
+                };
+                requests.push(serde_json::from_str::<serde_json::Value>(&text).unwrap());
+            }
+            for (height, request) in requests.iter().enumerate().rev() {
+                let response = json!({
+                    "jsonrpc": "2.0",
+                    "method": "nextBlock",
+                    "result": {
+                        "direction": "forward",
+                        "block": {
+                            "type": "praos",
+                            "era": "conway",
+                            "id": format!("{:064x}", height),
+                            "ancestor": format!("{:064x}", height.saturating_sub(1)),
+                            "height": height,
+                            "slot": height * 20,
+                            "transactions": []
+                        },
+                        "tip": { "slot": 1000, "id": format!("{:064x}", 50) }
+                    },
+                    "id": request["id"]
+                });
+                ws.send(Message::Text(response.to_string().into()))
+                    .await
+                    .unwrap();
+            }
+        })
+        .await;
+        let mut client = OgmiosWsClient::connect(server.url()).await.unwrap();
+
+        let responses = client.next_blocks(DEPTH).await.unwrap();
+
+        let heights: Vec<u64> = responses
+            .into_iter()
+            .map(|response| match Result::from(response).unwrap() {
+                NextBlockResult::Forward { block, .. } => block.height,
+                other => panic!("Expected a roll forward, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(heights, vec![0, 1, 2, 3, 4]);
+    }
+
+    async fn mempool_server(methods: Arc<Mutex<Vec<String>>>) -> MockWsServer {
+        let remaining = Arc::new(Mutex::new(2));
+        MockWsServer::rpc(move |method, _| {
+            methods.lock().unwrap().push(method.to_string());
+            match method {
+                "acquireMempool" => json!({ "result": { "acquired": "mempool", "slot": 10 } }),
+                "releaseMempool" => json!({ "result": { "released": "mempool" } }),
+                "hasTransaction" => json!({ "result": true }),
+                "sizeOfMempool" => json!({
+                    "result": {
+                        "maxCapacity": { "bytes": 180224 },
+                        "currentSize": { "bytes": 1024 },
+                        "transactions": { "count": 2 }
+                    }
+                }),
+                "nextTransaction" => {
+                    let mut remaining = remaining.lock().unwrap();
+                    if *remaining == 0 {
+                        return json!({ "result": { "transaction": null } });
+                    }
+                    *remaining -= 1;
+                    json!({ "result": { "transaction": { "id": format!("{:064x}", *remaining) } } })
+                }
+                _ => json!({ "result": { "slot": 1, "id": format!("{:064x}", 1) } }),
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn drain_mempool_collects_and_releases() {
+        let methods = Arc::new(Mutex::new(vec![]));
+        let server = mempool_server(methods.clone()).await;
+        let mut client = OgmiosWsClient::connect(server.url()).await.unwrap();
+
+        let transactions = client.drain_mempool().await.unwrap();
+
+        let ids: Vec<_> = transactions
+            .iter()
+            .map(|transaction| match transaction {
+                MempoolTransaction::TxPointer(pointer) => pointer.id.clone(),
+                other => panic!("Expected a transaction id, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(ids, [format!("{:064x}", 1), format!("{:064x}", 0)]);
+        assert_eq!(
+            *methods.lock().unwrap(),
+            vec![
+                "acquireMempool",
+                "nextTransaction",
+                "nextTransaction",
+                "nextTransaction",
+                "releaseMempool",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn mempool_snapshot_released_on_drop() {
+        let methods = Arc::new(Mutex::new(vec![]));
+        let server = mempool_server(methods.clone()).await;
+        let mut client = OgmiosWsClient::connect(server.url()).await.unwrap();
+
+        {
+            let mut snapshot = MempoolSnapshot::acquire(&mut client).await.unwrap();
+            assert_eq!(snapshot.slot(), 10);
+            assert_eq!(snapshot.size().await.unwrap().transactions.count, 2);
+            assert!(snapshot.has(&format!("{:064x}", 1)).await.unwrap());
+            let transactions: Vec<_> = snapshot.transactions().collect().await;
+            assert_eq!(transactions.len(), 2);
+            assert!(transactions.iter().all(Result::is_ok));
+        }
+
+        let _: TipResponse = client
+            .request("queryNetwork/tip", None::<()>)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *methods.lock().unwrap(),
+            vec![
+                "acquireMempool",
+                "sizeOfMempool",
+                "hasTransaction",
+                "nextTransaction",
+                "nextTransaction",
+                "nextTransaction",
+                "releaseMempool",
+                "queryNetwork/tip",
+            ]
+        );
+        assert!(client.messages.is_empty());
+    }
+
+    #[tokio::test]
+    async fn connection_closed_names_awaited_request() {
+        let server = MockWsServer::start(|mut ws| async move {
+            ws.next().await;
+            ws.close(None).await.unwrap();
+        })
+        .await;
+        let mut client =
+            OgmiosWsClient::connect_with_id_generator(server.url(), IdGenerator::sequential())
+                .await
+                .unwrap();
+
+        let error = client.next_block().await.unwrap_err();
+        let closed = error.downcast_ref::<ConnectionClosed>().unwrap();
+        assert_eq!(closed.awaiting.method, "nextBlock");
+        assert_eq!(
+            error.to_string(),
+            "Connection closed while awaiting nextBlock (id 00000000000000000000)"
+        );
+    }
+
+    /// Drops the first connection after reading a request, then answers `queryLedgerState/epoch`
+    async fn restarting_server() -> MockWsServer {
+        let connections = Arc::new(AtomicUsize::new(0));
+        MockWsServer::start(move |mut ws| {
+            let connection = connections.fetch_add(1, Ordering::SeqCst);
+            async move {
+                while let Some(Ok(Message::Text(text))) = ws.next().await {
+                    if connection == 0 {
+                        ws.close(None).await.unwrap();
+                        return;
+                    }
+                    let request: Value = serde_json::from_str(&text).unwrap();
+                    let response = json!({
+                        "jsonrpc": "2.0",
+                        "method": "queryLedgerState/epoch",
+                        "result": 528,
+                        "id": request["id"]
+                    });
+                    ws.send(Message::Text(response.to_string().into()))
+                        .await
+                        .unwrap();
+                }
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn auto_reconnect_retries_once_on_closed_connection() {
+        let server = restarting_server().await;
+        let mut client = OgmiosWsClient::connect(server.url()).await.unwrap();
+        client.auto_reconnect(true);
+
+        let response = client.query_epoch().await.unwrap();
+        assert_eq!(Result::from(response), Ok(528));
+    }
+
+    #[tokio::test]
+    async fn failed_auto_reconnect_reports_the_lost_connection() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("ws://{}/", listener.local_addr().unwrap())).unwrap();
+        // Closes the only connection it accepts once a request is read
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            drop(listener);
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            ws.next().await;
+            ws.close(None).await.unwrap();
+        });
+        let mut client = OgmiosWsClient::connect(url).await.unwrap();
+        client.auto_reconnect(true);
+
+        let error = client.query_epoch().await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<ConnectError>(),
+            Some(ConnectError::Tcp(_))
+        ));
+        assert!(
+            format!("{:#}", error)
+                .contains("Connection closed while awaiting queryLedgerState/epoch"),
+            "{:#}",
+            error
+        );
+    }
+
+    #[tokio::test]
+    async fn auto_reconnect_skips_session_bound_and_submit_methods() {
+        let server = restarting_server().await;
+        let mut client = OgmiosWsClient::connect(server.url()).await.unwrap();
+        client.auto_reconnect(true);
+
+        let error = client
+            .request::<(), Value>("nextBlock", None)
+            .await
+            .unwrap_err();
+        assert!(error.downcast_ref::<ConnectionClosed>().is_some());
+
+        let error = client
+            .request::<_, Value>(
+                "submitTransaction",
+                Some(json!({ "transaction": { "cbor": "00" } })),
+            )
+            .await
+            .unwrap_err();
+        assert!(is_connection_lost(&error));
+    }
+
+    #[tokio::test]
+    async fn reconnect_after_closed_connection() {
+        let server = restarting_server().await;
+        let mut client = OgmiosWsClient::connect(server.url()).await.unwrap();
+
+        let error = client.query_epoch().await.unwrap_err();
+        assert!(error.downcast_ref::<ConnectionClosed>().is_some());
+
+        client.reconnect().await.unwrap();
+        let response = client.query_epoch().await.unwrap();
+        assert_eq!(Result::from(response), Ok(528));
+    }
+
+    #[tokio::test]
+    async fn slow_response_times_out() {
+        let server = MockWsServer::start(|mut ws| async move {
+            // Requests are read but never answered
+            while let Some(Ok(_)) = ws.next().await {}
+        })
+        .await;
+        let mut client =
+            OgmiosWsClient::connect_with_id_generator(server.url(), IdGenerator::sequential())
+                .await
+                .unwrap();
+        client.request_timeout(Some(Duration::from_millis(100)));
+
+        let error = client.query_epoch().await.unwrap_err();
+        let timeout = error.downcast_ref::<RequestTimeout>().unwrap();
+        assert_eq!(timeout.awaiting.method, "queryLedgerState/epoch");
+        assert_eq!(timeout.timeout, Duration::from_millis(100));
+        assert_eq!(
+            error.to_string(),
+            "No response within 100ms while awaiting queryLedgerState/epoch (id 00000000000000000000)"
+        );
+    }
+
+    #[tokio::test]
+    async fn late_response_after_timeout_is_discarded() {
+        let server = MockWsServer::start(|mut ws| async move {
+            let mut answered = 0;
+            while let Some(Ok(Message::Text(text))) = ws.next().await {
+                // The first response comes after the client stopped waiting for it
+                if answered == 0 {
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                }
+                answered += 1;
+                let request: Value = serde_json::from_str(&text).unwrap();
+                let response = json!({
+                    "jsonrpc": "2.0",
+                    "method": "queryLedgerState/epoch",
+                    "result": answered,
+                    "id": request["id"]
+                });
+                ws.send(Message::Text(response.to_string().into()))
+                    .await
+                    .unwrap();
+            }
+        })
+        .await;
+        let mut client = OgmiosWsClient::connect(server.url()).await.unwrap();
+        client.request_timeout(Some(Duration::from_millis(100)));
+
+        let error = client.query_epoch().await.unwrap_err();
+        assert!(error.downcast_ref::<RequestTimeout>().is_some());
+
+        client.request_timeout(None);
+        let response = client.query_epoch().await.unwrap();
+        assert_eq!(Result::from(response), Ok(2));
+        assert!(client.drain_buffered().is_empty());
+    }
+
+    #[tokio::test]
+    async fn sequential_request_ids() {
+        let ids = Arc::new(Mutex::new(vec![]));
+        let server = MockWsServer::start({
+            let ids = ids.clone();
+            move |mut ws| {
+                let ids = ids.clone();
+                async move {
+                    while let Some(Ok(Message::Text(text))) = ws.next().await {
+                        let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+                        ids.lock().unwrap().push(request["id"].clone());
+                        let response = json!({
+                            "jsonrpc": "2.0",
+                            "method": request["method"],
+                            "result": "origin",
+                            "id": request["id"]
+                        });
+                        ws.send(Message::Text(response.to_string().into()))
+                            .await
+                            .unwrap();
+                    }
+                }
+            }
+        })
+        .await;
+        let mut client =
+            OgmiosWsClient::connect_with_id_generator(server.url(), IdGenerator::sequential())
+                .await
+                .unwrap();
+
+        for _ in 0..3 {
+            let _: TipResponse = client
+                .request("queryNetwork/tip", None::<()>)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            *ids.lock().unwrap(),
+            vec![
+                json!("00000000000000000000"),
+                json!("00000000000000000001"),
+                json!("00000000000000000002"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_response_split_across_frames() {
+        use tokio_tungstenite::tungstenite::protocol::frame::Frame;
+        use tokio_tungstenite::tungstenite::protocol::frame::coding::{Data, OpCode};
+
+        let server = MockWsServer::start(|mut ws| async move {
+            let Some(Ok(Message::Text(text))) = ws.next().await else {
+                return;
+            };
+            let request: serde_json::Value = serde_json::from_str(&text).unwrap();
+            let response = json!({
+                "jsonrpc": "2.0",
+                "method": "queryNetwork/tip",
+                "result": { "slot": 1234, "id": "ab".repeat(32) },
+                "padding": "x".repeat(256 * 1024),
+                "id": request["id"]
+            })
+            .to_string();
+
+            ws.send(Message::Ping(vec![1, 2, 3].into())).await.unwrap();
+            let chunks: Vec<&[u8]> = response.as_bytes().chunks(4096).collect();
+            let last = chunks.len() - 1;
+            for (i, chunk) in chunks.into_iter().enumerate() {
+                let opcode = if i == 0 {
+                    OpCode::Data(Data::Text)
+                } else {
+                    OpCode::Data(Data::Continue)
+                };
+                let frame = Frame::message(chunk.to_vec(), opcode, i == last);
+                ws.send(Message::Frame(frame)).await.unwrap();
+            }
+            // Keep the connection open until the client is done
+            let _ = ws.next().await;
+        })
+        .await;
+        let mut client = OgmiosWsClient::connect(server.url()).await.unwrap();
+
+        let response: TipResponse = client
+            .request("queryNetwork/tip", None::<()>)
+            .await
+            .unwrap();
+
+        let tip = Result::from(response).unwrap();
+        assert!(matches!(tip, Tip::Point { slot: 1234, .. }));
+    }
+
+    #[tokio::test]
+    async fn connect_rejects_non_websocket_scheme() {
+        let url = Url::parse("http://127.0.0.1:1337").unwrap();
+        let error = OgmiosWsClient::connect(url).await.unwrap_err();
+        assert!(matches!(error, ConnectError::InvalidUrl(_)), "{:?}", error);
+    }
+
+    #[tokio::test]
+    async fn connect_reports_unresolvable_host() {
+        let url = Url::parse("ws://ogmios.invalid:1337").unwrap();
+        let error = OgmiosWsClient::connect(url).await.unwrap_err();
+        assert!(matches!(error, ConnectError::Dns(_)), "{:?}", error);
+    }
+
+    #[tokio::test]
+    async fn connect_reports_refused_connection() {
+        // Bind then drop a listener to get a port nothing listens on
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let url = Url::parse(&format!("ws://{}", addr)).unwrap();
+        let error = OgmiosWsClient::connect(url).await.unwrap_err();
+        assert!(matches!(error, ConnectError::Tcp(_)), "{:?}", error);
+    }
+
+    #[tokio::test]
+    async fn receives_messages_above_tungstenite_default_limit() {
+        // Larger than tungstenite's default 16 MiB frame limit, sent as a single frame
+        let server = MockWsServer::rpc(|_, _| {
+            json!({
+                "result": { "slot": 1234, "id": "ab".repeat(32) },
+                "padding": "x".repeat(17 << 20)
+            })
+        })
+        .await;
+
+        let mut client = OgmiosWsClient::connect_with_config(
+            server.url(),
+            WebSocketConfig::default()
+                .max_message_size(Some(32 << 20))
+                .max_frame_size(Some(32 << 20)),
+        )
+        .await
+        .unwrap();
+        let response: TipResponse = client
+            .request("queryNetwork/tip", None::<()>)
+            .await
+            .unwrap();
+        assert!(matches!(
+            Result::from(response),
+            Ok(Tip::Point { slot: 1234, .. })
+        ));
+
+        let mut client =
+            OgmiosWsClient::connect_with_config(server.url(), WebSocketConfig::default())
+                .await
+                .unwrap();
+        assert!(
+            client
+                .request::<(), TipResponse>("queryNetwork/tip", None)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn abandoned_responses_can_be_drained() {
+        let server = MockWsServer::rpc(|method, _| match method {
+            "queryLedgerState/epoch" => json!({ "result": 528 }),
+            _ => json!({ "result": { "slot": 10, "id": "ab".repeat(32) } }),
+        })
+        .await;
+        let mut client = OgmiosWsClient::connect(server.url()).await.unwrap();
+
+        // The epoch response is never read, it gets buffered while reading the tip
+        let abandoned = client
+            .send_request("queryLedgerState/epoch", None::<()>)
+            .await
+            .unwrap();
+        let id = client
+            .send_request("queryNetwork/tip", None::<()>)
+            .await
+            .unwrap();
+        let _: serde_json::Value = client.read_response("queryNetwork/tip", id).await.unwrap();
+
+        let buffered = client.drain_buffered();
+        assert_eq!(buffered.len(), 1);
+        assert_eq!(
+            buffered[0].0,
+            RpcResponseIdentifier {
+                method: "queryLedgerState/epoch".to_string(),
+                id: Some(abandoned),
+            }
+        );
+        let response: serde_json::Value = serde_json::from_str(&buffered[0].1).unwrap();
+        assert_eq!(response["result"], 528);
+        assert!(client.drain_buffered().is_empty());
+    }
+
+    async fn id_stripping_server() -> MockWsServer {
+        MockWsServer::start(|mut ws| async move {
+            while let Some(Ok(Message::Text(text))) = ws.next().await {
+                let request: Value = serde_json::from_str(&text).unwrap();
+                let response = json!({
+                    "jsonrpc": "2.0",
+                    "method": request["method"],
+                    "result": 528,
+                });
+                if ws
+                    .send(Message::Text(response.to_string().into()))
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn responses_without_id_accepted_when_not_required() {
+        let server = id_stripping_server().await;
+        let mut client = OgmiosWsClient::connect(server.url()).await.unwrap();
+        client.require_id_match(false);
+
+        let epoch: Result<u64, _> = client.query_epoch().await.unwrap().into();
+        assert_eq!(epoch, Ok(528));
+        let epoch: Result<u64, _> = client.query_epoch().await.unwrap().into();
+        assert_eq!(epoch, Ok(528));
+    }
+
+    #[tokio::test]
+    async fn responses_without_id_not_matched_by_default() {
+        let server = id_stripping_server().await;
+        let mut client = OgmiosWsClient::connect(server.url()).await.unwrap();
+
+        let result = tokio::time::timeout(Duration::from_millis(200), client.query_epoch()).await;
+        assert!(result.is_err());
+        let buffered = client.drain_buffered();
+        assert_eq!(buffered.len(), 1);
+        assert_eq!(buffered[0].0.id, None);
+    }
+
+    #[tokio::test]
+    async fn query_epoch() {
+        let server = MockWsServer::rpc(|method, _| {
+            assert_eq!(method, "queryLedgerState/epoch");
+            json!({ "result": 528 })
+        })
+        .await;
+        let mut client = OgmiosWsClient::connect(server.url()).await.unwrap();
+
+        let epoch: Result<u64, _> = client.query_epoch().await.unwrap().into();
+        assert_eq!(epoch, Ok(528));
+    }
+
+    #[tokio::test]
+    async fn mempool_snapshot_explicit_release() {
+        let methods = Arc::new(Mutex::new(vec![]));
+        let server = mempool_server(methods.clone()).await;
+        let mut client = OgmiosWsClient::connect(server.url()).await.unwrap();
+
+        let snapshot = MempoolSnapshot::acquire(&mut client).await.unwrap();
+        snapshot.release().await.unwrap();
+        assert!(!client.pending_mempool_release);
+        assert_eq!(
+            *methods.lock().unwrap(),
+            vec!["acquireMempool", "releaseMempool"]
+        );
+    }
+
+    #[tokio::test]
+    async fn mempool_queries_fail_after_release() {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let server = {
+            let requests = requests.clone();
+            MockWsServer::rpc(move |method, _| {
+                requests.lock().unwrap().push(method.to_string());
+                match method {
+                    "acquireMempool" => json!({ "result": { "acquired": "mempool", "slot": 10 } }),
+                    "releaseMempool" => json!({ "result": { "released": "mempool" } }),
+                    _ => json!({ "result": { "transaction": null } }),
+                }
+            })
+            .await
+        };
+        let mut client = OgmiosWsClient::connect(server.url()).await.unwrap();
+
+        client.acquire_mempool().await.unwrap();
+        let released = client.release_mempool().await.unwrap();
+        assert_eq!(released.released, "mempool");
+
+        let result: Result<NextTransactionResult, _> =
+            client.next_mempool_tx().await.unwrap().into();
+        assert!(matches!(
+            result,
+            Err(MempoolError::MustAcquireMempoolFirst { .. })
+        ));
+        assert_eq!(
+            *requests.lock().unwrap(),
+            ["acquireMempool", "releaseMempool"]
+        );
+
+        client.acquire_mempool().await.unwrap();
+        let result: Result<NextTransactionResult, _> =
+            client.next_mempool_tx().await.unwrap().into();
+        assert!(result.unwrap().transaction.is_none());
+    }
+
+    #[tokio::test]
+    async fn has_transaction_sends_id() {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let server = {
+            let requests = requests.clone();
+            MockWsServer::rpc(move |method, params| {
+                requests
+                    .lock()
+                    .unwrap()
+                    .push((method.to_string(), params.clone()));
+                match method {
+                    "hasTransaction" => json!({ "result": true }),
+                    _ => json!({ "result": { "released": "mempool" } }),
+                }
+            })
+            .await
+        };
+        let mut client = OgmiosWsClient::connect(server.url()).await.unwrap();
+        let tx_id = "e8a3d2e4b7f1c6a5d9b0e3f2a1c4b7d6e5f8a9b0c1d2e3f4a5b6c7d8e9f0a1b2";
+
+        assert!(client.has_transaction(tx_id).await.unwrap());
+        assert_eq!(
+            requests.lock().unwrap()[0],
+            ("hasTransaction".to_string(), json!({ "id": tx_id }))
+        );
+
+        client.release_mempool().await.unwrap();
+        let error = client.has_transaction(tx_id).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<MempoolError>(),
+            Some(MempoolError::MustAcquireMempoolFirst { .. })
+        ));
+    }
+}
@@ -0,0 +1,115 @@
+//! Replays recorded Ogmios responses from `tests/fixtures/<method>.json` through the response
+//! type of their method. A fixture holds one JSON-RPC response, or an array of them, and every
+//! response must decode into a success. Drop new payloads there when a node version changes.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use ogmios_client::codec::RpcResponse;
+use ogmios_client::method::chain_sync::NextBlockResponse;
+use ogmios_client::method::epoch::EpochResponse;
+use ogmios_client::method::evaluate::EvaluateResponse;
+use ogmios_client::method::pparams::{ProtocolParams, ProtocolParamsError};
+use ogmios_client::method::rewards::{
+    DelegationsAndRewardsResponse, RewardAccountSummariesResponse,
+};
+use ogmios_client::method::stake_distribution::StakeDistributionResponse;
+use ogmios_client::method::tip::TipResponse;
+use ogmios_client::method::utxo::UtxoResponse;
+use serde::de::DeserializeOwned;
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+fn decode_method(method: &str, response: serde_json::Value) -> Result<(), String> {
+    macro_rules! methods {
+        ($($name:literal => $response:ty),+ $(,)?) => {
+            match method {
+                $($name => <$response as ResponseType>::decode(response),)+
+                _ => Err(format!("no response type registered for method '{}'", method)),
+            }
+        };
+    }
+
+    methods! {
+        "evaluateTransaction" => EvaluateResponse,
+        "nextBlock" => NextBlockResponse,
+        "queryLedgerState/delegationsAndRewards" => DelegationsAndRewardsResponse,
+        "queryLedgerState/epoch" => EpochResponse,
+        "queryLedgerState/liveStakeDistribution" => StakeDistributionResponse,
+        "queryLedgerState/protocolParameters" => RpcResponse<ProtocolParams, ProtocolParamsError>,
+        "queryLedgerState/rewardAccountSummaries" => RewardAccountSummariesResponse,
+        "queryLedgerState/tip" => TipResponse,
+        "queryLedgerState/utxo" => UtxoResponse,
+    }
+}
+
+/// Decodes a response through a response type alias, requiring a success
+trait ResponseType {
+    fn decode(response: serde_json::Value) -> Result<(), String>;
+}
+
+impl<T: DeserializeOwned, E: DeserializeOwned + std::fmt::Debug> ResponseType
+    for RpcResponse<T, E>
+{
+    fn decode(response: serde_json::Value) -> Result<(), String> {
+        match serde_json::from_value::<Self>(response) {
+            Ok(RpcResponse::Success(_)) => Ok(()),
+            Ok(RpcResponse::Error(error)) => Err(format!("decoded as an error: {:?}", error.error)),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+fn fixture_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            fixture_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "json") {
+            files.push(path);
+        }
+    }
+}
+
+#[test]
+fn fixtures_decode() {
+    let root = Path::new(FIXTURES);
+    let mut files = vec![];
+    fixture_files(root, &mut files);
+    files.sort();
+    assert!(!files.is_empty(), "no fixtures found in {}", FIXTURES);
+
+    let mut failures = vec![];
+    for file in &files {
+        let method = file
+            .strip_prefix(root)
+            .unwrap()
+            .with_extension("")
+            .to_string_lossy()
+            .replace('\\', "/");
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(file).unwrap()).unwrap();
+        let responses = match json {
+            serde_json::Value::Array(responses) => responses,
+            response => vec![response],
+        };
+        for (i, response) in responses.into_iter().enumerate() {
+            if response["method"] != method.as_str() {
+                failures.push(format!(
+                    "{} [{}]: method is {}, expected '{}'",
+                    method, i, response["method"], method
+                ));
+                continue;
+            }
+            if let Err(e) = decode_method(&method, response) {
+                failures.push(format!("{} [{}]: {}", method, i, e));
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "fixtures failed to decode:\n{}",
+        failures.join("\n")
+    );
+}